@@ -0,0 +1,310 @@
+use mirl::render::Buffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// An area on screen that has to be presented again, see [`DearMirlGuiManager::draw_changed_on_buffer`](crate::DearMirlGuiManager::draw_changed_on_buffer)
+pub struct DirtyRect {
+    /// The left edge
+    pub x: isize,
+    /// The top edge
+    pub y: isize,
+    #[allow(missing_docs)]
+    pub width: usize,
+    #[allow(missing_docs)]
+    pub height: usize,
+}
+impl DirtyRect {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn new(position: (isize, isize), size: (usize, usize)) -> Self {
+        Self { x: position.0, y: position.1, width: size.0, height: size.1 }
+    }
+    /// The area a window takes up on screen
+    #[must_use]
+    pub const fn of_window<const FAST: bool, const USE_CACHE: bool>(
+        window: &crate::DearMirlGui<FAST, USE_CACHE>,
+    ) -> Self {
+        Self::new(
+            (window.x as isize, window.y as isize),
+            (window.get_width(), window.get_height()),
+        )
+    }
+    /// The right edge, exclusive
+    #[must_use]
+    pub const fn right(&self) -> isize {
+        self.x + self.width as isize
+    }
+    /// The bottom edge, exclusive
+    #[must_use]
+    pub const fn bottom(&self) -> isize {
+        self.y + self.height as isize
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+    const fn from_edges(
+        left: isize,
+        top: isize,
+        right: isize,
+        bottom: isize,
+    ) -> Self {
+        Self::new(
+            (left, top),
+            ((right - left).max(0) as usize, (bottom - top).max(0) as usize),
+        )
+    }
+    /// The area both rectangles cover, None if they don't overlap
+    #[must_use]
+    pub const fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Self::from_edges(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        );
+        if rect.is_empty() { None } else { Some(rect) }
+    }
+    /// The smallest rectangle containing both
+    #[must_use]
+    pub const fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Self::from_edges(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        )
+    }
+    /// Check if the other rectangle lies completely inside this one
+    #[must_use]
+    pub const fn contains(&self, other: &Self) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+    /// The parts of this rectangle not covered by the other one (up to 4)
+    #[must_use]
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(covered) = self.intersection(other) else {
+            return Vec::from([*self]);
+        };
+        [
+            // Above and below span the full width, left and right only the covered rows
+            Self::from_edges(self.x, self.y, self.right(), covered.y),
+            Self::from_edges(
+                self.x,
+                covered.bottom(),
+                self.right(),
+                self.bottom(),
+            ),
+            Self::from_edges(self.x, covered.y, covered.x, covered.bottom()),
+            Self::from_edges(
+                covered.right(),
+                covered.y,
+                self.right(),
+                covered.bottom(),
+            ),
+        ]
+        .into_iter()
+        .filter(|rect| !rect.is_empty())
+        .collect()
+    }
+}
+
+/// The parts of the area that aren't covered by any of the given rectangles
+#[must_use]
+pub fn get_uncovered_areas(
+    area: DirtyRect,
+    covering: &[DirtyRect],
+) -> Vec<DirtyRect> {
+    let mut visible = Vec::from([area]);
+    for cover in covering {
+        visible =
+            visible.iter().flat_map(|rect| rect.subtract(cover)).collect();
+        if visible.is_empty() {
+            break;
+        }
+    }
+    visible
+}
+
+/// Merge overlapping rectangles until none overlap, empty ones are dropped
+#[must_use]
+pub fn merge_dirty_rects(rects: Vec<DirtyRect>) -> Vec<DirtyRect> {
+    let mut merged: Vec<DirtyRect> = Vec::new();
+    let mut pending: Vec<DirtyRect> =
+        rects.into_iter().filter(|rect| !rect.is_empty()).collect();
+    while let Some(mut rect) = pending.pop() {
+        // A grown rectangle may now overlap ones that have already been merged
+        while let Some(idx) =
+            merged.iter().position(|other| rect.intersection(other).is_some())
+        {
+            rect = rect.union(&merged.swap_remove(idx));
+        }
+        merged.push(rect);
+    }
+    merged
+}
+
+/// The part of the buffer positioned at `position` that lies within `area` and on the target
+fn get_drawn_area(
+    target: &Buffer,
+    source: &Buffer,
+    position: (isize, isize),
+    area: DirtyRect,
+) -> Option<DirtyRect> {
+    let screen = DirtyRect::new((0, 0), (target.width, target.height));
+    DirtyRect::new(position, (source.width, source.height))
+        .intersection(&area)
+        .and_then(|area| area.intersection(&screen))
+}
+
+/// Copy the part of an opaque buffer positioned at `position` that lies within `area` onto the target, all on screen
+pub fn draw_buffer_area_on_buffer(
+    target: &mut Buffer,
+    source: &Buffer,
+    position: (isize, isize),
+    area: DirtyRect,
+) {
+    let Some(area) = get_drawn_area(target, source, position, area) else {
+        return;
+    };
+    let source_x = (area.x - position.0) as usize;
+    for row in 0..area.height {
+        let source_start = (area.y - position.1) as usize * source.width
+            + row * source.width
+            + source_x;
+        let target_start =
+            (area.y as usize + row) * target.width + area.x as usize;
+        target.data[target_start..target_start + area.width].copy_from_slice(
+            &source.data[source_start..source_start + area.width],
+        );
+    }
+}
+
+/// Like [`draw_buffer_area_on_buffer`] but the buffer is blended onto the target using its alpha
+pub fn blend_buffer_area_on_buffer(
+    target: &mut Buffer,
+    source: &Buffer,
+    position: (isize, isize),
+    area: DirtyRect,
+) {
+    let Some(area) = get_drawn_area(target, source, position, area) else {
+        return;
+    };
+    let mut part = Buffer::new_empty((area.width, area.height));
+    draw_buffer_area_on_buffer(
+        &mut part,
+        source,
+        (position.0 - area.x, position.1 - area.y),
+        DirtyRect::new((0, 0), (area.width, area.height)),
+    );
+    mirl::render::draw_buffer_on_buffer::<true, true, true, false>(
+        target,
+        &part,
+        (area.x, area.y),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: isize, y: isize, width: usize, height: usize) -> DirtyRect {
+        DirtyRect::new((x, y), (width, height))
+    }
+    fn area(rects: &[DirtyRect]) -> usize {
+        rects.iter().map(|rect| rect.width * rect.height).sum()
+    }
+    fn overlap(rects: &[DirtyRect]) -> bool {
+        rects.iter().enumerate().any(|(idx, rect)| {
+            rects[idx + 1..]
+                .iter()
+                .any(|other| rect.intersection(other).is_some())
+        })
+    }
+
+    #[test]
+    fn subtract_leaves_the_uncovered_parts() {
+        let base = rect(0, 0, 10, 10);
+        // A hole in the middle leaves all 4 sides
+        let parts = base.subtract(&rect(3, 3, 4, 4));
+        assert_eq!(parts.len(), 4);
+        assert_eq!(area(&parts), 100 - 16);
+        assert!(!overlap(&parts));
+        assert!(parts.iter().all(|part| base.contains(part)));
+        // Overlapping a corner
+        let parts = base.subtract(&rect(5, -5, 10, 10));
+        assert_eq!(area(&parts), 100 - 25);
+        assert!(!overlap(&parts));
+        // Not touching and fully covering
+        assert_eq!(base.subtract(&rect(10, 0, 5, 5)), [base]);
+        assert!(base.subtract(&rect(-1, -1, 12, 12)).is_empty());
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = rect(0, 0, 10, 5);
+        let b = rect(5, 2, 10, 10);
+        assert_eq!(a.union(&b), rect(0, 0, 15, 12));
+        assert_eq!(a.intersection(&b), Some(rect(5, 2, 5, 3)));
+        assert_eq!(a.intersection(&rect(10, 0, 5, 5)), None);
+        // Empty rectangles don't grow the union
+        assert_eq!(a.union(&rect(100, 100, 0, 0)), a);
+        assert_eq!(rect(-5, -5, 0, 3).union(&a), a);
+    }
+
+    #[test]
+    fn uncovered_areas_exclude_every_cover() {
+        let screen = rect(0, 0, 100, 100);
+        let covers = [rect(0, 0, 50, 100), rect(40, 0, 20, 50)];
+        let visible = get_uncovered_areas(screen, &covers);
+        assert_eq!(area(&visible), 100 * 100 - 50 * 100 - 10 * 50);
+        assert!(!overlap(&visible));
+        for part in &visible {
+            assert!(
+                covers.iter().all(|cover| part.intersection(cover).is_none())
+            );
+        }
+        assert!(get_uncovered_areas(rect(10, 10, 5, 5), &[screen]).is_empty());
+        assert_eq!(get_uncovered_areas(screen, &[]), [screen]);
+    }
+
+    #[test]
+    fn merged_rects_cover_everything_without_overlapping() {
+        let rects = Vec::from([
+            rect(0, 0, 10, 10),
+            rect(5, 5, 10, 10),
+            rect(30, 30, 5, 5),
+            // Only overlaps once the first two are merged
+            rect(12, 0, 5, 3),
+            rect(50, 50, 0, 10),
+        ]);
+        let mut merged = merge_dirty_rects(rects);
+        merged.sort_by_key(|rect| rect.x);
+        assert_eq!(merged, [rect(0, 0, 17, 15), rect(30, 30, 5, 5)]);
+        assert!(merge_dirty_rects(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn areas_are_copied_inside_the_target() {
+        let mut target = Buffer::new_empty_with_color((4, 4), 1);
+        let source = Buffer::new_empty_with_color((3, 3), 2);
+        // Partially off screen and limited to the area
+        draw_buffer_area_on_buffer(
+            &mut target,
+            &source,
+            (2, -1),
+            rect(0, 0, 4, 1),
+        );
+        assert_eq!(&target.data[..4], [1, 1, 2, 2]);
+        assert!(target.data[4..].iter().all(|pixel| *pixel == 1));
+    }
+}
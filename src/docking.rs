@@ -0,0 +1,818 @@
+use mirl::{
+    directions::NormalDirections,
+    extensions::*,
+    platform::CursorStyle,
+    render::{self, Buffer},
+};
+
+use crate::{DearMirlGui, FocusTaken, GuiOutput, ModuleUpdateInfo};
+
+/// Position and size of an area
+type Area = ((f32, f32), (f32, f32));
+/// The size, allowed resizing directions and auto sized axes of a window before it has been docked
+pub type UndockedState = ((usize, usize), NormalDirections, (bool, bool));
+
+fn get_window_area<const FAST: bool, const USE_CACHE: bool>(
+    window: &DearMirlGui<FAST, USE_CACHE>,
+) -> Area {
+    (
+        (window.x as f32, window.y as f32),
+        (window.get_width() as f32, window.get_height() as f32),
+    )
+}
+
+fn area_contains(area: Area, pos: (f32, f32)) -> bool {
+    let local = pos.sub(area.0);
+    local.0 >= 0.0 && local.1 >= 0.0 && local.0 < area.1.0 && local.1 < area.1.1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How a split node arranges its two children
+pub enum SplitDirection {
+    /// Side by side, the first node is on the left
+    Horizontal,
+    /// Stacked, the first node is on top
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where a window is docked relative to the target
+pub enum DockPosition {
+    /// Added as a tab
+    Center,
+    #[allow(missing_docs)]
+    Left,
+    #[allow(missing_docs)]
+    Right,
+    #[allow(missing_docs)]
+    Top,
+    #[allow(missing_docs)]
+    Bottom,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A node of the docking tree, windows are referenced by their id
+pub enum DockNode {
+    /// Windows stacked as tabs, only the active one is shown
+    Tabs {
+        /// The ids of the docked windows
+        windows: Vec<usize>,
+        /// The index of the shown window
+        active: usize,
+    },
+    /// Two nodes next to each other, separated by a draggable splitter
+    Split {
+        #[allow(missing_docs)]
+        direction: SplitDirection,
+        /// How much of the space the first node takes up (0.0..1.0)
+        ratio: f32,
+        #[allow(missing_docs)]
+        first: Box<Self>,
+        #[allow(missing_docs)]
+        second: Box<Self>,
+    },
+}
+impl DockNode {
+    /// Check if the window is docked somewhere in this node
+    #[must_use]
+    pub fn contains(&self, window: usize) -> bool {
+        match self {
+            Self::Tabs { windows, .. } => windows.contains(&window),
+            Self::Split { first, second, .. } => {
+                first.contains(window) || second.contains(window)
+            }
+        }
+    }
+    /// Check if the window is docked and its tab is the active one
+    #[must_use]
+    pub fn is_shown(&self, window: usize) -> bool {
+        match self {
+            Self::Tabs { windows, active } => {
+                windows.get(*active) == Some(&window)
+            }
+            Self::Split { first, second, .. } => {
+                first.is_shown(window) || second.is_shown(window)
+            }
+        }
+    }
+    /// Remove a window, is None when nothing is left
+    ///
+    /// Splits with an empty side are replaced by the other side
+    #[must_use]
+    pub fn remove(self, window: usize) -> Option<Self> {
+        match self {
+            Self::Tabs { mut windows, active } => {
+                windows.retain(|id| *id != window);
+                if windows.is_empty() {
+                    return None;
+                }
+                let active = active.min(windows.len() - 1);
+                Some(Self::Tabs { windows, active })
+            }
+            Self::Split { direction, ratio, first, second } => {
+                match (first.remove(window), second.remove(window)) {
+                    (Some(first), Some(second)) => Some(Self::Split {
+                        direction,
+                        ratio,
+                        first: Box::new(first),
+                        second: Box::new(second),
+                    }),
+                    (Some(node), None) | (None, Some(node)) => Some(node),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+    /// Dock a window next to this node or add it as a tab
+    #[must_use]
+    pub fn dock(self, window: usize, position: DockPosition) -> Self {
+        if position == DockPosition::Center {
+            return match self {
+                Self::Tabs { mut windows, .. } => {
+                    windows.push(window);
+                    let active = windows.len() - 1;
+                    Self::Tabs { windows, active }
+                }
+                // There are no tabs to add to
+                split @ Self::Split { .. } => {
+                    split.dock(window, DockPosition::Right)
+                }
+            };
+        }
+        self.dock_node(
+            Self::Tabs { windows: Vec::from([window]), active: 0 },
+            position,
+        )
+    }
+    /// Dock a whole node next to this node, [`DockPosition::Center`] docks it to the right as nodes can't be added as a tab
+    #[must_use]
+    pub fn dock_node(self, new: Self, position: DockPosition) -> Self {
+        let (direction, new_first) = match position {
+            DockPosition::Left => (SplitDirection::Horizontal, true),
+            DockPosition::Right | DockPosition::Center => {
+                (SplitDirection::Horizontal, false)
+            }
+            DockPosition::Top => (SplitDirection::Vertical, true),
+            DockPosition::Bottom => (SplitDirection::Vertical, false),
+        };
+        let (first, second) = if new_first { (new, self) } else { (self, new) };
+        Self::Split {
+            direction,
+            ratio: 0.5,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+    /// Dock a window next to the node containing the target window
+    #[must_use]
+    pub fn dock_at(
+        self,
+        target: usize,
+        window: usize,
+        position: DockPosition,
+    ) -> Self {
+        match self {
+            Self::Tabs { .. } if self.contains(target) => {
+                self.dock(window, position)
+            }
+            Self::Split { direction, ratio, first, second } => Self::Split {
+                direction,
+                ratio,
+                first: Box::new(first.dock_at(target, window, position)),
+                second: Box::new(second.dock_at(target, window, position)),
+            },
+            tabs @ Self::Tabs { .. } => tabs,
+        }
+    }
+    /// Get a child node, `true` meaning the second node of a split
+    #[must_use]
+    pub fn get_mut(&mut self, path: &[bool]) -> Option<&mut Self> {
+        let Some((second, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        match self {
+            Self::Split { first, second: other, .. } => {
+                let node = if *second { other } else { first };
+                node.get_mut(rest)
+            }
+            Self::Tabs { .. } => None,
+        }
+    }
+    fn layout(
+        &self,
+        area: Area,
+        splitter_size: f32,
+        path: &mut Vec<bool>,
+        output: &mut DockLayout,
+    ) {
+        match self {
+            Self::Tabs { windows, active } => {
+                output.tabs.push((windows.clone(), *active, area));
+            }
+            Self::Split { direction, ratio, first, second } => {
+                let (pos, size) = area;
+                let (first_area, splitter, second_area) = match direction {
+                    SplitDirection::Horizontal => {
+                        let width = (size.0 - splitter_size).max(0.0);
+                        let first_width = (width * ratio).floor();
+                        (
+                            (pos, (first_width, size.1)),
+                            (
+                                pos.add((first_width, 0.0)),
+                                (splitter_size, size.1),
+                            ),
+                            (
+                                pos.add((first_width + splitter_size, 0.0)),
+                                (width - first_width, size.1),
+                            ),
+                        )
+                    }
+                    SplitDirection::Vertical => {
+                        let height = (size.1 - splitter_size).max(0.0);
+                        let first_height = (height * ratio).floor();
+                        (
+                            (pos, (size.0, first_height)),
+                            (
+                                pos.add((0.0, first_height)),
+                                (size.0, splitter_size),
+                            ),
+                            (
+                                pos.add((0.0, first_height + splitter_size)),
+                                (size.0, height - first_height),
+                            ),
+                        )
+                    }
+                };
+                output.splitters.push((
+                    path.clone(),
+                    *direction,
+                    area,
+                    splitter,
+                ));
+                path.push(false);
+                first.layout(first_area, splitter_size, path, output);
+                path.pop();
+                path.push(true);
+                second.layout(second_area, splitter_size, path, output);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Where everything of a [`DockSpace`] is on screen
+struct DockLayout {
+    /// Tab groups: Window ids, active tab, area
+    tabs: Vec<(Vec<usize>, usize, Area)>,
+    /// Splitters: Path to the split node, direction, area of the split node, area of the splitter
+    splitters: Vec<(Vec<bool>, SplitDirection, Area, Area)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Docked windows of the [`DearMirlGuiManager`](crate::DearMirlGuiManager), arranged in a tree of splits and tab groups
+///
+/// Drag a window by its title bar onto another window or an edge of the screen to dock it, drag it away to undock it
+///
+/// Dropping a window onto a floating window docks both of them into a new node
+pub struct DockSpace {
+    /// The root of the docking tree, is None when no window is docked
+    pub root: Option<DockNode>,
+    /// The top left corner of the dock space
+    pub position: (f32, f32),
+    /// The size of the dock space, the [`DearMirlGuiManager`](crate::DearMirlGuiManager) sets this to the size of the buffer it draws on
+    pub size: (f32, f32),
+    /// The thickness of the splitters between docked nodes
+    pub splitter_size: f32,
+    /// How close to the edges of the dock space a window has to be dropped to be docked to the edge
+    pub edge_size: f32,
+    /// The splitter currently being dragged
+    pub dragged_splitter: Option<Vec<bool>>,
+    /// The window that is currently being dragged
+    pub dragged_window: Option<usize>,
+    /// Where the dragged window would be docked when released: Target window (None = the dock space itself) and position
+    pub drop_target: Option<(Option<usize>, DockPosition)>,
+    /// The size, allowed resizing directions and auto sized axes windows had before they have been docked
+    pub undocked_state: std::collections::BTreeMap<usize, UndockedState>,
+    /// The color of the drop preview
+    pub preview_color: u32,
+}
+impl Default for DockSpace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl DockSpace {
+    /// The default thickness of the splitters
+    pub const DEFAULT_SPLITTER_SIZE: f32 = 4.0;
+    /// The default distance to the edges for docking to them
+    pub const DEFAULT_EDGE_SIZE: f32 = 32.0;
+    /// Half transparent blue
+    pub const DEFAULT_PREVIEW_COLOR: u32 =
+        mirl::graphics::rgba_to_u32(60, 120, 220, 100);
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            root: None,
+            position: (0.0, 0.0),
+            size: (0.0, 0.0),
+            splitter_size: Self::DEFAULT_SPLITTER_SIZE,
+            edge_size: Self::DEFAULT_EDGE_SIZE,
+            dragged_splitter: None,
+            dragged_window: None,
+            drop_target: None,
+            undocked_state: std::collections::BTreeMap::new(),
+            preview_color: Self::DEFAULT_PREVIEW_COLOR,
+        }
+    }
+    /// Check if the window with the given id is docked
+    #[must_use]
+    pub fn is_docked(&self, window: usize) -> bool {
+        self.root.as_ref().is_some_and(|root| root.contains(window))
+    }
+    /// Check if the window is docked in a tab that isn't active - Hidden windows are neither updated nor drawn
+    #[must_use]
+    pub fn is_hidden(&self, window: usize) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| root.contains(window) && !root.is_shown(window))
+    }
+    /// The height of the tab bar shown above tab groups with more than one window
+    #[must_use]
+    pub fn get_tab_height() -> usize {
+        let formatting = crate::module_manager::get_formatting();
+        formatting.height + formatting.vertical_margin * 2
+    }
+    fn get_tab_width<const FAST: bool, const USE_CACHE: bool>(
+        window: Option<&DearMirlGui<FAST, USE_CACHE>>,
+    ) -> f32 {
+        let formatting = crate::module_manager::get_formatting();
+        let title_width = window.map_or(0.0, |window| {
+            render::get_text_width(
+                &window.title,
+                formatting.height as f32,
+                &formatting.font,
+            )
+        });
+        (formatting.horizontal_margin as f32).mul_add(4.0, title_width)
+    }
+    fn get_layout(&self) -> DockLayout {
+        let mut layout = DockLayout::default();
+        if let Some(root) = &self.root {
+            root.layout(
+                (self.position, self.size),
+                self.splitter_size,
+                &mut Vec::new(),
+                &mut layout,
+            );
+        }
+        layout
+    }
+    /// Dock a window next to the target window (None = the dock space itself)
+    pub fn dock<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        window: &mut DearMirlGui<FAST, USE_CACHE>,
+        target: Option<usize>,
+        position: DockPosition,
+    ) {
+        if self.is_docked(window.id) {
+            self.undock(window);
+        }
+        let root = match (self.root.take(), target) {
+            (None, _) => {
+                DockNode::Tabs { windows: Vec::from([window.id]), active: 0 }
+            }
+            (Some(root), None) => root.dock(window.id, position),
+            (Some(root), Some(target)) => {
+                root.dock_at(target, window.id, position)
+            }
+        };
+        // The target window isn't docked, fall back to the dock space itself
+        self.root = Some(if root.contains(window.id) {
+            root
+        } else {
+            root.dock(window.id, position)
+        });
+        self.take_over_size(window);
+    }
+    /// Dock a window together with a floating (undocked) target window, both end up in a new node to the right of the already docked windows
+    ///
+    /// Docks next to the target like [`dock`](Self::dock) if the target is already docked
+    pub fn dock_with_floating<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        window: &mut DearMirlGui<FAST, USE_CACHE>,
+        target: &mut DearMirlGui<FAST, USE_CACHE>,
+        position: DockPosition,
+    ) {
+        if self.is_docked(target.id) {
+            self.dock(window, Some(target.id), position);
+            return;
+        }
+        if self.is_docked(window.id) {
+            self.undock(window);
+        }
+        let node =
+            DockNode::Tabs { windows: Vec::from([target.id]), active: 0 }
+                .dock(window.id, position);
+        self.root = Some(match self.root.take() {
+            None => node,
+            Some(root) => root.dock_node(node, DockPosition::Right),
+        });
+        self.take_over_size(target);
+        self.take_over_size(window);
+    }
+    /// Dock the window with the given id next to the target window (None = the dock space itself), floating targets are docked together with the window
+    ///
+    /// Returns false if there is no window with one of the ids
+    pub fn dock_by_id<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        windows: &mut [DearMirlGui<FAST, USE_CACHE>],
+        window: usize,
+        target: Option<usize>,
+        position: DockPosition,
+    ) -> bool {
+        let find =
+            |id: usize| windows.iter().position(|window| window.id == id);
+        let Some(window_idx) = find(window) else {
+            return false;
+        };
+        match target {
+            Some(target) if !self.is_docked(target) && target != window => {
+                let Some(target_idx) = find(target) else {
+                    return false;
+                };
+                let Ok([window, target]) =
+                    windows.get_disjoint_mut([window_idx, target_idx])
+                else {
+                    return false;
+                };
+                self.dock_with_floating(window, target, position);
+            }
+            _ => self.dock(&mut windows[window_idx], target, position),
+        }
+        true
+    }
+    /// Remember the size of a newly docked window and let the dock space control it
+    fn take_over_size<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        window: &mut DearMirlGui<FAST, USE_CACHE>,
+    ) {
+        self.undocked_state.insert(
+            window.id,
+            (
+                (window.width, window.height),
+                window.resizing_allowed_in_directions,
+                window.auto_size,
+            ),
+        );
+        // The size is dictated by the dock space now
+        window.resizing_allowed_in_directions = NormalDirections::all_false();
+        window.auto_size = (false, false);
+        window.collapsed = false;
+        window.needs_redraw = true;
+    }
+    /// Undock a window, restoring its previous size
+    pub fn undock<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        window: &mut DearMirlGui<FAST, USE_CACHE>,
+    ) {
+        self.root = self.root.take().and_then(|root| root.remove(window.id));
+        if let Some(((width, height), directions, auto_size)) =
+            self.undocked_state.remove(&window.id)
+        {
+            window.width = width;
+            window.height = height;
+            window.resizing_allowed_in_directions = directions;
+            window.auto_size = auto_size;
+            window.needs_redraw = true;
+        }
+    }
+    /// Where a window dropped at the given position would be docked
+    ///
+    /// Floating windows are targets as well, the windows that are being dragged are skipped
+    #[must_use]
+    pub fn get_drop_target<const FAST: bool, const USE_CACHE: bool>(
+        &self,
+        pos: (f32, f32),
+        windows: &[DearMirlGui<FAST, USE_CACHE>],
+    ) -> Option<(Option<usize>, DockPosition)> {
+        if !area_contains((self.position, self.size), pos) {
+            return None;
+        }
+        let local = pos.sub(self.position);
+        let edge = if local.0 < self.edge_size {
+            Some(DockPosition::Left)
+        } else if local.0 >= self.size.0 - self.edge_size {
+            Some(DockPosition::Right)
+        } else if local.1 < self.edge_size {
+            Some(DockPosition::Top)
+        } else if local.1 >= self.size.1 - self.edge_size {
+            Some(DockPosition::Bottom)
+        } else {
+            None
+        };
+        if let Some(edge) = edge {
+            return Some((None, edge));
+        }
+        // Windows are sorted front to back, only the front most one can be the target
+        let hovered = windows.iter().find(|window| {
+            window.open
+                && !window.dragging
+                && !self.is_hidden(window.id)
+                && area_contains(get_window_area(window), pos)
+        });
+        if let Some(window) = hovered
+            && !self.is_docked(window.id)
+        {
+            let position = Self::get_position_in(get_window_area(window), pos);
+            return Some((Some(window.id), position));
+        }
+        let (windows, active, area) = self
+            .get_layout()
+            .tabs
+            .into_iter()
+            .find(|(_, _, area)| area_contains(*area, pos))?;
+        Some((windows.get(active).copied(), Self::get_position_in(area, pos)))
+    }
+    /// The center docks as a tab, the outer quarters split the node
+    fn get_position_in(area: Area, pos: (f32, f32)) -> DockPosition {
+        let relative = pos.sub(area.0).div(area.1);
+        if relative.0 < 0.25 {
+            DockPosition::Left
+        } else if relative.0 >= 0.75 {
+            DockPosition::Right
+        } else if relative.1 < 0.25 {
+            DockPosition::Top
+        } else if relative.1 >= 0.75 {
+            DockPosition::Bottom
+        } else {
+            DockPosition::Center
+        }
+    }
+    /// The area a window would take up when docked at the drop target
+    fn get_preview_area(&self, target: (Option<usize>, DockPosition)) -> Area {
+        let whole = (self.position, self.size);
+        let area = match target.0 {
+            // The first docked window always fills the dock space
+            None if self.root.is_none() => return whole,
+            None => whole,
+            Some(target) if self.is_docked(target) => self
+                .get_layout()
+                .tabs
+                .into_iter()
+                .find(|(windows, _, _)| windows.contains(&target))
+                .map_or(whole, |(_, _, area)| area),
+            // Floating targets get a new node to the right of the docked windows
+            Some(_) if self.root.is_none() => whole,
+            Some(_) => {
+                let half = self.size.0 / 2.0;
+                (self.position.add((half, 0.0)), (half, self.size.1))
+            }
+        };
+        let (pos, size) = area;
+        let half = size.div((2.0, 2.0));
+        match target.1 {
+            DockPosition::Center => area,
+            DockPosition::Left => (pos, (half.0, size.1)),
+            DockPosition::Right => (pos.add((half.0, 0.0)), (half.0, size.1)),
+            DockPosition::Top => (pos, (size.0, half.1)),
+            DockPosition::Bottom => (pos.add((0.0, half.1)), (size.0, half.1)),
+        }
+    }
+    /// Handle the tab bars and splitters, call before updating the windows
+    pub fn update<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        inputs: &ModuleUpdateInfo,
+        windows: &[DearMirlGui<FAST, USE_CACHE>],
+    ) -> GuiOutput {
+        let Some(mouse_pos) = inputs.mouse_pos else {
+            return GuiOutput::empty();
+        };
+        let layout = self.get_layout();
+        if let Some(path) = self.dragged_splitter.clone() {
+            if !inputs.mouse_info.left.down {
+                self.dragged_splitter = None;
+                return GuiOutput::empty();
+            }
+            let Some((_, direction, (pos, size), _)) =
+                layout.splitters.iter().find(|(other, ..)| *other == path)
+            else {
+                self.dragged_splitter = None;
+                return GuiOutput::empty();
+            };
+            let (cursor_style, ratio) = match direction {
+                SplitDirection::Horizontal => (
+                    CursorStyle::ResizeHorizontally,
+                    (mouse_pos.0 - pos.0) / size.0,
+                ),
+                SplitDirection::Vertical => (
+                    CursorStyle::ResizeVertically,
+                    (mouse_pos.1 - pos.1) / size.1,
+                ),
+            };
+            if let Some(DockNode::Split { ratio: current, .. }) =
+                self.root.as_mut().and_then(|root| root.get_mut(&path))
+            {
+                *current = ratio.clamp(0.1, 0.9);
+            }
+            return GuiOutput::default(FocusTaken::FunctionallyTaken)
+                .with_cursor(cursor_style);
+        }
+        if inputs.focus_taken.is_focus_taken() {
+            return GuiOutput::empty();
+        }
+        for (path, direction, _, splitter) in &layout.splitters {
+            if !area_contains(*splitter, mouse_pos) {
+                continue;
+            }
+            let cursor_style = match direction {
+                SplitDirection::Horizontal => CursorStyle::ResizeHorizontally,
+                SplitDirection::Vertical => CursorStyle::ResizeVertically,
+            };
+            if inputs.mouse_info.left.clicked {
+                self.dragged_splitter = Some(path.clone());
+                return GuiOutput::default(FocusTaken::FunctionallyTaken)
+                    .with_cursor(cursor_style);
+            }
+            return GuiOutput::default(FocusTaken::VisuallyTaken)
+                .with_cursor(cursor_style);
+        }
+        let tab_height = Self::get_tab_height() as f32;
+        for (tab_windows, _, (pos, size)) in &layout.tabs {
+            if tab_windows.len() < 2
+                || !area_contains((*pos, (size.0, tab_height)), mouse_pos)
+            {
+                continue;
+            }
+            let mut x = pos.0;
+            for (idx, id) in tab_windows.iter().enumerate() {
+                let width = Self::get_tab_width(
+                    windows.iter().find(|window| window.id == *id),
+                );
+                if (x..x + width).contains(&mouse_pos.0) {
+                    if inputs.mouse_info.left.clicked
+                        && let Some(root) = self.root.as_mut()
+                    {
+                        Self::set_active_tab(root, *id, idx);
+                        return GuiOutput::default(
+                            FocusTaken::FunctionallyTaken,
+                        )
+                        .with_cursor(CursorStyle::Pointer);
+                    }
+                    return GuiOutput::default(FocusTaken::VisuallyTaken)
+                        .with_cursor(CursorStyle::Pointer);
+                }
+                x += width;
+            }
+            return GuiOutput::default(FocusTaken::VisuallyTaken);
+        }
+        GuiOutput::empty()
+    }
+    fn set_active_tab(node: &mut DockNode, window: usize, idx: usize) {
+        match node {
+            DockNode::Tabs { windows, active } => {
+                if windows.get(idx) == Some(&window) {
+                    *active = idx;
+                }
+            }
+            DockNode::Split { first, second, .. } => {
+                Self::set_active_tab(first, window, idx);
+                Self::set_active_tab(second, window, idx);
+            }
+        }
+    }
+    /// Undock dragged windows and dock them where they are released, call after updating the windows
+    pub fn update_dragging<const FAST: bool, const USE_CACHE: bool>(
+        &mut self,
+        inputs: &ModuleUpdateInfo,
+        windows: &mut [DearMirlGui<FAST, USE_CACHE>],
+    ) {
+        let mut dragged = None;
+        for window in windows.iter_mut() {
+            if !window.dragging {
+                continue;
+            }
+            // Only undock once the window is actually moved, not when just clicking the title bar
+            if self.is_docked(window.id) && inputs.mouse_pos_delta != (0.0, 0.0)
+            {
+                self.undock(window);
+            }
+            if !self.is_docked(window.id) {
+                dragged = Some(window.id);
+            }
+        }
+        if let Some(released) = self.dragged_window
+            && dragged != Some(released)
+            && let Some((target, position)) = self.drop_target
+        {
+            self.dock_by_id(windows, released, target, position);
+        }
+        self.dragged_window = dragged;
+        self.drop_target = dragged
+            .and(inputs.mouse_pos)
+            .and_then(|pos| self.get_drop_target(pos, windows));
+    }
+    /// Move and resize the docked windows to fit their nodes
+    pub fn apply_layout<const FAST: bool, const USE_CACHE: bool>(
+        &self,
+        windows: &mut [DearMirlGui<FAST, USE_CACHE>],
+    ) {
+        let tab_height = Self::get_tab_height() as f32;
+        for (tab_windows, _, (pos, size)) in self.get_layout().tabs {
+            let offset = if tab_windows.len() > 1 { tab_height } else { 0.0 };
+            for window in windows
+                .iter_mut()
+                .filter(|window| tab_windows.contains(&window.id))
+            {
+                let x = pos.0 as crate::DearMirlGuiCoordinateType;
+                let y = (pos.1 + offset) as crate::DearMirlGuiCoordinateType;
+                let width = size.0 as usize;
+                let height = (size.1 - offset).max(0.0) as usize;
+                if (window.x, window.y, window.width, window.height)
+                    != (x, y, width, height)
+                {
+                    window.x = x;
+                    window.y = y;
+                    window.width = width;
+                    window.height = height;
+                    window.needs_redraw = true;
+                }
+            }
+        }
+    }
+    /// Draw the tab bars and splitters, call before drawing the windows
+    pub fn draw<const FAST: bool, const USE_CACHE: bool>(
+        &self,
+        buffer: &mut Buffer,
+        windows: &[DearMirlGui<FAST, USE_CACHE>],
+    ) {
+        let formatting = crate::module_manager::get_formatting();
+        let layout = self.get_layout();
+        for (_, _, _, (pos, size)) in &layout.splitters {
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                (pos.0 as isize, pos.1 as isize),
+                (size.0 as isize, size.1 as isize),
+                formatting.foreground_color,
+            );
+        }
+        let tab_height = Self::get_tab_height();
+        for (tab_windows, active, (pos, size)) in &layout.tabs {
+            if tab_windows.len() < 2 {
+                continue;
+            }
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                (pos.0 as isize, pos.1 as isize),
+                (size.0 as isize, tab_height as isize),
+                formatting.background_color,
+            );
+            let mut x = pos.0;
+            for (idx, id) in tab_windows.iter().enumerate() {
+                let window = windows.iter().find(|window| window.id == *id);
+                let width = Self::get_tab_width(window);
+                if idx == *active {
+                    render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                        buffer,
+                        (x as isize, pos.1 as isize),
+                        (width as isize, tab_height as isize),
+                        formatting.foreground_color,
+                    );
+                }
+                if let Some(window) = window {
+                    render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                        buffer,
+                        &window.title,
+                        (
+                            (x as usize + formatting.horizontal_margin * 2)
+                                as isize,
+                            (pos.1 as usize + formatting.vertical_margin)
+                                as isize,
+                        ),
+                        formatting.text_color,
+                        formatting.height as f32,
+                        &formatting.font,
+                    );
+                }
+                x += width;
+            }
+        }
+    }
+    /// Show where the dragged window would be docked, call after drawing the windows
+    pub fn draw_drop_preview(&self, buffer: &mut Buffer) {
+        let Some(target) = self.drop_target else {
+            return;
+        };
+        let (pos, size) = self.get_preview_area(target);
+        let preview = Buffer::new_empty_with_color(
+            (size.0 as usize, size.1 as usize),
+            self.preview_color,
+        );
+        render::draw_buffer_on_buffer::<true, true, true, false>(
+            buffer,
+            &preview,
+            (pos.0 as isize, pos.1 as isize),
+        );
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 /// The current formatting for a window/its modules
 pub struct Formatting {
     /// The font in use
@@ -17,6 +17,36 @@ pub struct Formatting {
     pub horizontal_margin: usize,
     /// How many pixels there should be between each element of a module vertically
     pub vertical_margin: usize,
+    /// The colors of widget states and how their shapes are drawn
+    pub theme: crate::Theme,
+}
+// The font has no PartialEq, both compare its file hash instead
+impl PartialEq for Formatting {
+    fn eq(&self, other: &Self) -> bool {
+        self.font.file_hash() == other.font.file_hash()
+            && self.height == other.height
+            && self.background_color == other.background_color
+            && self.foreground_color == other.foreground_color
+            && self.text_color == other.text_color
+            && self.misc_ui_color == other.misc_ui_color
+            && self.horizontal_margin == other.horizontal_margin
+            && self.vertical_margin == other.vertical_margin
+            && self.theme == other.theme
+    }
+}
+impl Eq for Formatting {}
+impl std::hash::Hash for Formatting {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.font.file_hash().hash(state);
+        self.height.hash(state);
+        self.background_color.hash(state);
+        self.foreground_color.hash(state);
+        self.text_color.hash(state);
+        self.misc_ui_color.hash(state);
+        self.horizontal_margin.hash(state);
+        self.vertical_margin.hash(state);
+        self.theme.hash(state);
+    }
 }
 impl Formatting {
     /// The color of the background
@@ -57,6 +87,7 @@ impl Formatting {
             vertical_margin: vertical_margin
                 .unwrap_or(Self::DEFAULT_VERTICAL_MARGIN)
                 / 2,
+            theme: crate::Theme::dark(),
         }
     }
     /// Create a formatting instance and fill in all empty values with defaults
@@ -74,8 +105,49 @@ impl Formatting {
             misc_ui_color: 0,
             horizontal_margin: Self::DEFAULT_HORIZONTAL_MARGIN / 2,
             vertical_margin: Self::DEFAULT_VERTICAL_MARGIN / 2,
+            theme: crate::Theme::dark(),
         }
     }
+    /// The default formatting, dark with purple elements
+    #[must_use]
+    pub fn dark(
+        font: &mirl::dependencies::fontdue::Font,
+        height: usize,
+    ) -> Self {
+        Self::default(font, height)
+    }
+    /// Dark text and elements on a bright background
+    #[must_use]
+    pub fn light(
+        font: &mirl::dependencies::fontdue::Font,
+        height: usize,
+    ) -> Self {
+        Self::default(font, height)
+            .background_color(mirl::graphics::rgba_to_u32(240, 240, 245, 255))
+            .foreground_color(mirl::graphics::rgba_to_u32(200, 205, 220, 255))
+            .text_color(mirl::graphics::rgba_to_u32(20, 20, 30, 255))
+            .theme(crate::Theme::light())
+    }
+    /// White text and outlines on black for the best readability
+    #[must_use]
+    pub fn high_contrast(
+        font: &mirl::dependencies::fontdue::Font,
+        height: usize,
+    ) -> Self {
+        Self::default(font, height)
+            .background_color(mirl::graphics::colors::BLACK)
+            .foreground_color(mirl::graphics::colors::BLACK)
+            .text_color(mirl::graphics::colors::WHITE)
+            .theme(crate::Theme::high_contrast())
+    }
+    #[must_use]
+    /// The key module images drawn with this formatting are cached under, see [`get_image_cache`](crate::module_manager::get_image_cache)
+    pub fn get_cache_key(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
     #[must_use]
     /// Set the font
     pub fn font(mut self, font: mirl::dependencies::fontdue::Font) -> Self {
@@ -118,6 +190,13 @@ impl Formatting {
         self
     }
 
+    #[must_use]
+    /// Set the colors of widget states and how their shapes are drawn
+    pub const fn theme(mut self, theme: crate::Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     #[must_use]
     /// Set the vertical margin
     pub const fn vertical_margin(mut self, margin: usize) -> Self {
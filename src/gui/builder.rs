@@ -0,0 +1,661 @@
+use mirl::{directions::NormalDirections, misc::keybinds::KeyBind};
+
+use crate::{
+    DearMirlGui, Scrollbars, SmoothScroll, TitleBarButton, WindowStacking,
+    gui::Actions,
+    module_manager::{get_formatting, get_idx_of_id},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Why a [`DearMirlGuiBuilder`] couldn't build the window
+pub enum DearMirlGuiBuilderError {
+    /// The menu height is 0, the window couldn't be grabbed or collapsed
+    ZeroMenuHeight,
+    /// The window is less tall than its menu
+    HeightSmallerThanMenu {
+        #[allow(missing_docs)]
+        height: usize,
+        #[allow(missing_docs)]
+        menu_height: usize,
+    },
+    /// The window is narrower than the minimum width it was given
+    WidthSmallerThanMinimum {
+        #[allow(missing_docs)]
+        width: usize,
+        #[allow(missing_docs)]
+        min_width: usize,
+    },
+    /// The window is less tall than the minimum height it was given
+    HeightSmallerThanMinimum {
+        #[allow(missing_docs)]
+        height: usize,
+        #[allow(missing_docs)]
+        min_height: usize,
+    },
+    /// The maximum size is smaller than the minimum size on one of the axes
+    MaxSizeSmallerThanMinimum {
+        #[allow(missing_docs)]
+        max_size: (usize, usize),
+        #[allow(missing_docs)]
+        min_size: (usize, usize),
+    },
+    /// The window is bigger than the maximum size it was given
+    SizeBiggerThanMaximum {
+        #[allow(missing_docs)]
+        size: (usize, usize),
+        #[allow(missing_docs)]
+        max_size: (usize, usize),
+    },
+    /// The aspect ratio is not a positive finite number
+    InvalidAspectRatio(f32),
+    /// A scroll multiplier is NaN or infinite
+    InvalidScrollMultiplier(f32),
+    /// Smooth scrolling is enabled but doesn't move towards its target
+    InvalidSmoothScrollSpeed(f32),
+    /// No module with this id has been registered
+    UnknownModule(u32),
+}
+impl std::error::Error for DearMirlGuiBuilderError {}
+impl std::fmt::Display for DearMirlGuiBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroMenuHeight => {
+                write!(f, "The menu height of a window may not be 0")
+            }
+            Self::HeightSmallerThanMenu { height, menu_height } => write!(
+                f,
+                "The window height ({height}) is smaller than its menu height ({menu_height})"
+            ),
+            Self::WidthSmallerThanMinimum { width, min_width } => write!(
+                f,
+                "The window width ({width}) is smaller than its minimum width ({min_width})"
+            ),
+            Self::HeightSmallerThanMinimum { height, min_height } => write!(
+                f,
+                "The window height ({height}) is smaller than its minimum height ({min_height})"
+            ),
+            Self::MaxSizeSmallerThanMinimum { max_size, min_size } => write!(
+                f,
+                "The maximum size ({}x{}) is smaller than the minimum size ({}x{})",
+                max_size.0, max_size.1, min_size.0, min_size.1
+            ),
+            Self::SizeBiggerThanMaximum { size, max_size } => write!(
+                f,
+                "The window size ({}x{}) is bigger than its maximum size ({}x{})",
+                size.0, size.1, max_size.0, max_size.1
+            ),
+            Self::InvalidAspectRatio(ratio) => write!(
+                f,
+                "The aspect ratio {ratio} has to be a positive finite number"
+            ),
+            Self::InvalidScrollMultiplier(multiplier) => write!(
+                f,
+                "The scroll multiplier {multiplier} is not a finite number"
+            ),
+            Self::InvalidSmoothScrollSpeed(speed) => write!(
+                f,
+                "The smooth scroll speed {speed} has to be a positive number"
+            ),
+            Self::UnknownModule(id) => {
+                write!(f, "There is no registered module with the id {id}")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Configure every setting of a [`DearMirlGui`] by name instead of the positional arguments of [`DearMirlGui::new_advanced`]
+///
+/// ```ignore
+/// let window: DearMirlGui<false, true> = DearMirlGuiBuilder::new("Settings")
+///     .with_position((100, 10))
+///     .with_size((300, 200))
+///     .with_modules(&[text.id()])
+///     .with_dragging(false)
+///     .build()?;
+/// ```
+pub struct DearMirlGuiBuilder {
+    #[allow(missing_docs)]
+    pub title: String,
+    #[allow(missing_docs)]
+    pub position:
+        (crate::DearMirlGuiCoordinateType, crate::DearMirlGuiCoordinateType),
+    /// None = Big enough to see all modules
+    pub size: Option<(usize, usize)>,
+    #[allow(missing_docs)]
+    pub modules: Vec<u32>,
+    #[allow(missing_docs)]
+    pub toolbar_modules: Vec<u32>,
+    /// None = [`DearMirlGui::DEFAULT_MENU_HEIGHT`]
+    pub menu_height: Option<usize>,
+    /// None = Wide enough for the title
+    pub min_width: Option<usize>,
+    /// None = As tall as the menu
+    pub min_height: Option<usize>,
+    /// None = Unlimited
+    pub max_size: Option<(usize, usize)>,
+    /// Width divided by height, kept while resizing
+    pub aspect_ratio: Option<f32>,
+    /// The area the window is kept inside of while dragging and resizing
+    pub bounds: Option<crate::DirtyRect>,
+    /// If the width and height follow the size of the modules, see [`DearMirlGui::auto_size`]
+    pub auto_size: (bool, bool),
+    /// None = The global formatting
+    pub formatting: Option<crate::Formatting>,
+    #[allow(missing_docs)]
+    pub resizing_allowed_in_directions: NormalDirections,
+    #[allow(missing_docs)]
+    pub allow_dragging: bool,
+    #[allow(missing_docs)]
+    pub collapsed: bool,
+    /// The horizontal and vertical scroll multiplier, None = [`DearMirlGui::DEFAULT_VERTICAL_SCROLL_X_MULTIPLIER`]/[`DearMirlGui::DEFAULT_VERTICAL_SCROLL_Y_MULTIPLIER`]
+    pub scroll_multipliers: Option<(f32, f32)>,
+    /// None = [`DearMirlGui::HORIZONTAL_CONTEXT_SWITCHES_CAMERA_SCROLL_MULTIPLIERS`]
+    pub horizontal_context_switches_camera_scroll_multipliers: Option<bool>,
+    /// None = [`DearMirlGui::ALLOW_FREE_SCROLL`]
+    pub allow_free_scroll: Option<bool>,
+    /// None = [`get_default_keybinds`](crate::gui::get_default_keybinds)
+    pub keybinds: Option<Vec<KeyBind<Actions>>>,
+    #[allow(missing_docs)]
+    pub stacking: WindowStacking,
+    #[allow(missing_docs)]
+    pub scrollbars: Scrollbars,
+    #[allow(missing_docs)]
+    pub smooth_scroll: SmoothScroll,
+    #[allow(missing_docs)]
+    pub context_menu: Option<crate::modules::Menu>,
+    /// If the close button is shown
+    pub closable: bool,
+    /// If the pin button is shown
+    pub pinnable: bool,
+    #[allow(missing_docs)]
+    pub title_bar_buttons: Vec<TitleBarButton>,
+}
+impl DearMirlGuiBuilder {
+    /// Start building a window with the default settings of [`DearMirlGui::new_simple`]
+    #[must_use]
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            position: (
+                crate::DearMirlGuiCoordinateType::default(),
+                crate::DearMirlGuiCoordinateType::default(),
+            ),
+            size: None,
+            modules: Vec::new(),
+            toolbar_modules: Vec::new(),
+            menu_height: None,
+            min_width: None,
+            min_height: None,
+            max_size: None,
+            aspect_ratio: None,
+            bounds: None,
+            auto_size: (false, false),
+            formatting: None,
+            resizing_allowed_in_directions: NormalDirections::all_true(),
+            allow_dragging: true,
+            collapsed: false,
+            scroll_multipliers: None,
+            horizontal_context_switches_camera_scroll_multipliers: None,
+            allow_free_scroll: None,
+            keybinds: None,
+            stacking: WindowStacking::Normal,
+            scrollbars: Scrollbars::new(),
+            smooth_scroll: SmoothScroll::disabled(),
+            context_menu: None,
+            closable: false,
+            pinnable: false,
+            title_bar_buttons: Vec::new(),
+        }
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn with_position(
+        mut self,
+        position: (
+            crate::DearMirlGuiCoordinateType,
+            crate::DearMirlGuiCoordinateType,
+        ),
+    ) -> Self {
+        self.position = position;
+        self
+    }
+    /// Quick creation setter for the size, without one the window is made big enough to see all modules
+    #[must_use]
+    pub const fn with_size(mut self, size: (usize, usize)) -> Self {
+        self.size = Some(size);
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn with_modules(mut self, modules: &[u32]) -> Self {
+        self.modules = modules.to_vec();
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn with_toolbar_modules(mut self, modules: &[u32]) -> Self {
+        self.toolbar_modules = modules.to_vec();
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn with_menu_height(mut self, menu_height: usize) -> Self {
+        self.menu_height = Some(menu_height);
+        self
+    }
+    /// Quick creation setter for the minimum width, the window can't be resized to be narrower
+    #[must_use]
+    pub const fn with_min_width(mut self, min_width: usize) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+    /// Quick creation setter for the minimum width and height, the window can't be resized to be smaller
+    #[must_use]
+    pub const fn with_min_size(mut self, size: (usize, usize)) -> Self {
+        self.min_width = Some(size.0);
+        self.min_height = Some(size.1);
+        self
+    }
+    /// Quick creation setter for the maximum size, the window can't be resized to be bigger
+    #[must_use]
+    pub const fn with_max_size(mut self, size: (usize, usize)) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+    /// Quick creation setter for locking the width divided by the height while resizing
+    #[must_use]
+    pub const fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.aspect_ratio = Some(aspect_ratio);
+        self
+    }
+    /// Quick creation setter for the area the window is kept inside of
+    #[must_use]
+    pub const fn with_bounds(mut self, bounds: crate::DirtyRect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// Quick creation setter for following the size of the modules every update, it stops growing at the maximum size
+    #[must_use]
+    pub const fn with_auto_size(mut self, width: bool, height: bool) -> Self {
+        self.auto_size = (width, height);
+        self
+    }
+    /// Quick creation setter for the formatting of the window and its modules
+    #[must_use]
+    pub fn with_formatting(mut self, formatting: crate::Formatting) -> Self {
+        self.formatting = Some(formatting);
+        self
+    }
+    /// Quick creation setter for the edges and corners the window can be resized at
+    #[must_use]
+    pub const fn with_resizing(mut self, directions: NormalDirections) -> Self {
+        self.resizing_allowed_in_directions = directions;
+        self
+    }
+    /// Quick creation setter for if the window can be dragged by its title bar
+    #[must_use]
+    pub const fn with_dragging(mut self, allow_dragging: bool) -> Self {
+        self.allow_dragging = allow_dragging;
+        self
+    }
+    /// Quick creation setter for if the window starts out collapsed
+    #[must_use]
+    pub const fn with_collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+    /// Quick creation setter for by how much the horizontal and vertical mouse scroll is multiplied
+    #[must_use]
+    pub const fn with_scroll_multipliers(mut self, x: f32, y: f32) -> Self {
+        self.scroll_multipliers = Some((x, y));
+        self
+    }
+    /// Quick creation setter for if holding shift also swaps the scroll multipliers
+    #[must_use]
+    pub const fn with_horizontal_context_switches_camera_scroll_multipliers(
+        mut self,
+        switches: bool,
+    ) -> Self {
+        self.horizontal_context_switches_camera_scroll_multipliers =
+            Some(switches);
+        self
+    }
+    /// Quick creation setter for if the content can be scrolled while it fits into the window
+    #[must_use]
+    pub const fn with_free_scroll(mut self, allow_free_scroll: bool) -> Self {
+        self.allow_free_scroll = Some(allow_free_scroll);
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn with_keybinds(mut self, keybinds: Vec<KeyBind<Actions>>) -> Self {
+        self.keybinds = Some(keybinds);
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn with_stacking(mut self, stacking: WindowStacking) -> Self {
+        self.stacking = stacking;
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn with_scrollbars(mut self, scrollbars: Scrollbars) -> Self {
+        self.scrollbars = scrollbars;
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn with_smooth_scroll(
+        mut self,
+        smooth_scroll: SmoothScroll,
+    ) -> Self {
+        self.smooth_scroll = smooth_scroll;
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn with_context_menu(mut self, menu: crate::modules::Menu) -> Self {
+        self.context_menu = Some(menu);
+        self
+    }
+    /// Quick creation setter for showing the close button
+    #[must_use]
+    pub const fn with_close_button(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+    /// Quick creation setter for showing the pin button
+    #[must_use]
+    pub const fn with_pin_button(mut self, pinnable: bool) -> Self {
+        self.pinnable = pinnable;
+        self
+    }
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn with_title_bar_button(mut self, button: TitleBarButton) -> Self {
+        self.title_bar_buttons.push(button);
+        self
+    }
+    /// Check if the settings make up a usable window
+    ///
+    /// # Errors
+    /// The first invalid setting found, see [`DearMirlGuiBuilderError`]
+    pub fn validate(&self) -> Result<(), DearMirlGuiBuilderError> {
+        let menu_height = self
+            .menu_height
+            .unwrap_or(DearMirlGui::<false, false>::DEFAULT_MENU_HEIGHT);
+        if menu_height == 0 {
+            return Err(DearMirlGuiBuilderError::ZeroMenuHeight);
+        }
+        if let Some((width, height)) = self.size {
+            if height < menu_height {
+                return Err(DearMirlGuiBuilderError::HeightSmallerThanMenu {
+                    height,
+                    menu_height,
+                });
+            }
+            if let Some(min_width) = self.min_width
+                && width < min_width
+            {
+                return Err(DearMirlGuiBuilderError::WidthSmallerThanMinimum {
+                    width,
+                    min_width,
+                });
+            }
+            if let Some(min_height) = self.min_height
+                && height < min_height
+            {
+                return Err(
+                    DearMirlGuiBuilderError::HeightSmallerThanMinimum {
+                        height,
+                        min_height,
+                    },
+                );
+            }
+        }
+        if let Some(max_size) = self.max_size {
+            let min_size = (
+                self.min_width.unwrap_or_default(),
+                self.min_height.unwrap_or_default().max(menu_height),
+            );
+            if max_size.0 < min_size.0 || max_size.1 < min_size.1 {
+                return Err(
+                    DearMirlGuiBuilderError::MaxSizeSmallerThanMinimum {
+                        max_size,
+                        min_size,
+                    },
+                );
+            }
+            if let Some(size) = self.size
+                && (size.0 > max_size.0 || size.1 > max_size.1)
+            {
+                return Err(DearMirlGuiBuilderError::SizeBiggerThanMaximum {
+                    size,
+                    max_size,
+                });
+            }
+        }
+        if let Some(ratio) = self.aspect_ratio
+            && !(ratio.is_finite() && ratio > 0.0)
+        {
+            return Err(DearMirlGuiBuilderError::InvalidAspectRatio(ratio));
+        }
+        if let Some(multipliers) = self.scroll_multipliers {
+            for multiplier in <[f32; 2]>::from(multipliers) {
+                if !multiplier.is_finite() {
+                    return Err(
+                        DearMirlGuiBuilderError::InvalidScrollMultiplier(
+                            multiplier,
+                        ),
+                    );
+                }
+            }
+        }
+        if self.smooth_scroll.enabled
+            && (self.smooth_scroll.speed.is_nan()
+                || self.smooth_scroll.speed <= 0.0)
+        {
+            return Err(DearMirlGuiBuilderError::InvalidSmoothScrollSpeed(
+                self.smooth_scroll.speed,
+            ));
+        }
+        if let Some(id) = self
+            .modules
+            .iter()
+            .chain(&self.toolbar_modules)
+            .find(|id| get_idx_of_id(**id).is_none())
+        {
+            return Err(DearMirlGuiBuilderError::UnknownModule(*id));
+        }
+        Ok(())
+    }
+    /// Validate the settings and create the window
+    ///
+    /// # Errors
+    /// The first invalid setting found, see [`DearMirlGuiBuilderError`]
+    pub fn build<const FAST: bool, const USE_CACHE: bool>(
+        self,
+    ) -> Result<DearMirlGui<FAST, USE_CACHE>, DearMirlGuiBuilderError> {
+        self.validate()?;
+        let (width, height) = self.size.unwrap_or_default();
+        let global_formatting = get_formatting();
+        let font = self
+            .formatting
+            .as_ref()
+            .map_or(&global_formatting.font, |formatting| &formatting.font);
+        let mut gui = DearMirlGui::new_advanced(
+            &self.title,
+            self.position.0,
+            self.position.1,
+            width,
+            height,
+            self.modules,
+            self.toolbar_modules,
+            font,
+            self.menu_height,
+            self.min_width,
+            Some(self.resizing_allowed_in_directions),
+            self.allow_dragging,
+            self.collapsed,
+            self.scroll_multipliers.map(|multipliers| multipliers.0),
+            self.scroll_multipliers.map(|multipliers| multipliers.1),
+            self.keybinds.unwrap_or_else(crate::gui::get_default_keybinds),
+            self.horizontal_context_switches_camera_scroll_multipliers,
+            self.allow_free_scroll,
+        )
+        .with_stacking(self.stacking)
+        .with_scrollbars(self.scrollbars)
+        .with_smooth_scroll(self.smooth_scroll);
+        gui.set_formatting(self.formatting);
+        gui.context_menu = self.context_menu;
+        gui.closable = self.closable;
+        gui.pinnable = self.pinnable;
+        gui.title_bar_buttons = self.title_bar_buttons;
+        // Making room for the title bar buttons, a given minimum width is kept as is
+        if self.min_width.is_none() && !gui.get_title_bar_buttons().is_empty() {
+            gui.update_min_width();
+        }
+        if self.size.is_none() {
+            gui.set_size_to_see_all_modules();
+        }
+        gui.min_height = self.min_height.unwrap_or_default();
+        gui.max_width = self.max_size.map(|size| size.0);
+        gui.max_height = self.max_size.map(|size| size.1);
+        gui.aspect_ratio = self.aspect_ratio;
+        gui.bounds = self.bounds;
+        gui.auto_size = self.auto_size;
+        gui.update_auto_size();
+        gui.apply_size_constraints();
+        Ok(gui)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DearMirlGuiBuilder, DearMirlGuiBuilderError};
+    use crate::SmoothScroll;
+
+    fn builder() -> DearMirlGuiBuilder {
+        DearMirlGuiBuilder::new("Test").with_menu_height(20)
+    }
+
+    #[test]
+    fn valid_settings_pass() {
+        let builder = builder()
+            .with_size((200, 100))
+            .with_min_size((50, 40))
+            .with_max_size((400, 300))
+            .with_aspect_ratio(2.0)
+            .with_scroll_multipliers(1.0, -1.0);
+        assert_eq!(builder.validate(), Ok(()));
+    }
+
+    #[test]
+    fn menu_height_is_checked() {
+        assert_eq!(
+            builder().with_menu_height(0).validate(),
+            Err(DearMirlGuiBuilderError::ZeroMenuHeight)
+        );
+        assert_eq!(
+            builder().with_size((100, 10)).validate(),
+            Err(DearMirlGuiBuilderError::HeightSmallerThanMenu {
+                height: 10,
+                menu_height: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn size_has_to_fit_the_constraints() {
+        assert_eq!(
+            builder().with_size((30, 100)).with_min_width(40).validate(),
+            Err(DearMirlGuiBuilderError::WidthSmallerThanMinimum {
+                width: 30,
+                min_width: 40,
+            })
+        );
+        assert_eq!(
+            builder().with_size((100, 30)).with_min_size((0, 50)).validate(),
+            Err(DearMirlGuiBuilderError::HeightSmallerThanMinimum {
+                height: 30,
+                min_height: 50,
+            })
+        );
+        assert_eq!(
+            builder()
+                .with_size((300, 100))
+                .with_max_size((200, 200))
+                .validate(),
+            Err(DearMirlGuiBuilderError::SizeBiggerThanMaximum {
+                size: (300, 100),
+                max_size: (200, 200),
+            })
+        );
+    }
+
+    #[test]
+    fn maximum_size_has_to_fit_the_minimum() {
+        assert_eq!(
+            builder()
+                .with_min_size((100, 10))
+                .with_max_size((50, 50))
+                .validate(),
+            Err(DearMirlGuiBuilderError::MaxSizeSmallerThanMinimum {
+                max_size: (50, 50),
+                min_size: (100, 20),
+            })
+        );
+        // The menu height is the smallest possible height
+        assert_eq!(
+            builder().with_max_size((50, 10)).validate(),
+            Err(DearMirlGuiBuilderError::MaxSizeSmallerThanMinimum {
+                max_size: (50, 10),
+                min_size: (0, 20),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_numbers_are_rejected() {
+        assert_eq!(
+            builder().with_aspect_ratio(0.0).validate(),
+            Err(DearMirlGuiBuilderError::InvalidAspectRatio(0.0))
+        );
+        assert!(matches!(
+            builder().with_aspect_ratio(f32::NAN).validate(),
+            Err(DearMirlGuiBuilderError::InvalidAspectRatio(_))
+        ));
+        assert_eq!(
+            builder().with_scroll_multipliers(1.0, f32::INFINITY).validate(),
+            Err(DearMirlGuiBuilderError::InvalidScrollMultiplier(
+                f32::INFINITY
+            ))
+        );
+        let mut smooth_scroll = SmoothScroll::new();
+        smooth_scroll.speed = -1.0;
+        assert_eq!(
+            builder().with_smooth_scroll(smooth_scroll).validate(),
+            Err(DearMirlGuiBuilderError::InvalidSmoothScrollSpeed(-1.0))
+        );
+        // The speed is ignored while smooth scrolling is disabled
+        smooth_scroll.enabled = false;
+        assert_eq!(
+            builder().with_smooth_scroll(smooth_scroll).validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn unknown_modules_are_rejected() {
+        assert_eq!(
+            builder().with_modules(&[u32::MAX]).validate(),
+            Err(DearMirlGuiBuilderError::UnknownModule(u32::MAX))
+        );
+        assert_eq!(
+            builder().with_toolbar_modules(&[u32::MAX - 1]).validate(),
+            Err(DearMirlGuiBuilderError::UnknownModule(u32::MAX - 1))
+        );
+    }
+}
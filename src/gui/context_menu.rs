@@ -0,0 +1,34 @@
+use crate::modules::{Menu, PopupMenu};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where the menu of an opened [`ContextMenu`] comes from
+pub enum ContextMenuSource {
+    /// Set for the module with the given id using [`set_context_menu`](crate::module_manager::set_context_menu)
+    Registry(u32),
+    /// Returned by [`get_context_menu`](crate::DearMirlGuiModule::get_context_menu) of the module with the given id
+    Module(u32),
+    /// The [`context_menu`](super::DearMirlGui::context_menu) of the window itself
+    Window,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A menu opened by right clicking, drawn above all windows
+pub struct ContextMenu {
+    /// A copy of the menu, written back when an item has been clicked so checked states are kept
+    pub menu: Menu,
+    /// The opened menu, positioned on screen
+    pub popup: PopupMenu,
+    #[allow(missing_docs)]
+    pub source: ContextMenuSource,
+}
+impl ContextMenu {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn new(
+        menu: Menu,
+        position: (isize, isize),
+        source: ContextMenuSource,
+    ) -> Self {
+        Self { menu, popup: PopupMenu::new(position), source }
+    }
+}
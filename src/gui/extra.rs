@@ -110,6 +110,37 @@ impl ModuleContainer {
     }
     /// See [crate::DearMirlGuiModule] for documentation
     #[must_use]
+    pub fn draw_overlay(
+        &self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> Vec<(Buffer, (isize, isize))> {
+        self.with_ref_mut(|item| item.draw_overlay(formatting, info))
+    }
+    /// See [crate::DearMirlGuiModule] for documentation
+    #[must_use]
+    pub fn update_overlay(
+        &self,
+        info: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        self.with_ref_mut(|item| item.update_overlay(info))
+    }
+    /// See [crate::DearMirlGuiModule] for documentation
+    #[must_use]
+    pub fn get_context_menu(&self) -> Option<crate::modules::Menu> {
+        self.with_ref_mut(|item| item.get_context_menu())
+    }
+    /// See [crate::DearMirlGuiModule] for documentation
+    #[must_use]
+    pub fn context_menu_action(
+        &self,
+        id: &str,
+        info: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        self.with_ref_mut(|item| item.context_menu_action(id, info))
+    }
+    /// See [crate::DearMirlGuiModule] for documentation
+    #[must_use]
     pub fn need_redraw(&self) -> bool {
         self.with_ref_mut(|item| item.need_redraw())
     }
@@ -117,4 +148,8 @@ impl ModuleContainer {
     pub fn set_need_redraw(&self, redraw: Vec<(usize, bool)>) {
         self.with_ref_mut(|item| item.set_need_redraw(redraw));
     }
+    /// See [crate::DearMirlGuiModule] for documentation
+    pub fn set_available_area(&self, area: crate::DirtyRect) {
+        self.with_ref_mut(|item| item.set_available_area(area));
+    }
 }
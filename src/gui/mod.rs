@@ -24,6 +24,12 @@
 /// Magic stuff to make all modules work in harmony
 pub mod extra;
 pub use extra::ModuleContainer;
+/// Menus opened by right clicking a module or window
+pub mod context_menu;
+pub use context_menu::{ContextMenu, ContextMenuSource};
+/// Creating windows with named and validated settings
+pub mod builder;
+pub use builder::{DearMirlGuiBuilder, DearMirlGuiBuilderError};
 use mirl::{
     directions::{
         NormalDirections,
@@ -33,7 +39,7 @@ use mirl::{
     },
     extensions::*,
     math::{ConstNumbers128, ConstZero, geometry::GetShapeDirectionType},
-    misc::keybinds::KeyBind,
+    misc::keybinds::{KeyBind, sort_actions},
     platform::{
         CursorStyle,
         keycodes::KeyCode,
@@ -42,7 +48,7 @@ use mirl::{
     render::{self, Buffer, BufferCollision},
 };
 
-use crate::{ModuleDrawInfo, ModuleUpdateInfo, module_manager::{InsertionMode, MODULES, get_available_id, get_idx_of_id, get_image_cache, get_module_raw_mut, insert_into_image_cache}, prelude::*};
+use crate::{ModuleDrawInfo, ModuleUpdateInfo, module_manager::{InsertionMode, MODULES, get_available_id, get_idx_of_id, get_image_cache, get_module_raw_mut, insert_into_image_cache}, modules::PopupEvent, prelude::*};
 #[derive(Debug, Clone, PartialEq)]
 /// Actions that the gui can execute upon request
 pub enum Actions {
@@ -125,6 +131,18 @@ pub enum Actions {
 // impl std::marker::ConstParamTy_ for Performance {}
 // impl std::marker::UnsizedConstParamTy for Performance {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+/// In which layer a window is kept by the [`DearMirlGuiManager`](crate::DearMirlGuiManager), focusing a window only brings it to the front of its layer
+pub enum WindowStacking {
+    /// Always above all other windows
+    AlwaysOnTop,
+    #[default]
+    /// Brought to the front when focused
+    Normal,
+    /// Always below all other windows
+    AlwaysAtBack,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[allow(clippy::struct_excessive_bools)] // This ain't no state machine!
 /// A single window
@@ -163,8 +181,8 @@ pub struct DearMirlGui<const FAST: bool, const USE_CACHE: bool> {
     pub min_width: usize,
     /// If the window should be collapsed
     pub collapsed: bool,
-    // /// A cache so toolbar modules don't need to redraw themselves every frame
-    // pub toolbar_cache: Vec<Option<std::rc::Rc<Buffer>>>,
+    /// A cache of the toolbar strip so toolbar modules don't need to redraw themselves every frame
+    pub toolbar_cache: Option<std::sync::Arc<Buffer>>,
     /// If the collapse button collision should be circular
     pub collapse_button_collision_is_circle: bool,
     /// If there have been any changes to the gui
@@ -184,6 +202,54 @@ pub struct DearMirlGui<const FAST: bool, const USE_CACHE: bool> {
     )>,
     /// A set of keybinds for non mouse input purposes
     pub keybinds: Vec<KeyBind<Actions>>,
+    /// The keys held down last update, keybinds only react to newly pressed keys
+    pub last_keys_pressed: Vec<KeyCode>,
+    /// The module that took the focus last, used by [`Actions::CenterCameraOnSelected`]
+    pub selected_module: Option<u32>,
+    /// The menu that opens when right clicking the window where no module has its own menu
+    pub context_menu: Option<crate::modules::Menu>,
+    /// The currently opened right click menu
+    pub open_context_menu: Option<ContextMenu>,
+    /// The module and item id of a clicked context menu item, applied after the module has been updated
+    pub pending_context_menu_action: Option<(u32, String)>,
+    /// The layer the window is kept in when stacking windows
+    pub stacking: WindowStacking,
+    /// Modules that reported needing a redraw while being updated, they are redrawn on the next render
+    pub modules_to_redraw: Vec<u32>,
+    /// The scrollbars shown when the modules don't fit into the window
+    pub scrollbars: crate::Scrollbars,
+    /// Animated and kinetic scrolling of the camera, disabled by default
+    pub smooth_scroll: crate::SmoothScroll,
+    /// If the window is shown, closed windows are neither updated nor drawn
+    pub open: bool,
+    /// If the close button is shown on the title bar
+    pub closable: bool,
+    /// If the pin button is shown on the title bar
+    pub pinnable: bool,
+    /// Pinned windows can't be dragged or resized
+    pub pinned: bool,
+    /// User defined buttons shown on the title bar, right to left
+    pub title_bar_buttons: Vec<crate::TitleBarButton>,
+    /// The title bar button the mouse is above
+    pub hovered_title_bar_button: Option<crate::TitleBarButtonKind>,
+    /// What happened to the window, moved to the [`GuiOutput`] on the next update
+    pub events: Vec<crate::WindowEvent>,
+    /// The minimum height - Windows cannot be vertically smaller than this or the menu height
+    pub min_height: usize,
+    /// The maximum width when resizing, None = unlimited
+    pub max_width: Option<usize>,
+    /// The maximum height when resizing, None = unlimited
+    pub max_height: Option<usize>,
+    /// Width divided by height, kept while resizing
+    pub aspect_ratio: Option<f32>,
+    /// The area the window is kept inside of while dragging and resizing
+    pub bounds: Option<crate::DirtyRect>,
+    /// If the width and height follow the size of the modules every update
+    ///
+    /// The size stops growing at [`Self::max_width`]/[`Self::max_height`], from there on the content has to be scrolled
+    pub auto_size: (bool, bool),
+    /// The formatting of this window and its modules, None = the global formatting
+    pub formatting: Option<std::sync::Arc<crate::Formatting>>,
 }
 
 impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
@@ -202,7 +268,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
     pub const ALLOW_FREE_SCROLL: bool = true;
     #[must_use]
     #[allow(clippy::too_many_arguments)]
-    /// Create a new `DearMirlGui` window
+    /// Create a new `DearMirlGui` window, [`DearMirlGuiBuilder`] is easier to read
     /// Just clone the `mirl::prelude::fontdue::Font`
     pub fn new_advanced(
         title: &str,
@@ -231,6 +297,28 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             height,
             modules: Vec::new(),
             toolbar_modules: Vec::new(),
+            toolbar_cache: None,
+            context_menu: None,
+            open_context_menu: None,
+            pending_context_menu_action: None,
+            stacking: WindowStacking::Normal,
+            modules_to_redraw: Vec::new(),
+            scrollbars: crate::Scrollbars::new(),
+            smooth_scroll: crate::SmoothScroll::disabled(),
+            open: true,
+            closable: false,
+            pinnable: false,
+            pinned: false,
+            title_bar_buttons: Vec::new(),
+            hovered_title_bar_button: None,
+            events: Vec::new(),
+            min_height: 0,
+            max_width: None,
+            max_height: None,
+            aspect_ratio: None,
+            bounds: None,
+            auto_size: (false, false),
+            formatting: None,
             last_mouse_pos: core::default::Default::default(),
             last_left_mouse_down: false,
             last_middle_mouse_down: false,
@@ -270,6 +358,8 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
 
             size_to_see_all_modules: None,
             keybinds,
+            last_keys_pressed: Vec::new(),
+            selected_module: None,
         };
 
         for module in modules {
@@ -298,6 +388,28 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             height: 0,
             modules: Vec::new(),
             toolbar_modules: Vec::new(),
+            toolbar_cache: None,
+            context_menu: None,
+            open_context_menu: None,
+            pending_context_menu_action: None,
+            stacking: WindowStacking::Normal,
+            modules_to_redraw: Vec::new(),
+            scrollbars: crate::Scrollbars::new(),
+            smooth_scroll: crate::SmoothScroll::disabled(),
+            open: true,
+            closable: false,
+            pinnable: false,
+            pinned: false,
+            title_bar_buttons: Vec::new(),
+            hovered_title_bar_button: None,
+            events: Vec::new(),
+            min_height: 0,
+            max_width: None,
+            max_height: None,
+            aspect_ratio: None,
+            bounds: None,
+            auto_size: (false, false),
+            formatting: None,
             last_mouse_pos: core::default::Default::default(),
             last_left_mouse_down: false,
             last_middle_mouse_down: false,
@@ -334,6 +446,8 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             id: get_available_id(),
             size_to_see_all_modules: None,
             keybinds: get_default_keybinds(),
+            last_keys_pressed: Vec::new(),
+            selected_module: None,
         };
         for module in modules {
             gui.add_module(*module);
@@ -343,13 +457,13 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
     }
     /// Update the min width based on the length of the window title
     pub fn update_min_width(&mut self) {
-        let formatting = get_formatting();
+        let formatting = self.get_formatting();
         self.min_width = render::get_text_width(
             &self.title,
             self.menu_height as f32,
             &formatting.font,
         ) as usize
-            + self.menu_height
+            + self.menu_height * (1 + self.get_title_bar_buttons().len())
             + formatting.horizontal_margin * 3;
     }
     #[must_use]
@@ -375,6 +489,9 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
     /// Remove a module
     pub fn remove_module(&mut self, name: u32) {
         remove(&mut self.modules, name, self.id);
+        if self.selected_module == Some(name) {
+            self.selected_module = None;
+        }
         self.size_to_see_all_modules = None;
     }
 
@@ -382,17 +499,708 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
     pub fn add_toolbar_module(&mut self, name: u32) {
         add(&mut self.toolbar_modules, name, self.id);
         self.size_to_see_all_modules = None;
+        self.toolbar_cache = None;
     }
     /// Remove a toolbar module
     pub fn remove_toolbar_module(&mut self, name: u32) {
         remove(&mut self.toolbar_modules, name, self.id);
         self.size_to_see_all_modules = None;
+        self.toolbar_cache = None;
+    }
+    #[must_use]
+    /// Quick creation setter for adding modules to the toolbar
+    pub fn with_toolbar_modules(mut self, modules: &[u32]) -> Self {
+        for module in modules {
+            self.add_toolbar_module(*module);
+        }
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the menu that opens when right clicking the window
+    pub fn with_context_menu(mut self, menu: crate::modules::Menu) -> Self {
+        self.context_menu = Some(menu);
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the layer the window is kept in, see [`WindowStacking`]
+    pub const fn with_stacking(mut self, stacking: WindowStacking) -> Self {
+        self.stacking = stacking;
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the scrollbars, use [`Scrollbars::none`](crate::Scrollbars::none) to hide them
+    pub const fn with_scrollbars(
+        mut self,
+        scrollbars: crate::Scrollbars,
+    ) -> Self {
+        self.scrollbars = scrollbars;
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for animated and kinetic scrolling, see [`SmoothScroll`](crate::SmoothScroll)
+    pub const fn with_smooth_scroll(
+        mut self,
+        smooth_scroll: crate::SmoothScroll,
+    ) -> Self {
+        self.smooth_scroll = smooth_scroll;
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for showing the close button on the title bar
+    pub fn with_close_button(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self.update_min_width();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for showing the pin button on the title bar
+    pub fn with_pin_button(mut self, pinnable: bool) -> Self {
+        self.pinnable = pinnable;
+        self.update_min_width();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for adding a user defined button to the title bar, see [`TitleBarButton`](crate::TitleBarButton)
+    pub fn with_title_bar_button(
+        mut self,
+        button: crate::TitleBarButton,
+    ) -> Self {
+        self.title_bar_buttons.push(button);
+        self.update_min_width();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the smallest size the window can be resized to, the width is at least [`Self::min_width`]
+    pub fn with_min_size(mut self, size: (usize, usize)) -> Self {
+        self.min_width = self.min_width.max(size.0);
+        self.min_height = size.1;
+        self.apply_size_constraints();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the biggest size the window can be resized to
+    pub fn with_max_size(mut self, size: (usize, usize)) -> Self {
+        self.max_width = Some(size.0);
+        self.max_height = Some(size.1);
+        self.apply_size_constraints();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for locking the width divided by the height while resizing
+    pub fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.aspect_ratio = Some(aspect_ratio);
+        self.apply_size_constraints();
+        self
+    }
+    #[must_use]
+    /// Quick creation setter for the area the window is kept inside of
+    pub fn with_bounds(mut self, bounds: crate::DirtyRect) -> Self {
+        self.bounds = Some(bounds);
+        self.apply_size_constraints();
+        self
+    }
+    #[must_use]
+    /// The smallest size the window can be resized to
+    pub const fn get_min_size(&self) -> (usize, usize) {
+        (self.min_width, self.min_height.max(self.menu_height))
+    }
+    #[must_use]
+    /// The biggest size the window can be resized to, also limited by the size of the bounds
+    pub fn get_max_size(&self) -> (usize, usize) {
+        let (min_width, min_height) = self.get_min_size();
+        let mut max_size = (
+            self.max_width.unwrap_or(usize::MAX),
+            self.max_height.unwrap_or(usize::MAX),
+        );
+        if let Some(bounds) = self.bounds {
+            max_size =
+                (max_size.0.min(bounds.width), max_size.1.min(bounds.height));
+        }
+        // The minimum wins, the title has to stay readable
+        (max_size.0.max(min_width), max_size.1.max(min_height))
+    }
+    /// Resize the window while keeping the size constraints, the aspect ratio and the bounds
+    ///
+    /// `moving_edges` are whether the left and the top edge move, otherwise the right and bottom edge do
+    pub fn resize_to(
+        &mut self,
+        size: (isize, isize),
+        moving_edges: (bool, bool),
+    ) {
+        let right = self.x as isize + self.width as isize;
+        let bottom = self.y as isize + self.height as isize;
+        let (min_width, min_height) = self.get_min_size();
+        let (mut max_width, mut max_height) = self.get_max_size();
+        // The moving edges can't cross the bounds
+        if let Some(bounds) = self.bounds {
+            let room_x = if moving_edges.0 {
+                right - bounds.x
+            } else {
+                bounds.right() - self.x as isize
+            };
+            let room_y = if moving_edges.1 {
+                bottom - bounds.y
+            } else {
+                bounds.bottom() - self.y as isize
+            };
+            max_width = max_width.min(room_x.max(0) as usize).max(min_width);
+            max_height = max_height.min(room_y.max(0) as usize).max(min_height);
+        }
+        let mut width = size.0.max(0) as usize;
+        let mut height = size.1.max(0) as usize;
+        if let Some(ratio) =
+            self.aspect_ratio.filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+        {
+            // The axis that changed the most decides the size
+            if width.abs_diff(self.width) >= height.abs_diff(self.height) {
+                height = (width as f32 / ratio).round() as usize;
+            } else {
+                width = (height as f32 * ratio).round() as usize;
+            }
+            if width != width.clamp(min_width, max_width) {
+                width = width.clamp(min_width, max_width);
+                height = (width as f32 / ratio).round() as usize;
+            }
+            if height != height.clamp(min_height, max_height) {
+                height = height.clamp(min_height, max_height);
+                width = (height as f32 * ratio).round() as usize;
+            }
+        }
+        let width = width.clamp(min_width, max_width);
+        let height = height.clamp(min_height, max_height);
+        if moving_edges.0 {
+            self.x =
+                (right - width as isize) as crate::DearMirlGuiCoordinateType;
+        }
+        if moving_edges.1 {
+            self.y =
+                (bottom - height as isize) as crate::DearMirlGuiCoordinateType;
+        }
+        self.width = width;
+        self.height = height;
+    }
+    /// Move the window back inside of its bounds, windows bigger than the bounds are aligned to their top left
+    pub const fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+        let x = (self.x as isize)
+            .min(bounds.right() - Self::get_width(self) as isize)
+            .max(bounds.x);
+        let y = (self.y as isize)
+            .min(bounds.bottom() - Self::get_height(self) as isize)
+            .max(bounds.y);
+        self.x = x as crate::DearMirlGuiCoordinateType;
+        self.y = y as crate::DearMirlGuiCoordinateType;
+    }
+    /// Apply the size constraints and move the window inside of its bounds
+    pub fn apply_size_constraints(&mut self) {
+        // Moved first so the window only shrinks when it doesn't fit at all
+        self.clamp_to_bounds();
+        self.resize_to(
+            (self.width as isize, self.height as isize),
+            (false, false),
+        );
+        self.clamp_to_bounds();
+    }
+    /// Open or close the window, adds a [`WindowEvent::Opened`](crate::WindowEvent::Opened)/[`WindowEvent::Closed`](crate::WindowEvent::Closed) if it changed
+    pub fn set_open(&mut self, open: bool) {
+        if self.open == open {
+            return;
+        }
+        self.open = open;
+        self.dragging = false;
+        self.resizing = false;
+        self.needs_redraw = true;
+        self.events.push(if open {
+            crate::WindowEvent::Opened(self.id)
+        } else {
+            crate::WindowEvent::Closed(self.id)
+        });
+    }
+    #[must_use]
+    /// The buttons shown on the right side of the title bar, right to left
+    pub fn get_title_bar_buttons(&self) -> Vec<crate::TitleBarButtonKind> {
+        let mut buttons = Vec::new();
+        if self.closable {
+            buttons.push(crate::TitleBarButtonKind::Close);
+        }
+        if self.pinnable {
+            buttons.push(crate::TitleBarButtonKind::Pin);
+        }
+        buttons.extend(
+            (0..self.title_bar_buttons.len())
+                .map(crate::TitleBarButtonKind::Custom),
+        );
+        buttons
+    }
+    #[must_use]
+    /// Get the title bar button below the mouse, the mouse position is on screen
+    pub fn get_title_bar_button_at(
+        &self,
+        mouse_pos: (f32, f32),
+    ) -> Option<crate::TitleBarButtonKind> {
+        let local = (mouse_pos.0 - self.x as f32, mouse_pos.1 - self.y as f32);
+        if local.1 < 0.0 || local.1 >= self.menu_height as f32 {
+            return None;
+        }
+        // Counted from the right edge
+        let from_right = self.width as f32 - local.0;
+        if from_right <= 0.0 {
+            return None;
+        }
+        let idx = (from_right / self.menu_height as f32) as usize;
+        self.get_title_bar_buttons().get(idx).copied()
+    }
+    fn click_title_bar_button(&mut self, button: crate::TitleBarButtonKind) {
+        match button {
+            crate::TitleBarButtonKind::Close => self.set_open(false),
+            crate::TitleBarButtonKind::Pin => {
+                self.pinned = !self.pinned;
+                self.dragging = false;
+                self.resizing = false;
+                self.events.push(crate::WindowEvent::Pinned {
+                    window: self.id,
+                    pinned: self.pinned,
+                });
+            }
+            crate::TitleBarButtonKind::Custom(idx) => {
+                let Some(button) = self.title_bar_buttons.get(idx) else {
+                    return;
+                };
+                if let Some(callback) = button.callback {
+                    callback(self.id);
+                }
+                self.events.push(crate::WindowEvent::TitleBarButtonClicked {
+                    window: self.id,
+                    button: button.id.clone(),
+                });
+            }
+        }
+        self.needs_redraw = true;
+    }
+    #[allow(clippy::too_many_lines)]
+    fn draw_title_bar_buttons(
+        &self,
+        buffer: &mut Buffer,
+        formatting: &crate::Formatting,
+    ) {
+        let size = self.menu_height as isize;
+        for (idx, button) in
+            self.get_title_bar_buttons().into_iter().enumerate()
+        {
+            let x = self.width as isize - (idx as isize + 1) * size;
+            let background = if self.hovered_title_bar_button == Some(button) {
+                formatting.theme.hover_color
+            } else {
+                mirl::graphics::adjust_brightness_hsl_of_rgb(
+                    formatting.foreground_color,
+                    -10.0,
+                )
+            };
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                (x, 0),
+                (size, size),
+                background,
+            );
+            let padding = size / 4;
+            match button {
+                crate::TitleBarButtonKind::Close => {
+                    let start = (x + padding).max(0) as usize;
+                    let end = (x + size - padding).max(0) as usize;
+                    let (top, bottom) =
+                        (padding as usize, (size - padding) as usize);
+                    render::draw_line::<true>(
+                        buffer,
+                        (start, top),
+                        (end, bottom),
+                        formatting.text_color,
+                        1,
+                    );
+                    render::draw_line::<true>(
+                        buffer,
+                        (start, bottom),
+                        (end, top),
+                        formatting.text_color,
+                        1,
+                    );
+                }
+                crate::TitleBarButtonKind::Pin => {
+                    let center = (x + size / 2, size / 2);
+                    render::draw_circle::<true, false>(
+                        buffer,
+                        center,
+                        size / 2 - padding,
+                        formatting.text_color,
+                    );
+                    // A hollow circle while not pinned
+                    if !self.pinned {
+                        render::draw_circle::<true, false>(
+                            buffer,
+                            center,
+                            (size / 2 - padding - 2).max(0),
+                            background,
+                        );
+                    }
+                }
+                crate::TitleBarButtonKind::Custom(idx) => {
+                    match &self.title_bar_buttons[idx].icon {
+                        crate::TitleBarIcon::Text(text) => {
+                            let text_size = (self.menu_height
+                                - formatting.vertical_margin)
+                                as f32;
+                            let width = render::get_text_width(
+                                text,
+                                text_size,
+                                &formatting.font,
+                            ) as isize;
+                            render::draw_text_antialiased_isize::<
+                                { crate::DRAW_SAFE },
+                            >(
+                                buffer,
+                                text,
+                                (
+                                    x + (size - width) / 2,
+                                    formatting.vertical_margin as isize / 2,
+                                ),
+                                formatting.text_color,
+                                text_size,
+                                &formatting.font,
+                            );
+                        }
+                        crate::TitleBarIcon::Image(image) => {
+                            render::draw_buffer_on_buffer::<
+                                true,
+                                true,
+                                false,
+                                false,
+                            >(
+                                buffer,
+                                image.as_ref(),
+                                (
+                                    x + (size - image.width as isize) / 2,
+                                    (size - image.height as isize) / 2,
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// Set the container and content size of the camera to the current size of the window and its modules
+    pub fn update_camera_bounds(&mut self) {
+        let size = self
+            .get_size_to_see_all_modules()
+            .try_tuple_into()
+            .unwrap_or_default();
+        self.camera.container_width = Self::get_width(self) as f32;
+        self.camera.container_height = Self::get_height(self) as f32
+            - self.menu_height as f32
+            - self.get_toolbar_size().1 as f32;
+        self.camera.content_height = size.1;
+        self.camera.content_width = size.0;
+    }
+    /// Move the camera to the given offset, animated if [`Self::smooth_scroll`] is enabled
+    pub fn scroll_to(&mut self, offset: (f32, f32)) {
+        self.update_camera_bounds();
+        self.smooth_scroll.scroll_to(&mut self.camera, offset);
+    }
+    /// Scroll to the top most position
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_to((self.camera.offset_x, 0.0));
+    }
+    /// Scroll to the bottom most position
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to((self.camera.offset_x, f32::MIN));
+    }
+    /// Scroll to the left most position
+    pub fn scroll_to_left(&mut self) {
+        self.scroll_to((0.0, self.camera.offset_y));
+    }
+    /// Scroll to the right most position
+    pub fn scroll_to_right(&mut self) {
+        self.scroll_to((f32::MIN, self.camera.offset_y));
+    }
+    /// Scroll so the module is in the center of the window, returns false if the module isn't in this window
+    pub fn center_camera_on_module(&mut self, module_id: u32) -> bool {
+        let Some((position, size)) = self.get_module_area(module_id) else {
+            return false;
+        };
+        self.update_camera_bounds();
+        self.scroll_to((
+            self.camera.container_width / 2.0 - position.0 - size.0 / 2.0,
+            self.camera.container_height / 2.0 - position.1 - size.1 / 2.0,
+        ));
+        true
+    }
+    /// Scroll by a number of lines, higher numbers move the camera down/right
+    ///
+    /// Consecutive calls add up while the camera is still moving
+    pub fn scroll_by_lines(&mut self, lines: (f32, f32)) {
+        let line_height = self.get_formatting().height as f32;
+        let from = self
+            .smooth_scroll
+            .target
+            .unwrap_or((self.camera.offset_x, self.camera.offset_y));
+        self.scroll_to((
+            lines.0.mul_add(-line_height, from.0),
+            lines.1.mul_add(-line_height, from.1),
+        ));
+    }
+    /// Execute an action as if its keybind was pressed
+    ///
+    /// Module selection and the virtual cursor aren't supported yet, such actions are ignored
+    pub fn handle_action(&mut self, action: &Actions) {
+        match action {
+            Actions::ScrollToTop => self.scroll_to_top(),
+            Actions::ScrollToBottom => self.scroll_to_bottom(),
+            Actions::ScrollToLeft => self.scroll_to_left(),
+            Actions::ScrollToRight => self.scroll_to_right(),
+            Actions::ScrollVertically(lines) => {
+                self.scroll_by_lines((0.0, *lines));
+            }
+            Actions::ScrollHorizontally(lines) => {
+                self.scroll_by_lines((*lines, 0.0));
+            }
+            Actions::CenterCameraOnSelected => {
+                if let Some(module_id) = self.selected_module {
+                    self.center_camera_on_module(module_id);
+                }
+            }
+            Actions::ToggleCollapse(collapse) => {
+                self.collapsed = collapse.unwrap_or(!self.collapsed);
+            }
+            _ => {}
+        }
+    }
+    /// Execute the keybinds activated by newly pressed keys, the held keys are remembered even when not active
+    fn update_keybinds(&mut self, pressed_keys: &[KeyCode], active: bool) {
+        let new_keys: Vec<KeyCode> = self
+            .last_keys_pressed
+            .get_old_items(pressed_keys)
+            .iter()
+            .map(|x| **x)
+            .collect();
+        self.last_keys_pressed = pressed_keys.to_vec();
+        if !active || new_keys.is_empty() {
+            return;
+        }
+        let shift_down = pressed_keys.contains(&KeyCode::LeftShift)
+            || pressed_keys.contains(&KeyCode::RightShift);
+        let alt_down = pressed_keys.contains(&KeyCode::LeftAlt)
+            || pressed_keys.contains(&KeyCode::RightAlt);
+        let control_down = pressed_keys.contains(&KeyCode::LeftControl)
+            || pressed_keys.contains(&KeyCode::RightControl);
+        let active_keybinds = self
+            .keybinds
+            .iter()
+            .filter(|keybind| {
+                keybind.is_keybind_activated(
+                    &new_keys,
+                    shift_down,
+                    alt_down,
+                    control_down,
+                )
+            })
+            .cloned()
+            .collect();
+        for keybind in sort_actions(active_keybinds) {
+            self.handle_action(&keybind.action);
+        }
+    }
+    #[must_use]
+    /// Get the position and size of a module relative to the top left of the unscrolled content, None if the module isn't in this window
+    pub fn get_module_area(
+        &self,
+        module_id: u32,
+    ) -> Option<((f32, f32), (f32, f32))> {
+        let formatting = self.get_formatting();
+        let mut extra_vertical_offset = crate::DearMirlGuiCoordinateType::ZERO;
+        let mut extra_horizontal_offset =
+            crate::DearMirlGuiCoordinateType::ZERO;
+        let mut used_idx = Vec::new();
+        let modules = MODULES.read().ok()?;
+        for module_name in &self.modules {
+            let Some(module_idx) = get_idx_of_id(*module_name) else {
+                continue;
+            };
+            used_idx.push(module_idx);
+            let module_container = &modules[module_idx];
+            let module_width = module_container.get_width(&formatting);
+            let module_height = module_container.get_height(&formatting);
+            if *module_name == module_id {
+                // Matching the position the module is drawn at
+                return Some((
+                    (
+                        (formatting.horizontal_margin
+                            as crate::DearMirlGuiCoordinateType
+                            + extra_horizontal_offset)
+                            as f32,
+                        (formatting.horizontal_margin
+                            as crate::DearMirlGuiCoordinateType
+                            + extra_vertical_offset)
+                            as f32,
+                    ),
+                    (module_width as f32, module_height as f32),
+                ));
+            }
+            extra_vertical_offset += module_height
+                + formatting.vertical_margin
+                    as crate::DearMirlGuiCoordinateType;
+            module_container.modify_offset_cursor(
+                &modules,
+                &used_idx,
+                &formatting,
+                (&mut extra_horizontal_offset, &mut extra_vertical_offset),
+            );
+        }
+        drop(modules);
+        None
+    }
+    #[must_use]
+    /// Get the size of the toolbar strip below the menu, is (0, 0) when there are no toolbar modules
+    pub fn get_toolbar_size(&self) -> (usize, usize) {
+        if self.toolbar_modules.is_empty() {
+            return (0, 0);
+        }
+        let formatting = self.get_formatting();
+        let mut width = formatting.horizontal_margin;
+        let mut height = 0;
+        if let Ok(modules) = MODULES.read() {
+            for module_name in &self.toolbar_modules {
+                let Some(module_idx) = get_idx_of_id(*module_name) else {
+                    continue;
+                };
+                let module_container = &modules[module_idx];
+                width += module_container.get_width(&formatting) as usize
+                    + formatting.horizontal_margin;
+                height = height
+                    .max(module_container.get_height(&formatting) as usize);
+            }
+        }
+        (width, height + formatting.vertical_margin * 2)
+    }
+    /// Draw a module or reuse its cached image when it doesn't need to be redrawn
+    ///
+    /// Returns the image, how it should be inserted and if it should be put into the cache
+    fn draw_module(
+        &self,
+        module_container: &ModuleContainer,
+        module_idx: usize,
+        need_redraw: bool,
+        formatting: &crate::Formatting,
+        info: ModuleDrawInfo,
+    ) -> (std::sync::Arc<Buffer>, InsertionMode, bool) {
+        if USE_CACHE
+            && !need_redraw
+            && let Some(cached_buf) =
+                get_image_cache(module_idx, self.id, formatting.get_cache_key())
+        {
+            return (cached_buf, InsertionMode::Simple, false);
+        }
+        let drawn_buffer = module_container.draw(formatting, &info);
+        (std::sync::Arc::new(drawn_buffer.0), drawn_buffer.1, true)
+    }
+    /// Draw the toolbar strip, the modules are laid out horizontally
+    ///
+    /// The strip is only redrawn when a toolbar module needs to be redrawn or the window width changed
+    fn render_toolbar(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: ModuleDrawInfo,
+        modules_to_redraw: &[u32],
+    ) -> Option<std::sync::Arc<Buffer>> {
+        let toolbar_height = self.get_toolbar_size().1;
+        if toolbar_height == 0 {
+            return None;
+        }
+        let modules = MODULES.read().ok()?;
+        let (module_names, module_idxs): (Vec<u32>, Vec<usize>) = self
+            .toolbar_modules
+            .iter()
+            .filter_map(|module_name| {
+                get_idx_of_id(*module_name).map(|idx| (*module_name, idx))
+            })
+            .unzip();
+        // Asking a module resets its redraw flag so it is only asked once
+        let need_redraw: Vec<bool> = module_names
+            .iter()
+            .zip(&module_idxs)
+            .map(|(name, idx)| {
+                modules[*idx].need_redraw() || modules_to_redraw.contains(name)
+            })
+            .collect();
+        if USE_CACHE
+            && let Some(cache) = &self.toolbar_cache
+            && cache.width == self.width
+            && cache.height == toolbar_height
+            && !need_redraw.contains(&true)
+        {
+            return Some(cache.clone());
+        }
+
+        let mut buffer = Buffer::new_empty_with_color(
+            (self.width, toolbar_height),
+            formatting.background_color,
+        );
+        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+            &mut buffer,
+            (0, toolbar_height as isize - 1),
+            (self.width as isize, 1),
+            formatting.foreground_color,
+        );
+        let mut x = formatting.horizontal_margin as isize;
+        for (module_idx, need_redraw) in module_idxs.into_iter().zip(need_redraw)
+        {
+            let module_container = &modules[module_idx];
+            let buf = self.draw_module(
+                module_container,
+                module_idx,
+                need_redraw,
+                formatting,
+                info,
+            );
+            let position = (x, formatting.vertical_margin as isize);
+            if FAST {
+                render::draw_buffer_on_buffer::<true, true, false, false>(
+                    &mut buffer,
+                    &buf.0,
+                    position,
+                );
+            } else {
+                render::draw_buffer_on_buffer::<true, true, true, false>(
+                    &mut buffer,
+                    &buf.0,
+                    position,
+                );
+            }
+            x += module_container.get_width(formatting) as isize
+                + formatting.horizontal_margin as isize;
+            if buf.2 {
+                insert_into_image_cache(
+                    module_idx,
+                    self.id,
+                    (*buf.0).clone(),
+                    buf.1,
+                    formatting.get_cache_key(),
+                );
+            }
+        }
+        drop(modules);
+        let buffer = std::sync::Arc::new(buffer);
+        self.toolbar_cache = Some(buffer.clone());
+        Some(buffer)
     }
 
     /// Automatically draw the window onto a `mirl::prelude::Buffer`
     ///
     /// If nothing is showing up, maybe check the size if the gui
     pub fn draw_on_buffer(&mut self, buffer: &mut Buffer) -> Option<()> {
+        if !self.open {
+            return Some(());
+        }
         #[cfg(feature = "draw_debug")]
         println!("Before Drawing");
         let to_draw = self.render();
@@ -431,7 +1239,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         collapse_button_size: f64,
         collapse_button_color_change: f32,
     ) {
-        let formatting = get_formatting();
+        let formatting = self.get_formatting();
         render::draw_rectangle::<{ crate::DRAW_SAFE }>(
             buffer,
             (0, 0),
@@ -466,6 +1274,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 collapse_button_color_change,
             ), // THIS CRASHES WHEN THE CURRENT IS SMALLER THAN THE MENU HEIGHT
         );
+        self.draw_title_bar_buttons(buffer, &formatting);
 
         #[cfg(feature = "debug-window")]
         {
@@ -501,6 +1310,42 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         self
     }
     #[must_use]
+    /// Quick creation setter for the formatting of this window and its modules
+    pub fn with_formatting(mut self, formatting: crate::Formatting) -> Self {
+        self.set_formatting(Some(formatting));
+        self
+    }
+    /// Set the formatting of this window and its modules, None = the global formatting
+    pub fn set_formatting(&mut self, formatting: Option<crate::Formatting>) {
+        self.formatting = formatting.map(std::sync::Arc::new);
+        // Everything has been drawn and measured with the old formatting
+        self.modules_to_redraw =
+            self.modules.iter().chain(&self.toolbar_modules).copied().collect();
+        self.toolbar_cache = None;
+        self.size_to_see_all_modules = None;
+        self.needs_redraw = true;
+    }
+    #[must_use]
+    /// The formatting used by this window, its own or the global one
+    pub fn get_formatting(&self) -> std::sync::Arc<crate::Formatting> {
+        self.formatting.clone().unwrap_or_else(get_formatting)
+    }
+    #[must_use]
+    /// If the window hides everything behind it: an opaque background and square corners
+    pub fn is_opaque(&self) -> bool {
+        let formatting = self.get_formatting();
+        mirl::graphics::get_alpha_of_u32_in_u8(formatting.background_color)
+            == u8::MAX
+            && formatting.theme.corner_rounding == 0
+    }
+    #[must_use]
+    /// Quick creation setter for following the size of the modules every update, see [`Self::auto_size`]
+    pub fn with_auto_size(mut self, width: bool, height: bool) -> Self {
+        self.auto_size = (width, height);
+        self.update_auto_size();
+        self
+    }
+    #[must_use]
     #[allow(clippy::too_many_lines)]
     /// Use [`draw_buffer_on_buffer`](mirl::render::draw_buffer_on_buffer) (or [`draw_buffer_on_buffer_1_to_1`](mirl::render::draw_buffer_on_buffer_1_to_1)) to draw this buffer on any other
     pub fn render(&mut self) -> Buffer {
@@ -514,7 +1359,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         let collapse_button_size = 0.8;
         let collapse_button_color_change = -10.0;
 
-        let formatting = get_formatting();
+        let formatting = self.get_formatting();
 
         let mut buffer = Buffer::new_empty_with_color(
             (
@@ -556,8 +1401,14 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         };
         let mut module_idx_cache = Vec::new();
 
+        let modules_to_redraw = std::mem::take(&mut self.modules_to_redraw);
+        let toolbar =
+            self.render_toolbar(&formatting, info, &modules_to_redraw);
+        let toolbar_height = toolbar.as_ref().map_or(0, |x| x.height);
+
         let static_vertical_offset = self.camera.offset_y
-            + (self.menu_height + formatting.horizontal_margin) as f32;
+            + (self.menu_height + toolbar_height + formatting.horizontal_margin)
+                as f32;
         let static_horizontal_offset = self.camera.offset_x;
 
         let mut extra_vertical_offset: crate::DearMirlGuiCoordinateType =
@@ -580,38 +1431,15 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                         &idx
                     )
                 );
-                let buf: (std::sync::Arc<Buffer>, InsertionMode);
-                let insert_into_cache: bool;
-
-                if USE_CACHE {
-                    if module_container.need_redraw() {
-                        let drawn_buffer =
-                            module_container.draw(&formatting, &info);
-                        buf = (
-                            std::sync::Arc::new(drawn_buffer.0),
-                            drawn_buffer.1,
-                        );
-                        insert_into_cache = true;
-                    } else if let Some(cached_buf) =
-                        get_image_cache(module_idx, self.id)
-                    {
-                        insert_into_cache = false;
-                        buf = (cached_buf, InsertionMode::Simple);
-                    } else {
-                        let drawn_buffer =
-                            module_container.draw(&formatting, &info);
-                        buf = (
-                            std::sync::Arc::new(drawn_buffer.0),
-                            drawn_buffer.1,
-                        );
-                        insert_into_cache = true;
-                    }
-                } else {
-                    let drawn_buffer =
-                        module_container.draw(&formatting, &info);
-                    buf = (std::sync::Arc::new(drawn_buffer.0), drawn_buffer.1);
-                    insert_into_cache = true;
-                }
+                let buf = self.draw_module(
+                    module_container,
+                    module_idx,
+                    USE_CACHE
+                        && (module_container.need_redraw()
+                            || modules_to_redraw.contains(module_name)),
+                    &formatting,
+                    info,
+                );
 
                 // if buf.1.2 {
                 //     extra_horizontal_offset = buf.1.0;
@@ -689,12 +1517,13 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                     &formatting,
                     (&mut extra_horizontal_offset, &mut extra_vertical_offset),
                 );
-                if insert_into_cache {
+                if buf.2 {
                     insert_into_image_cache(
                         module_idx,
                         self.id,
                         (*buf.0).clone(),
                         buf.1,
+                        formatting.get_cache_key(),
                     );
                 }
             }
@@ -702,6 +1531,21 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         // #[cfg(feature = "draw_debug")]
         // println!("Done with drawing modules");
 
+        // The toolbar doesn't move with the camera, the scrolled modules go below it
+        if let Some(toolbar) = toolbar {
+            render::draw_buffer_on_buffer::<true, false, false, false>(
+                &mut buffer,
+                &toolbar,
+                (0, self.menu_height as isize),
+            );
+        }
+        self.scrollbars.draw(
+            &mut buffer,
+            &self.camera,
+            (0, (self.menu_height + toolbar_height) as isize),
+            &formatting,
+        );
+
         #[cfg(feature = "draw_debug")]
         println!("4");
         self.draw_menu(
@@ -753,6 +1597,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 self.dragging = true;
                 self.x += mouse_pos_delta.0 as crate::DearMirlGuiCoordinateType;
                 self.y += mouse_pos_delta.1 as crate::DearMirlGuiCoordinateType;
+                self.clamp_to_bounds();
             } else {
                 self.dragging = false;
             }
@@ -813,6 +1658,16 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 {
                     return (None, FocusTaken::FocusFree);
                 }
+                // Auto sized axes follow the modules, not the mouse
+                let resized = corner_type_and_delta_to_metric_change::<isize>(
+                    at_corner,
+                    (1, 1),
+                );
+                if (self.auto_size.0 && resized.2 != 0)
+                    || (self.auto_size.1 && resized.3 != 0)
+                {
+                    return (None, FocusTaken::FocusFree);
+                }
                 // Do not show the option to drag if over the collapse button, that is so annoying
                 if at_corner != u8::MAX
                     && (self.resizing || !over_collapse_button)
@@ -841,29 +1696,19 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                                     .try_tuple_into()
                                     .unwrap_or_default(),
                             );
-                        let width = self.width
-                            as crate::DearMirlGuiCoordinateType
-                            + metric_change.2;
-                        if width
-                            < self.min_width as crate::DearMirlGuiCoordinateType
-                        {
-                            self.width = self.min_width;
-                        } else {
-                            self.width = width as usize;
-                            self.x += metric_change.0;
-                        }
-                        let height = self.height
-                            as crate::DearMirlGuiCoordinateType
-                            + metric_change.3;
-                        if height
-                            < self.menu_height
-                                as crate::DearMirlGuiCoordinateType
-                        {
-                            self.height = self.menu_height;
-                        } else {
-                            self.height = height as usize;
-                            self.y += metric_change.1;
-                        }
+                        // The position only changes when the left or top edge is moved
+                        let moving_edges =
+                            corner_type_and_delta_to_metric_change::<isize>(
+                                at_corner,
+                                (1, 1),
+                            );
+                        self.resize_to(
+                            (
+                                self.width as isize + metric_change.2 as isize,
+                                self.height as isize + metric_change.3 as isize,
+                            ),
+                            (moving_edges.0 != 0, moving_edges.1 != 0),
+                        );
                     }
                 } else {
                     self.at_corner = u8::MAX;
@@ -992,7 +1837,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             crate::DearMirlGuiCoordinateType::ZERO;
 
         let mut used_idx = Vec::new();
-        let formatting = get_formatting();
+        let formatting = self.get_formatting();
 
         if let Ok(modules) = MODULES.read() {
             for module_name in &self.modules {
@@ -1076,16 +1921,41 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
 
         // (min_width , min_height+ formatting.vertical_margin as isize * 2)
     }
-    /// Set the current size of the window to be able to see all modules
-    pub fn set_size_to_see_all_modules(&mut self) {
+    #[must_use]
+    /// Get the size the window needs to see all modules, including the menu and toolbar
+    pub fn get_window_size_to_see_all_modules(&self) -> (usize, usize) {
         let size = self.get_size_to_see_all_modules();
-        self.width =
-            size.0.max(self.min_width as crate::DearMirlGuiCoordinateType)
-                as usize;
-        self.height =
+        let toolbar_size = self.get_toolbar_size();
+        (
+            size.0
+                .max(self.min_width as crate::DearMirlGuiCoordinateType)
+                .max(toolbar_size.0 as crate::DearMirlGuiCoordinateType)
+                as usize,
             size.1.max(self.menu_height as crate::DearMirlGuiCoordinateType)
                 as usize
-                + self.menu_height;
+                + self.menu_height
+                + toolbar_size.1,
+        )
+    }
+    /// Set the current size of the window to be able to see all modules
+    pub fn set_size_to_see_all_modules(&mut self) {
+        (self.width, self.height) = self.get_window_size_to_see_all_modules();
+    }
+    /// Resize the auto sized axes to the size of the modules, see [`Self::auto_size`]
+    pub fn update_auto_size(&mut self) {
+        if self.auto_size == (false, false) {
+            return;
+        }
+        let size = self.get_window_size_to_see_all_modules();
+        let width = if self.auto_size.0 { size.0 } else { self.width };
+        let height = if self.auto_size.1 { size.1 } else { self.height };
+        let size_before = (self.width, self.height);
+        self.resize_to((width as isize, height as isize), (false, false));
+        if size_before != (self.width, self.height) {
+            // Content that fits again can't stay scrolled away
+            self.update_camera_bounds();
+            self.camera.clamp_to_bounds();
+        }
     }
     #[allow(clippy::too_many_lines)] // Well, clippy... it's just... big. :(
     fn internal_update(
@@ -1093,7 +1963,21 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         module_input: ModuleUpdateInfo,
         module_outputs: &GuiOutput,
     ) -> GuiOutput {
-        self.needs_redraw = true; // TODO: This could probably be done more efficiently
+        if !self.open {
+            let mut output = GuiOutput::empty();
+            output.window_events = std::mem::take(&mut self.events);
+            return output;
+        }
+        // Anything changing these has to be redrawn, modules report their own changes
+        let layout_before = (
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            self.collapsed,
+            self.camera.offset_x,
+            self.camera.offset_y,
+        );
         let horizontal_context = false;
         let mut module_input = module_input;
         //println!(">{:?}", module_input.container_id);
@@ -1112,8 +1996,12 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         // Is this like a form of schizophrenia or a parasocial thing?
         // Temporal collab :fire: :fire: :fire:
 
+        // Keybinds only go to the window below the mouse, not to the ones behind it
+        let keybinds_allowed = !module_input.focus_taken.is_focus_taken();
         let mut gui_in_focus = module_input.focus_taken;
         let mut cursor_style = module_outputs.new_cursor_style;
+        let hovered_title_bar_button_before =
+            self.hovered_title_bar_button.take();
 
         if !gui_in_focus {
             //println!(">>>{:?}", gui_in_focus);
@@ -1182,6 +2070,22 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                     gui_in_focus |= FocusTaken::VisuallyTaken;
                 }
             }
+            if !(self.dragging || self.resizing)
+                && let Some(current_mouse_pos) = module_input.mouse_pos
+                && let Some(button) =
+                    self.get_title_bar_button_at(current_mouse_pos)
+            {
+                // Title bar buttons block dragging and resizing just like the collapse button
+                over_collapse_button = true;
+                self.hovered_title_bar_button = Some(button);
+                cursor_style = Some(CursorStyle::ContextMenu);
+                if module_input.mouse_info.left.clicked {
+                    self.click_title_bar_button(button);
+                    gui_in_focus |= FocusTaken::FunctionallyTaken;
+                } else {
+                    gui_in_focus |= FocusTaken::VisuallyTaken;
+                }
+            }
 
             // If the mouse position is invalid, reset it
             if self.last_mouse_pos == (f32::MIN, f32::MIN)
@@ -1197,7 +2101,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             // Dragging has priority, not because it should but because it's ordered like this
 
             // Handle dragging
-            if self.allow_dragging {
+            if self.allow_dragging && !self.pinned {
                 let dragging_output = self.handle_dragging(
                     module_input.real_mouse_pos,
                     module_input.mouse_pos_delta,
@@ -1217,7 +2121,7 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 gui_in_focus |= dragging_output.1;
             }
             // Handle resizing
-            if !self.collapsed {
+            if !self.collapsed && !self.pinned {
                 let resizing_output = self.handle_resizing(
                     module_input.real_mouse_pos,
                     module_input.mouse_pos_delta,
@@ -1238,18 +2142,32 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 gui_in_focus |= resizing_output.1;
             }
         }
-        let formatting = get_formatting();
+        let formatting = self.get_formatting();
 
         let mut hide_cursor = false;
         let mut text_input_selected = false;
         let mut new_cursor_position = None;
         let mut new_clipboard_data = None;
         let mut request_clipboard_data = false;
+        let mut selected_menu_item = None;
 
-        let cursor_offset = (0.0, 0.0)
+        let toolbar_height = self.get_toolbar_size().1 as f32;
+        let window_offset: (f32, f32) =
+            (0.0, 0.0).sub((self.x, self.y).try_tuple_into().unwrap_or_default());
+        let cursor_offset = window_offset
             .sub((formatting.horizontal_margin as f32, 0.0))
-            .sub((self.x, self.y).try_tuple_into().unwrap_or_default())
-            .sub((0.0, self.menu_height as f32));
+            .sub((0.0, self.menu_height as f32 + toolbar_height));
+        let toolbar_cursor_offset = window_offset.sub((
+            formatting.horizontal_margin as f32,
+            (self.menu_height + formatting.vertical_margin) as f32,
+        ));
+        // The toolbar covers the modules scrolled below it
+        let over_toolbar = module_input.mouse_pos.is_some_and(|pos| {
+            let y = pos.add(window_offset).1;
+            y >= self.menu_height as f32
+                && y < self.menu_height as f32 + toolbar_height
+        });
+        let mut toolbar_horizontal_offset = 0.0;
 
         let static_vertical_offset =
             self.camera.offset_y as f32 + formatting.horizontal_margin as f32;
@@ -1260,30 +2178,148 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
         let mut extra_horizontal_offset: crate::DearMirlGuiCoordinateType =
             crate::DearMirlGuiCoordinateType::ZERO;
 
+        // The scrollbars lie above the modules so they get the input first
+        if !self.collapsed {
+            self.update_camera_bounds();
+            let scrollbars_before = self.scrollbars;
+            let mut scrollbar_input = module_input;
+            scrollbar_input.focus_taken = gui_in_focus;
+            let scrollbar_output = self.scrollbars.update(
+                &mut self.camera,
+                module_input.mouse_pos.map(|pos| {
+                    pos.add(window_offset)
+                        .sub((0.0, self.menu_height as f32 + toolbar_height))
+                }),
+                &scrollbar_input,
+            );
+            if scrollbars_before != self.scrollbars {
+                self.needs_redraw = true;
+            }
+            if gui_in_focus < scrollbar_output.focus_taken
+                && scrollbar_output.new_cursor_style.is_some()
+            {
+                cursor_style = scrollbar_output.new_cursor_style;
+            }
+            gui_in_focus |= scrollbar_output.focus_taken;
+            module_input.focus_taken |= scrollbar_output.focus_taken;
+            if scrollbar_output.focus_taken == FocusTaken::FunctionallyTaken {
+                // The scrollbars moved the camera themselves
+                self.smooth_scroll.stop();
+            }
+
+            scrollbar_input.focus_taken = gui_in_focus;
+            let dragging_output = self.smooth_scroll.update_dragging(
+                &mut self.camera,
+                module_input.mouse_pos.map(|pos| {
+                    pos.add(window_offset)
+                        .sub((0.0, self.menu_height as f32 + toolbar_height))
+                }),
+                &scrollbar_input,
+            );
+            if gui_in_focus < dragging_output.focus_taken
+                && dragging_output.new_cursor_style.is_some()
+            {
+                cursor_style = dragging_output.new_cursor_style;
+            }
+            gui_in_focus |= dragging_output.focus_taken;
+            module_input.focus_taken |= dragging_output.focus_taken;
+            self.smooth_scroll
+                .update(&mut self.camera, module_input.delta_time as f32);
+        }
+
         let mut module_idx_cache = Vec::new();
+        let mut right_clicked_module = None;
         let local_pos = module_input.mouse_pos; // Yay, finally fixed the issue with guis inside guis not using local mouse positioning
         if let Ok(modules) = MODULES.read() {
-            for module_name in &self.modules {
+            // Toolbar modules are updated first as they are drawn on top
+            let toolbar_modules =
+                self.toolbar_modules.iter().map(|x| (true, x));
+            for (in_toolbar, module_name) in
+                toolbar_modules.chain(self.modules.iter().map(|x| (false, x)))
+            {
                 let Some(module_idx) = get_idx_of_id(*module_name) else {
                     continue;
                 };
                 let module = &modules[module_idx];
-                module_idx_cache.push(module_idx);
 
                 //let height = module.get_height(&formatting);
-                let position = local_pos.map(|input| {
-                    input
-                        .add(cursor_offset)
-                        .sub(
-                            (extra_horizontal_offset, extra_vertical_offset)
-                                .try_tuple_into()
-                                .unwrap_or_default(),
-                        )
-                        .sub((static_horizontal_offset, static_vertical_offset))
-                });
-                module_input.mouse_pos = position;
+                if in_toolbar {
+                    module_input.mouse_pos = local_pos.map(|input| {
+                        input
+                            .add(toolbar_cursor_offset)
+                            .sub((toolbar_horizontal_offset, 0.0))
+                    });
+                } else {
+                    module_idx_cache.push(module_idx);
+                    module_input.mouse_pos = local_pos
+                        .filter(|_| !over_toolbar)
+                        .map(|input| {
+                            input
+                                .add(cursor_offset)
+                                .sub(
+                                    (
+                                        extra_horizontal_offset,
+                                        extra_vertical_offset,
+                                    )
+                                        .try_tuple_into()
+                                        .unwrap_or_default(),
+                                )
+                                .sub((
+                                    static_horizontal_offset,
+                                    static_vertical_offset,
+                                ))
+                        });
+                }
 
-                let module_output = module.update(&module_input);
+                if !in_toolbar {
+                    // Where the visible part of the window is, seen from the module
+                    let origin = (
+                        formatting.horizontal_margin as f32
+                            + extra_horizontal_offset as f32
+                            + static_horizontal_offset,
+                        extra_vertical_offset as f32 + static_vertical_offset,
+                    );
+                    module.set_available_area(crate::DirtyRect::new(
+                        (-origin.0 as isize, -origin.1 as isize),
+                        (
+                            self.camera.container_width.max(0.0) as usize,
+                            self.camera.container_height.max(0.0) as usize,
+                        ),
+                    ));
+                }
+                let focus_before_update = gui_in_focus;
+                let mut module_output = module.update(&module_input);
+                // Asking resets the flag, so it is remembered until the next render
+                if module.need_redraw() {
+                    self.needs_redraw = true;
+                    if !self.modules_to_redraw.contains(module_name) {
+                        self.modules_to_redraw.push(*module_name);
+                    }
+                }
+                // Applied after the update as the click on the menu took the focus of this frame
+                if self
+                    .pending_context_menu_action
+                    .as_ref()
+                    .is_some_and(|(module_id, _)| module_id == module_name)
+                    && let Some((_, item_id)) =
+                        self.pending_context_menu_action.take()
+                {
+                    module_output |=
+                        module.context_menu_action(&item_id, &module_input);
+                }
+                if module_input.mouse_info.right.clicked
+                    && focus_before_update != FocusTaken::FunctionallyTaken
+                    && module_output.focus_taken
+                        != FocusTaken::FunctionallyTaken
+                    && module_input.mouse_pos.is_some_and(|pos| {
+                        pos.0 >= 0.0
+                            && pos.1 >= 0.0
+                            && pos.0 < module.get_width(&formatting) as f32
+                            && pos.1 < module.get_height(&formatting) as f32
+                    })
+                {
+                    right_clicked_module = Some(*module_name);
+                }
 
                 // Setting variables based on module output
                 request_clipboard_data = request_clipboard_data
@@ -1305,6 +2341,13 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                         module_output.focus_taken
                     );
                 }
+                if !in_toolbar
+                    && focus_before_update != FocusTaken::FunctionallyTaken
+                    && module_output.focus_taken
+                        == FocusTaken::FunctionallyTaken
+                {
+                    self.selected_module = Some(*module_name);
+                }
                 gui_in_focus |= module_output.focus_taken;
                 module_input.focus_taken = gui_in_focus;
                 text_input_selected =
@@ -1316,7 +2359,17 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 if module_output.new_clipboard_data.is_some() {
                     new_clipboard_data = module_output.new_clipboard_data;
                 }
+                if module_output.selected_menu_item.is_some() {
+                    selected_menu_item = module_output.selected_menu_item;
+                }
 
+                if in_toolbar {
+                    toolbar_horizontal_offset += (module.get_width(&formatting)
+                        + formatting.horizontal_margin
+                            as crate::DearMirlGuiCoordinateType)
+                        as f32;
+                    continue;
+                }
                 if horizontal_context {
                     extra_horizontal_offset += module.get_width(&formatting);
                 } else {
@@ -1332,6 +2385,8 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                 );
             }
         }
+        // The module might have been removed in the meantime
+        self.pending_context_menu_action = None;
 
         if gui_in_focus != FocusTaken::FunctionallyTaken
             && let Some(mouse_pos) = local_pos
@@ -1350,7 +2405,13 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             if window_hit_box.does_area_contain_point(mouse_pos)
                 || self.resizing
             {
-                if module_input.mouse_scroll == (0.0, 0.0) {
+                if module_input.mouse_info.right.clicked
+                    && let Some(real_mouse_pos) = module_input.real_mouse_pos
+                    && self
+                        .show_context_menu(right_clicked_module, real_mouse_pos)
+                {
+                    gui_in_focus = FocusTaken::FunctionallyTaken;
+                } else if module_input.mouse_scroll == (0.0, 0.0) {
                     // This is if the cursor is on top on the gui but not interacting with anything
                     if module_input.mouse_info.left.clicked {
                         gui_in_focus = FocusTaken::FunctionallyTaken;
@@ -1358,17 +2419,9 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
                         gui_in_focus = FocusTaken::VisuallyTaken;
                     }
                 } else {
-                    let size = self
-                        .get_size_to_see_all_modules()
-                        .try_tuple_into()
-                        .unwrap_or_default();
-
-                    self.camera.container_width = Self::get_width(self) as f32;
-                    self.camera.container_height =
-                        Self::get_height(self) as f32 - self.menu_height as f32;
-                    self.camera.content_height = size.1;
-                    self.camera.content_width = size.0;
-                    self.camera.scroll(
+                    self.update_camera_bounds();
+                    self.smooth_scroll.scroll(
+                        &mut self.camera,
                         module_input.mouse_scroll,
                         !module_input
                             .pressed_keys
@@ -1429,6 +2482,14 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             }
         }
 
+        // Selected text inputs use the keys themselves
+        self.update_keybinds(
+            module_input.pressed_keys,
+            keybinds_allowed
+                && gui_in_focus.is_focus_taken()
+                && !text_input_selected,
+        );
+
         if self.collapsed {
             if cursor_style.is_none() {
                 gui_in_focus = FocusTaken::FocusFree;
@@ -1437,9 +2498,39 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             }
         }
 
+        // The modules may have changed their size during the update
+        self.update_auto_size();
+
         self.last_left_mouse_down = module_input.mouse_info.left.down;
         self.last_middle_mouse_down = module_input.mouse_info.middle.down;
         self.last_right_mouse_down = module_input.mouse_info.right.down;
+        if layout_before
+            != (
+                self.x,
+                self.y,
+                self.width,
+                self.height,
+                self.collapsed,
+                self.camera.offset_x,
+                self.camera.offset_y,
+            )
+            || hovered_title_bar_button_before != self.hovered_title_bar_button
+        {
+            self.needs_redraw = true;
+        }
+        if (layout_before.0, layout_before.1) != (self.x, self.y) {
+            self.events.push(crate::WindowEvent::Moved(self.id));
+        }
+        if (layout_before.2, layout_before.3) != (self.width, self.height) {
+            self.events.push(crate::WindowEvent::Resized(self.id));
+        }
+        if layout_before.4 != self.collapsed {
+            self.events.push(if self.collapsed {
+                crate::WindowEvent::Collapsed(self.id)
+            } else {
+                crate::WindowEvent::Expanded(self.id)
+            });
+        }
         #[cfg(any(feature = "draw_debug", feature = "focus_debug"))]
         if gui_in_focus == FocusTaken::FunctionallyTaken {
             cursor_style = Some(CursorStyle::Cell);
@@ -1452,7 +2543,174 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGui<FAST, USE_CACHE> {
             new_clipboard_data,
             text_input_selected,
             request_clipboard_data,
+            selected_menu_item,
+            window_events: std::mem::take(&mut self.events),
+        }
+    }
+    /// Open the right click menu of a module at a position on screen, falls back to the [`context_menu`](Self::context_menu) of the window
+    ///
+    /// Returns if a menu has been opened
+    pub fn show_context_menu(
+        &mut self,
+        module: Option<u32>,
+        position: (f32, f32),
+    ) -> bool {
+        let menu = module
+            .and_then(|id| {
+                crate::module_manager::get_context_menu(id)
+                    .map(|menu| (menu, ContextMenuSource::Registry(id)))
+                    .or_else(|| {
+                        get_module_raw_mut(id, |module| {
+                            module.get_context_menu()
+                        })
+                        .flatten()
+                        .map(|menu| (menu, ContextMenuSource::Module(id)))
+                    })
+            })
+            .or_else(|| {
+                self.context_menu
+                    .clone()
+                    .map(|menu| (menu, ContextMenuSource::Window))
+            });
+        let Some((menu, source)) = menu else {
+            return false;
+        };
+        self.open_context_menu = Some(ContextMenu::new(
+            menu,
+            (position.0 as isize, position.1 as isize),
+            source,
+        ));
+        self.needs_redraw = true;
+        true
+    }
+    /// Close the opened right click menu
+    pub fn close_context_menu(&mut self) {
+        if self.open_context_menu.take().is_some() {
+            self.needs_redraw = true;
+        }
+    }
+    /// Apply the clicked item of a closed right click menu
+    fn select_context_menu_item(
+        &mut self,
+        context_menu: ContextMenu,
+        item_id: String,
+    ) -> GuiOutput {
+        match context_menu.source {
+            ContextMenuSource::Registry(module_id) => {
+                // Keep the checked states
+                if let Ok(mut menus) =
+                    crate::module_manager::CONTEXT_MENUS.write()
+                    && let Some(menu) = menus.get_mut(&module_id)
+                {
+                    *menu = context_menu.menu;
+                }
+                GuiOutput::empty().with_selected_menu_item(Some(item_id))
+            }
+            ContextMenuSource::Module(module_id) => {
+                self.pending_context_menu_action = Some((module_id, item_id));
+                GuiOutput::empty()
+            }
+            ContextMenuSource::Window => {
+                self.context_menu = Some(context_menu.menu);
+                GuiOutput::empty().with_selected_menu_item(Some(item_id))
+            }
+        }
+    }
+    /// Draw what the modules show above all windows, e.g. opened menus, positions are on screen
+    pub fn draw_overlays(&self) -> Vec<(Buffer, (isize, isize))> {
+        let mut overlays = Vec::new();
+        if self.collapsed || !self.open {
+            return overlays;
+        }
+        let formatting = self.get_formatting();
+        let info = ModuleDrawInfo {
+            container_id: self.id,
+        };
+        if let Ok(modules) = MODULES.read() {
+            for module_name in self.toolbar_modules.iter().chain(&self.modules)
+            {
+                let Some(module_idx) = get_idx_of_id(*module_name) else {
+                    continue;
+                };
+                overlays.extend(
+                    modules[module_idx].draw_overlay(&formatting, &info),
+                );
+            }
+        }
+        if let Some(context_menu) = &self.open_context_menu {
+            overlays.extend(
+                context_menu
+                    .popup
+                    .draw(&context_menu.menu.entries, &formatting),
+            );
+        }
+        overlays
+    }
+    /// Update what the modules show above all windows, called before any window is updated
+    ///
+    /// The mouse position is expected to be on screen
+    pub fn update_overlays(
+        &mut self,
+        module_input: ModuleUpdateInfo,
+    ) -> GuiOutput {
+        let mut output = GuiOutput::empty();
+        if self.collapsed || !self.open {
+            return output;
+        }
+        let mut module_input = module_input;
+        module_input.container_id = self.id;
+        module_input.mouse_pos = module_input.real_mouse_pos;
+        // The right click menu is drawn last so it gets the input first
+        if let Some(mut context_menu) = self.open_context_menu.take() {
+            let formatting = self.get_formatting();
+            let previous = context_menu.popup.clone();
+            let (event, cursor_style) = context_menu.popup.update(
+                &mut context_menu.menu.entries,
+                module_input.mouse_pos,
+                &module_input,
+                &formatting,
+            );
+            if previous != context_menu.popup {
+                self.needs_redraw = true;
+            }
+            let focus_output =
+                GuiOutput::default(FocusTaken::FunctionallyTaken)
+                    .with_optional_cursor(cursor_style);
+            match event {
+                PopupEvent::None => {
+                    self.open_context_menu = Some(context_menu);
+                }
+                PopupEvent::Hovered => {
+                    self.open_context_menu = Some(context_menu);
+                    return focus_output;
+                }
+                PopupEvent::Selected(item_id) => {
+                    self.needs_redraw = true;
+                    return focus_output
+                        | self.select_context_menu_item(context_menu, item_id);
+                }
+                PopupEvent::Dismissed => {
+                    self.needs_redraw = true;
+                    return focus_output;
+                }
+            }
+        }
+        if let Ok(modules) = MODULES.read() {
+            for module_name in self.toolbar_modules.iter().chain(&self.modules)
+            {
+                let Some(module_idx) = get_idx_of_id(*module_name) else {
+                    continue;
+                };
+                let module_output =
+                    modules[module_idx].update_overlay(&module_input);
+                module_input.focus_taken |= module_output.focus_taken;
+                output |= module_output;
+            }
         }
+        if output.focus_taken.is_focus_taken() {
+            self.needs_redraw = true;
+        }
+        output
     }
     /// Set the initial state of the window to be closed
     #[must_use]
@@ -1597,3 +2855,139 @@ fn remove(list: &mut Vec<u32>, name: u32, id: usize) {
         x.removed(id);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A window without a font, [`DearMirlGui::new_advanced`] requires one
+    fn window(size: (usize, usize)) -> DearMirlGui<false, false> {
+        DearMirlGui {
+            title: String::new(),
+            width: size.0,
+            height: size.1,
+            modules: Vec::new(),
+            toolbar_modules: Vec::new(),
+            toolbar_cache: None,
+            context_menu: None,
+            open_context_menu: None,
+            pending_context_menu_action: None,
+            stacking: WindowStacking::Normal,
+            modules_to_redraw: Vec::new(),
+            scrollbars: crate::Scrollbars::new(),
+            smooth_scroll: crate::SmoothScroll::disabled(),
+            open: true,
+            closable: false,
+            pinnable: false,
+            pinned: false,
+            title_bar_buttons: Vec::new(),
+            hovered_title_bar_button: None,
+            events: Vec::new(),
+            min_height: 0,
+            max_width: None,
+            max_height: None,
+            aspect_ratio: None,
+            bounds: None,
+            auto_size: (false, false),
+            formatting: None,
+            last_mouse_pos: (0.0, 0.0),
+            last_left_mouse_down: false,
+            last_middle_mouse_down: false,
+            last_right_mouse_down: false,
+            x: 0 as crate::DearMirlGuiCoordinateType,
+            y: 0 as crate::DearMirlGuiCoordinateType,
+            resizing: false,
+            at_corner: u8::MAX,
+            dragging: false,
+            menu_height: 20,
+            min_width: 40,
+            collapsed: false,
+            collapse_button_collision_is_circle: false,
+            needs_redraw: true,
+            resizing_allowed_in_directions: NormalDirections::all_true(),
+            allow_dragging: true,
+            camera: mirl::misc::ScrollableCamera {
+                container_width: size.0 as f32,
+                container_height: size.1 as f32,
+                content_width: 0.0,
+                content_height: 0.0,
+                offset_x: 0.0,
+                offset_y: 0.0,
+                scroll_multiplier_x: 1.0,
+                scroll_multiplier_y: 1.0,
+                horizontal_context_switch_multipliers: true,
+                allow_free_scroll: true,
+            },
+            id: 0,
+            size_to_see_all_modules: None,
+            keybinds: Vec::new(),
+            last_keys_pressed: Vec::new(),
+            selected_module: None,
+        }
+    }
+    fn position(
+        window: &DearMirlGui<false, false>,
+    ) -> (isize, isize) {
+        (window.x as isize, window.y as isize)
+    }
+
+    #[test]
+    fn size_is_clamped_to_the_constraints() {
+        let mut gui = window((10, 5));
+        gui.min_height = 30;
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (40, 30));
+
+        gui.max_width = Some(100);
+        gui.max_height = Some(60);
+        gui.width = 500;
+        gui.height = 500;
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (100, 60));
+
+        // The minimum wins over a smaller maximum, the title has to fit
+        gui.max_width = Some(10);
+        gui.apply_size_constraints();
+        assert_eq!(gui.width, 40);
+    }
+
+    #[test]
+    fn aspect_ratio_is_kept_within_the_constraints() {
+        let mut gui = window((100, 100));
+        gui.aspect_ratio = Some(2.0);
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (100, 50));
+
+        gui.max_height = Some(40);
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (80, 40));
+    }
+
+    #[test]
+    fn windows_are_moved_into_their_bounds_before_shrinking() {
+        let mut gui = window((50, 50));
+        gui.x = 90 as crate::DearMirlGuiCoordinateType;
+        gui.y = -10 as crate::DearMirlGuiCoordinateType;
+        gui.bounds = Some(crate::DirtyRect::new((0, 0), (100, 100)));
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (50, 50));
+        assert_eq!(position(&gui), (50, 0));
+
+        // Too big for the bounds, aligned to their top left
+        gui.width = 300;
+        gui.height = 150;
+        gui.apply_size_constraints();
+        assert_eq!((gui.width, gui.height), (100, 100));
+        assert_eq!(position(&gui), (0, 0));
+    }
+
+    #[test]
+    fn collapsed_windows_only_need_room_for_the_menu() {
+        let mut gui = window((50, 80));
+        gui.collapsed = true;
+        gui.y = 90 as crate::DearMirlGuiCoordinateType;
+        gui.bounds = Some(crate::DirtyRect::new((0, 0), (100, 100)));
+        gui.clamp_to_bounds();
+        assert_eq!(position(&gui), (0, 80));
+    }
+}
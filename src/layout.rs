@@ -0,0 +1,310 @@
+use crate::DearMirlGui;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+/// The saved state of a single window
+pub struct WindowLayout {
+    #[allow(missing_docs)]
+    pub x: crate::DearMirlGuiCoordinateType,
+    #[allow(missing_docs)]
+    pub y: crate::DearMirlGuiCoordinateType,
+    #[allow(missing_docs)]
+    pub width: usize,
+    #[allow(missing_docs)]
+    pub height: usize,
+    #[allow(missing_docs)]
+    pub collapsed: bool,
+    /// The position in the z order, 0 is in front
+    pub z: usize,
+    /// How far the content has been scrolled
+    pub camera_offset: (f32, f32),
+}
+impl WindowLayout {
+    /// Save the state of a window
+    #[must_use]
+    pub const fn of_window<const FAST: bool, const USE_CACHE: bool>(
+        window: &DearMirlGui<FAST, USE_CACHE>,
+        z: usize,
+    ) -> Self {
+        Self {
+            x: window.x,
+            y: window.y,
+            width: window.width,
+            height: window.height,
+            collapsed: window.collapsed,
+            z,
+            camera_offset: (window.camera.offset_x, window.camera.offset_y),
+        }
+    }
+    /// Restore the state of a window, the z order is handled by the [`DearMirlGuiManager`](crate::DearMirlGuiManager)
+    ///
+    /// The size constraints and bounds of the window still apply, a stale or hand edited layout can't break them
+    pub fn apply<const FAST: bool, const USE_CACHE: bool>(
+        &self,
+        window: &mut DearMirlGui<FAST, USE_CACHE>,
+    ) {
+        window.x = self.x;
+        window.y = self.y;
+        window.width = self.width;
+        window.height = self.height;
+        window.collapsed = self.collapsed;
+        window.camera.offset_x = self.camera_offset.0;
+        window.camera.offset_y = self.camera_offset.1;
+        window.apply_size_constraints();
+        window.needs_redraw = true;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The state of all windows of a [`DearMirlGuiManager`](crate::DearMirlGuiManager), windows are identified by their title as ids change between runs
+///
+/// Windows sharing a title are told apart by the order they have been created in: the second one is saved as `Title##1`, the third as `Title##2`...
+///
+/// Stored in a small ini like text format, similar to `imgui.ini`:
+/// ```text
+/// [Window][Gui Window]
+/// Pos=100,10
+/// Size=300,200
+/// Collapsed=0
+/// Z=0
+/// Scroll=0,-20
+/// ```
+pub struct LayoutSettings {
+    /// The key of each window (see [`get_keys`](Self::get_keys)) and its state, in the order they are saved in
+    pub windows: Vec<(String, WindowLayout)>,
+}
+impl LayoutSettings {
+    /// Get the saved state of the window with the given key
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&WindowLayout> {
+        self.windows
+            .iter()
+            .find(|(window_key, _)| window_key == key)
+            .map(|(_, layout)| layout)
+    }
+    /// Get the key the layout of each window is saved under, in the order of the given windows
+    ///
+    /// The title, windows sharing a title get `##1`, `##2`... appended in the order of their ids
+    #[must_use]
+    pub fn get_keys<const FAST: bool, const USE_CACHE: bool>(
+        windows: &[DearMirlGui<FAST, USE_CACHE>],
+    ) -> Vec<String> {
+        get_keys_of_titles(
+            &windows
+                .iter()
+                .map(|window| (window.title.as_str(), window.id))
+                .collect::<Vec<_>>(),
+        )
+    }
+    /// Convert into the text format
+    ///
+    /// Backslashes, line breaks and `]` in the keys are escaped with a backslash
+    #[must_use]
+    pub fn to_ini_string(&self) -> String {
+        use std::fmt::Write;
+
+        let mut output = String::new();
+        for (title, layout) in &self.windows {
+            let _ = write!(
+                output,
+                "[Window][{}]\nPos={},{}\nSize={},{}\nCollapsed={}\nZ={}\nScroll={},{}\n\n",
+                escape_key(title),
+                layout.x,
+                layout.y,
+                layout.width,
+                layout.height,
+                u8::from(layout.collapsed),
+                layout.z,
+                layout.camera_offset.0,
+                layout.camera_offset.1,
+            );
+        }
+        output
+    }
+    /// Read the text format, unknown sections, keys and invalid values are skipped
+    #[must_use]
+    pub fn from_ini_str(text: &str) -> Self {
+        let mut windows: Vec<(String, WindowLayout)> = Vec::new();
+        let mut in_window = false;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') {
+                in_window = false;
+                if let Some(title) = line
+                    .strip_prefix("[Window][")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                {
+                    windows
+                        .push((unescape_key(title), WindowLayout::default()));
+                    in_window = true;
+                }
+                continue;
+            }
+            let (Some((_, layout)), Some((key, value))) = (
+                windows.last_mut().filter(|_| in_window),
+                line.split_once('='),
+            ) else {
+                continue;
+            };
+            match key.trim() {
+                "Pos" => {
+                    if let Some((x, y)) = parse_pair::<f64>(value) {
+                        layout.x = x as crate::DearMirlGuiCoordinateType;
+                        layout.y = y as crate::DearMirlGuiCoordinateType;
+                    }
+                }
+                "Size" => {
+                    if let Some(size) = parse_pair(value) {
+                        (layout.width, layout.height) = size;
+                    }
+                }
+                "Collapsed" => layout.collapsed = value.trim() == "1",
+                "Z" => {
+                    if let Ok(z) = value.trim().parse() {
+                        layout.z = z;
+                    }
+                }
+                "Scroll" => {
+                    if let Some(offset) = parse_pair(value) {
+                        layout.camera_offset = offset;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self { windows }
+    }
+}
+
+fn parse_pair<T: std::str::FromStr>(value: &str) -> Option<(T, T)> {
+    let (first, second) = value.split_once(',')?;
+    Some((first.trim().parse().ok()?, second.trim().parse().ok()?))
+}
+
+/// [`LayoutSettings::get_keys`] for the title and id of each window
+fn get_keys_of_titles(windows: &[(&str, usize)]) -> Vec<String> {
+    let mut by_id: Vec<usize> = (0..windows.len()).collect();
+    by_id.sort_by_key(|idx| windows[*idx].1);
+    let mut keys = vec![String::new(); windows.len()];
+    let mut used = std::collections::HashSet::new();
+    let mut occurrences: std::collections::HashMap<&str, usize> =
+        std::collections::HashMap::new();
+    for idx in by_id {
+        let title = windows[idx].0;
+        let occurrence = occurrences.entry(title).or_default();
+        let mut key = if *occurrence == 0 {
+            title.to_string()
+        } else {
+            format!("{title}##{occurrence}")
+        };
+        // Another title may already end in `##1`
+        while used.contains(&key) {
+            *occurrence += 1;
+            key = format!("{title}##{occurrence}");
+        }
+        *occurrence += 1;
+        used.insert(key.clone());
+        keys[idx] = key;
+    }
+    keys
+}
+
+fn escape_key(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+    for character in key.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ']' => escaped.push_str("\\]"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn unescape_key(key: &str) -> String {
+    let mut unescaped = String::with_capacity(key.len());
+    let mut characters = key.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(seed: usize) -> WindowLayout {
+        WindowLayout {
+            x: (seed * 10) as crate::DearMirlGuiCoordinateType,
+            y: -(seed as crate::DearMirlGuiCoordinateType),
+            width: 300 + seed,
+            height: 200,
+            collapsed: seed % 2 == 1,
+            z: seed,
+            camera_offset: (0.0, -20.25),
+        }
+    }
+
+    #[test]
+    fn ini_round_trip() {
+        let settings = LayoutSettings {
+            windows: Vec::from([
+                ("Gui Window".to_string(), layout(0)),
+                ("Gui Window##1".to_string(), layout(1)),
+                ("Multi\nline\r".to_string(), layout(2)),
+                ("Ends with ]".to_string(), layout(3)),
+                ("[Window][x] \\ \\n".to_string(), layout(4)),
+                (String::new(), layout(5)),
+            ]),
+        };
+        let text = settings.to_ini_string();
+        // Every window is a single section
+        assert_eq!(text.matches("[Window][").count(), settings.windows.len());
+        assert_eq!(LayoutSettings::from_ini_str(&text), settings);
+    }
+
+    #[test]
+    fn invalid_lines_are_skipped() {
+        let settings = LayoutSettings::from_ini_str(
+            "; comment\n[Docking][Data]\nPos=1,2\n[Window][A]\nPos=5,x\nSize=10,20\nUnknown=1\nZ=-1\n",
+        );
+        assert_eq!(settings.windows.len(), 1);
+        assert_eq!(settings.windows[0].0, "A");
+        let a = settings.windows[0].1;
+        let origin = WindowLayout::default();
+        assert_eq!((a.x, a.y), (origin.x, origin.y));
+        assert_eq!((a.width, a.height, a.z), (10, 20, 0));
+        assert!(settings.get("Docking").is_none());
+    }
+
+    #[test]
+    fn duplicate_titles_get_numbered_keys_in_id_order() {
+        let keys = get_keys_of_titles(&[
+            ("Tool", 7),
+            ("Main", 1),
+            ("Tool", 3),
+            ("Tool", 5),
+        ]);
+        assert_eq!(keys, ["Tool##2", "Main", "Tool", "Tool##1"]);
+        // A title that looks like a numbered key doesn't share it
+        let keys =
+            get_keys_of_titles(&[("Tool", 1), ("Tool##1", 2), ("Tool", 3)]);
+        assert_eq!(keys, ["Tool", "Tool##1", "Tool##2"]);
+        let keys =
+            get_keys_of_titles(&[("Tool##1", 1), ("Tool", 2), ("Tool", 3)]);
+        assert_eq!(keys, ["Tool##1", "Tool", "Tool##2"]);
+    }
+}
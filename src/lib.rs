@@ -117,7 +117,6 @@
 //! **[9]** Crank module rotation is slightly offset
 //! **[7]** (Plugin makers only) Single insert mode overwrites the image data of other modules, use the replace all option
 //! **[4]** Remove few usages of `.unwrap()` that are left, they only occur for very, very, specific circumstances yet we'd rather want to deal with another `Option<T>` than a crash, right?
-//! **[2]** Module registered windows are able to be resized outside of their parent container (Fine if already dragging but should not prompt the user to start dragging)
 //!
 //! ### To add:
 //! **[4]** Color picker missing
@@ -149,12 +148,46 @@ pub mod modules;
 
 /// The `DearMirlGui` defining file
 pub mod gui;
-pub use gui::DearMirlGui;
+pub use gui::{
+    DearMirlGui, DearMirlGuiBuilder, DearMirlGuiBuilderError, WindowStacking,
+};
 
 // A struct to handle having multiple guis at once
 mod window_manager;
 pub use window_manager::*;
 
+// Windows that capture all input until they are closed
+mod modal;
+pub use modal::*;
+
+// Windows docked next to each other or stacked as tabs
+mod docking;
+pub use docking::*;
+
+// Tracking which areas of the screen changed
+mod dirty_rect;
+pub use dirty_rect::*;
+
+// Saving and restoring where the windows are
+mod layout;
+pub use layout::*;
+
+// Visible scrollbars for scrollable containers
+mod scrollbar;
+pub use scrollbar::*;
+
+// Animated and kinetic scrolling for scrollable containers
+mod smooth_scroll;
+pub use smooth_scroll::*;
+
+// Close, pin and custom buttons on the title bar of a window
+mod title_bar;
+pub use title_bar::*;
+
+// What happened to a window
+mod window_event;
+pub use window_event::*;
+
 /// All required components
 pub mod prelude;
 
@@ -171,6 +204,10 @@ pub use focus_taken::*;
 mod formatting;
 pub use formatting::*;
 
+// The colors of widget states and how their shapes are drawn, part of the formatting
+mod theme;
+pub use theme::*;
+
 // Used for telling the compiler what module is what type
 mod module_path;
 pub use module_path::*;
@@ -234,6 +271,41 @@ pub trait DearMirlGuiModule:
     /// When you have updated the formatting and wish to properly apply the change to all modules
     fn apply_new_formatting(&mut self, formatting: &crate::Formatting) {}
     #[allow(unused_variables)]
+    /// Draw what is shown above all windows (e.g. opened menus), each buffer is drawn at the given position on screen by the [`DearMirlGuiManager`]
+    fn draw_overlay(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> Vec<(mirl::prelude::Buffer, (isize, isize))> {
+        Vec::new()
+    }
+    #[allow(unused_variables)]
+    /// Update what is drawn in [`draw_overlay`](Self::draw_overlay), called before any window is updated with the mouse position on screen
+    fn update_overlay(
+        &mut self,
+        inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+    #[allow(unused_variables)]
+    /// Called before each update with the visible area of the window the module is in, relative to the top left of the module
+    ///
+    /// Nested windows use it to stay inside of their parent while being resized
+    fn set_available_area(&mut self, area: crate::DirtyRect) {}
+    /// The menu that opens when right clicking the module, menus set using [`set_context_menu`](crate::module_manager::set_context_menu) take priority
+    fn get_context_menu(&mut self) -> Option<crate::modules::Menu> {
+        None
+    }
+    #[allow(unused_variables)]
+    /// Called when an item of the menu returned by [`get_context_menu`](Self::get_context_menu) has been clicked
+    fn context_menu_action(
+        &mut self,
+        id: &str,
+        inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+    #[allow(unused_variables)]
     /// When a element has been added to a container, this will also be called when moving a module from one container to another
     fn added(&mut self, container_id: usize) {}
     #[allow(unused_variables)]
@@ -317,6 +389,7 @@ mod struct_editor_test;
 
 #[test]
 #[cfg(not(feature = "debug-window"))]
+#[allow(clippy::panic)]
 fn main() {
     panic!(
         "The debug-window flag has not been set. You can use either of these:\n - 'cargo test -p dear_mirl_gui --features debug-window -- --nocapture'\n - 'cargo test -p dear_mirl_gui --release --features debug-window -- --nocapture'\n\nOr if you also want to see experimental features use these:\n - 'cargo test -p dear_mirl_gui --features experimental -- --nocapture'\n - 'cargo test -p dear_mirl_gui --release --features experimental -- --nocapture'"
@@ -0,0 +1,183 @@
+use mirl::{
+    directions::NormalDirections, math::ConstZero, platform::keycodes::KeyCode,
+};
+
+use crate::{
+    DearMirlGui, ModulePath,
+    module_manager::{register_module, remove_module},
+    modules::{Button, SameLine, TextDisplay, button::ButtonModulePathSupport},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How a modal has been closed
+pub enum ModalResult {
+    /// The OK button of a message box or confirm dialog
+    Ok,
+    /// The Cancel button of a confirm dialog or Escape
+    Cancel,
+    /// A button of a custom modal, contains the text of the button
+    Button(String),
+}
+
+#[derive(Debug, Clone, Default)]
+/// Poll the result of a modal opened using the [`DearMirlGuiManager`](crate::DearMirlGuiManager)
+pub struct ModalHandle {
+    /// Is None while the modal is open
+    pub result: std::sync::Arc<std::sync::RwLock<Option<ModalResult>>>,
+}
+impl ModalHandle {
+    /// Get how the modal has been closed, None while it is still open
+    #[must_use]
+    pub fn poll(&self) -> Option<ModalResult> {
+        self.result.read().ok()?.clone()
+    }
+    /// Check if the modal is still open
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.poll().is_none()
+    }
+    /// Close the modal with the given result, it is removed on the next update
+    pub fn close(&self, result: ModalResult) {
+        if let Ok(mut current) = self.result.write()
+            && current.is_none()
+        {
+            *current = Some(result);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A window that captures all input until it is closed, see [`DearMirlGuiManager::open_modal`](crate::DearMirlGuiManager::open_modal)
+pub struct Modal<const FAST: bool, const USE_CACHE: bool> {
+    /// The window shown in the center of the buffer
+    pub window: DearMirlGui<FAST, USE_CACHE>,
+    /// The buttons closing the modal and the result they close it with
+    pub buttons: Vec<(ModulePath<Button>, ModalResult)>,
+    /// Modules created for this modal, removed from the global context once it is closed
+    pub owned_modules: Vec<u32>,
+    /// The result pressing Escape closes the modal with, None if Escape should be ignored
+    pub escape_result: Option<ModalResult>,
+    #[allow(missing_docs)]
+    pub handle: ModalHandle,
+}
+impl<const FAST: bool, const USE_CACHE: bool> Modal<FAST, USE_CACHE> {
+    /// Show some text with an OK button
+    #[must_use]
+    pub fn message_box(title: &str, text: &str) -> Self {
+        Self::with_text(title, text, &[("OK", ModalResult::Ok)])
+    }
+    /// Ask the user to confirm something with OK and Cancel buttons
+    #[must_use]
+    pub fn confirm(title: &str, text: &str) -> Self {
+        Self::with_text(
+            title,
+            text,
+            &[("OK", ModalResult::Ok), ("Cancel", ModalResult::Cancel)],
+        )
+    }
+    /// Use any window as a modal, the given buttons are added below its modules and close the modal with [`ModalResult::Button`]
+    ///
+    /// Use the [`ModalHandle`] to close it from elsewhere
+    #[must_use]
+    pub fn custom(
+        window: DearMirlGui<FAST, USE_CACHE>,
+        buttons: &[&str],
+    ) -> Self {
+        let buttons: Vec<(&str, ModalResult)> = buttons
+            .iter()
+            .map(|text| (*text, ModalResult::Button((*text).to_string())))
+            .collect();
+        // Escape could mean anything, configure it using `with_escape_result`
+        Self::new(window, Vec::new(), &buttons).with_escape_result(None)
+    }
+    fn with_text(
+        title: &str,
+        text: &str,
+        buttons: &[(&str, ModalResult)],
+    ) -> Self {
+        let lines: Vec<u32> = text
+            .lines()
+            .map(|line| register_module(TextDisplay::new(line)).id())
+            .collect();
+        let window = DearMirlGui::new_simple(
+            title,
+            (
+                crate::DearMirlGuiCoordinateType::ZERO,
+                crate::DearMirlGuiCoordinateType::ZERO,
+            ),
+            &lines,
+        );
+        Self::new(window, lines, buttons)
+    }
+    fn new(
+        window: DearMirlGui<FAST, USE_CACHE>,
+        owned_modules: Vec<u32>,
+        buttons: &[(&str, ModalResult)],
+    ) -> Self {
+        let mut window = window;
+        let mut owned_modules = owned_modules;
+        let mut button_paths = Vec::new();
+        // The buttons are placed in a single row
+        let same_line = (buttons.len() > 1).then(|| {
+            let same_line = register_module(SameLine::default()).id();
+            owned_modules.push(same_line);
+            same_line
+        });
+        for (idx, (text, result)) in buttons.iter().enumerate() {
+            if idx > 0
+                && let Some(same_line) = same_line
+            {
+                window.add_module(same_line);
+            }
+            let path = register_module(Button::new((*text).to_string()));
+            window.add_module(path.id());
+            owned_modules.push(path.id());
+            button_paths.push((path, result.clone()));
+        }
+        // Modals stay centered and keep their content visible
+        window.allow_dragging = false;
+        window.resizing_allowed_in_directions = NormalDirections::all_false();
+        window.collapsed = false;
+        window.set_size_to_see_all_modules();
+        let escape_result = button_paths
+            .iter()
+            .find(|(_, result)| *result == ModalResult::Cancel)
+            .or_else(|| button_paths.last())
+            .map(|(_, result)| result.clone());
+        Self {
+            window,
+            buttons: button_paths,
+            owned_modules,
+            escape_result,
+            handle: ModalHandle::default(),
+        }
+    }
+    /// Set the result pressing Escape closes the modal with, None if Escape should be ignored
+    #[must_use]
+    pub fn with_escape_result(mut self, result: Option<ModalResult>) -> Self {
+        self.escape_result = result;
+        self
+    }
+    /// Check if a button has been clicked, Escape was pressed or the modal has been closed using the [`ModalHandle`]
+    ///
+    /// Returns if the modal has been closed
+    #[must_use]
+    pub fn update_result(&self, pressed_keys: &[KeyCode]) -> bool {
+        if let Some((_, result)) =
+            self.buttons.iter().find(|(path, _)| path.clicked())
+        {
+            self.handle.close(result.clone());
+        } else if pressed_keys.contains(&KeyCode::Escape)
+            && let Some(result) = &self.escape_result
+        {
+            self.handle.close(result.clone());
+        }
+        !self.handle.is_open()
+    }
+    /// Remove the modules created for this modal
+    pub fn remove_owned_modules(&self) {
+        for id in &self.owned_modules {
+            remove_module(&ModulePath::<()>::const_new(*id));
+        }
+    }
+}
@@ -11,7 +11,7 @@ pub fn get_available_id() -> usize {
 
 use crate::{
     DearMirlGuiModule, Formatting, GuiReturnModuleError, ModulePath, WhatAmI,
-    gui::ModuleContainer,
+    gui::ModuleContainer, modules::Menu,
 };
 
 /// A global context that manages the existence of modules
@@ -63,6 +63,28 @@ pub fn get_formatting() -> std::sync::Arc<Formatting> {
         .clone()
 }
 // type BufferAndOffset = std::sync::RwLock<(Buffer, (isize, isize))>;
+/// The right click menus of modules, see [`set_context_menu`]
+pub static CONTEXT_MENUS: std::sync::LazyLock<
+    std::sync::RwLock<std::collections::HashMap<u32, Menu>>,
+> = std::sync::LazyLock::new(|| {
+    std::sync::RwLock::new(std::collections::HashMap::new())
+});
+/// Set the menu that opens when right clicking the module, None removes it
+///
+/// The id of the clicked item is returned in [`GuiOutput::selected_menu_item`](crate::GuiOutput::selected_menu_item)
+pub fn set_context_menu<T>(path: &ModulePath<T>, menu: Option<Menu>) {
+    if let Ok(mut menus) = CONTEXT_MENUS.write() {
+        if let Some(menu) = menu {
+            menus.insert(path.id, menu);
+        } else {
+            menus.remove(&path.id);
+        }
+    }
+}
+/// Get the menu that opens when right clicking the module with the given id
+pub fn get_context_menu(id: u32) -> Option<Menu> {
+    CONTEXT_MENUS.read().ok()?.get(&id).cloned()
+}
 
 /// Add a module to the global context
 pub fn register_module<T: DearMirlGuiModule + 'static>(
@@ -112,6 +134,10 @@ pub fn remove_module<T>(path: &ModulePath<T>) -> bool {
         cache.remove(idx);
     }
 
+    if let Ok(mut menus) = CONTEXT_MENUS.write() {
+        menus.remove(&path.id);
+    }
+
     if let Ok(mut index_map) = MODULE_INDEX.write() {
         index_map.remove(&path.id);
         for (_, v) in index_map.iter_mut() {
@@ -142,26 +168,40 @@ pub fn reset_cache() {
 // }
 
 fn resolve_buffer_state(
-    current: &Vec<BufferState>,
+    current: &[BufferState],
     id: usize,
-) -> Option<std::sync::Arc<Buffer>> {
+) -> Option<(std::sync::Arc<Buffer>, u64)> {
     match current.get(id)? {
         BufferState::Empty => None,
         BufferState::FoundAt(new_id) => resolve_buffer_state(current, *new_id),
         BufferState::Filled {
             image,
+            formatting,
             ..
-        } => Some(image.clone()),
+        } => Some((image.clone(), *formatting)),
     }
 }
-/// Get the cached image for the current module
+/// Get the cached image for the current module, images drawn with another formatting are ignored
+///
+/// `formatting` is the [`Formatting::get_cache_key`] of the formatting the image would be drawn with
 pub fn get_image_cache(
     module_idx: usize,
     buffer_id: usize,
+    formatting: u64,
 ) -> Option<std::sync::Arc<Buffer>> {
     let cache = MODULES_IMAGE_CACHE.read().ok()?;
     let module_cache = cache.get(module_idx)?;
     resolve_buffer_state(module_cache, buffer_id)
+        .filter(|(_, key)| *key == formatting)
+        .map(|(image, _)| image)
+}
+/// Check if the slot is empty or holds an image drawn with the given formatting
+fn shares_formatting(
+    module: &[BufferState],
+    id: usize,
+    formatting: u64,
+) -> bool {
+    resolve_buffer_state(module, id).is_none_or(|(_, key)| key == formatting)
 }
 
 /// Ensure a module's cache is properly initialized with the given capacity
@@ -191,7 +231,10 @@ fn replace_referenced(module: &mut [BufferState], idx: usize) {
             BufferState::Filled {
                 image,
                 referenced_by,
-            } if !referenced_by.is_empty() => (image, referenced_by),
+                formatting,
+            } if !referenced_by.is_empty() => {
+                ((image, formatting), referenced_by)
+            }
             other => {
                 module[idx] = other;
                 return;
@@ -207,17 +250,22 @@ fn replace_referenced(module: &mut [BufferState], idx: usize) {
     }
 
     module[first] = BufferState::Filled {
-        image,
+        image: image.0,
         referenced_by: refs,
+        formatting: image.1,
     };
 }
 
 /// Replace the cached image with another
+///
+/// `formatting` is the [`Formatting::get_cache_key`] of the formatting the image has been drawn with, images are only shared between slots drawn with the same formatting
+#[allow(clippy::too_many_lines)]
 pub fn insert_into_image_cache(
     idx: usize,
     id: usize,
     buffer: Buffer,
     insertion_mode: InsertionMode,
+    formatting: u64,
 ) -> Option<()> {
     //println!("Idx {} from {}", idx, id);
     let mut cache = MODULES_IMAGE_CACHE.write().ok()?;
@@ -235,6 +283,7 @@ pub fn insert_into_image_cache(
             cache[idx][id] = BufferState::Filled {
                 image: std::sync::Arc::new(buffer),
                 referenced_by: Vec::new(),
+                formatting,
             };
         }
         InsertionMode::SearchInSameModule => {
@@ -242,8 +291,10 @@ pub fn insert_into_image_cache(
             for (existing_id, existing_state) in cache[idx].iter().enumerate() {
                 if let BufferState::Filled {
                     image,
+                    formatting: existing_formatting,
                     ..
                 } = existing_state
+                    && *existing_formatting == formatting
                     && std::sync::Arc::ptr_eq(image, &buffer_rc)
                 {
                     cache[idx][id] = BufferState::FoundAt(existing_id);
@@ -260,24 +311,42 @@ pub fn insert_into_image_cache(
             cache[idx][id] = BufferState::Filled {
                 image: buffer_rc,
                 referenced_by: Vec::new(),
+                formatting,
             };
         }
         InsertionMode::ReplaceAll => {
-            let cache_length = cache[idx].len();
-            let references = if cache_length < 2 {
-                Vec::new()
-            } else {
-                (1..cache_length).collect()
-            };
-            cache[idx][0] = BufferState::Filled {
+            // Slots drawn with another formatting keep their own image
+            let references: Vec<usize> = (0..cache[idx].len())
+                .filter(|other| {
+                    *other != id
+                        && shares_formatting(&cache[idx], *other, formatting)
+                })
+                .collect();
+            for existing_state in &mut cache[idx] {
+                if let BufferState::Filled {
+                    referenced_by,
+                    ..
+                } = existing_state
+                {
+                    referenced_by.retain(|other| {
+                        *other != id && !references.contains(other)
+                    });
+                }
+            }
+            for other in &references {
+                cache[idx][*other] = BufferState::FoundAt(id);
+            }
+            cache[idx][id] = BufferState::Filled {
                 image: std::sync::Arc::new(buffer),
                 referenced_by: references,
+                formatting,
             };
-            for existing_state in cache[idx].iter_mut().skip(1) {
-                *existing_state = BufferState::FoundAt(0);
-            }
         }
-        InsertionMode::CloneAcrossIds(targets) => {
+        InsertionMode::CloneAcrossIds(mut targets) => {
+            targets.retain(|target| {
+                *target == id
+                    || shares_formatting(&cache[idx], *target, formatting)
+            });
             if !targets.is_empty() {
                 let first_id =
                     unsafe { *targets.iter().min().unwrap_unchecked() };
@@ -294,6 +363,7 @@ pub fn insert_into_image_cache(
                         .copied()
                         .filter(|&id| id != first_id)
                         .collect(),
+                    formatting,
                 };
 
                 for &target_id in &targets {
@@ -322,6 +392,8 @@ pub enum BufferState {
         image: std::sync::Arc<Buffer>,
         /// The slots that reference this one
         referenced_by: Vec<usize>,
+        /// The [`Formatting::get_cache_key`] of the formatting the image has been drawn with
+        formatting: u64,
     },
     /// Buffer! But somewhere else
     FoundAt(usize),
@@ -70,6 +70,22 @@ impl<const FAST: bool, const USE_CACHE: bool> DearMirlGuiModule
     ) -> (Buffer, InsertionMode) {
         (self.render(), InsertionMode::ReplaceAll)
     }
+    fn draw_overlay(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> Vec<(Buffer, (isize, isize))> {
+        self.draw_overlays()
+    }
+    fn update_overlay(
+        &mut self,
+        info: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        self.update_overlays(*info)
+    }
+    fn set_available_area(&mut self, area: crate::DirtyRect) {
+        self.bounds = Some(area);
+    }
     fn added(&mut self, _container_id: usize) {
         self.allow_dragging = false;
         self.x.set_zero();
@@ -1,14 +1,19 @@
 use mirl::{
     extensions::*,
+    platform::{CursorStyle, keycodes::KeyCode},
     render::{self, Buffer},
 };
 
 use crate::{
-    AnyCasting, AnyCloning, Formatting, ModuleDrawInfo, ModuleUpdateInfo,
+    AnyCasting, AnyCloning, DearMirlGuiModule, Formatting, ModuleDrawInfo,
+    ModuleUpdateInfo,
 };
 
 /// Support builtin types
 pub mod primitives;
+/// Edit any serializable value without implementing [`Inspectable`]
+#[cfg(feature = "serde")]
+pub mod serde_support;
 
 /// Define what struct should be used instead of the "real" struct -> bool cannot store data
 pub trait InspectableType {
@@ -34,7 +39,7 @@ pub trait Inspectable:
     + crate::DearMirlGuiModule //+ mirl::misc::Comparable
 {
     /// Get all sub values
-    fn get_fields_mut(&mut self) -> Vec<(&'static str, DynSyncInspectable)>;
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)>;
     // #[allow(unused_variables)]
     // /// Draw the gui of the current thing
     // fn draw_ui(
@@ -60,8 +65,171 @@ pub trait Inspectable:
     fn as_enum(&mut self) -> Option<&mut dyn InspectEnum> {
         None
     }
+    /// COLLECTION ONLY allows adding/removing/reordering the fields
+    fn as_collection(&mut self) -> Option<&mut dyn InspectCollection> {
+        None
+    }
     /// Get the name of the current thing: MyStruct {} -> MyStruct
     fn get_name(&mut self) -> &'static str;
+    /// PRIMITIVE ONLY gets the currently edited value, used for reporting changes
+    fn get_value(&mut self) -> Option<InspectValue> {
+        None
+    }
+}
+
+/// A snapshot of an edited value
+#[derive(Debug, Clone, PartialEq)]
+pub enum InspectValue {
+    #[allow(missing_docs)]
+    Bool(bool),
+    /// Any signed integer
+    Int(i128),
+    /// Any unsigned integer
+    UInt(u128),
+    /// Any float
+    Float(f64),
+    #[allow(missing_docs)]
+    Char(char),
+    #[allow(missing_docs)]
+    String(String),
+    /// The name of the active variant of an enum
+    Variant(&'static str),
+    /// The amount of items in a collection
+    Length(usize),
+}
+impl std::fmt::Display for InspectValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::UInt(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Char(value) => write!(f, "{value:?}"),
+            Self::String(value) => write!(f, "{value:?}"),
+            Self::Variant(value) => write!(f, "{value}"),
+            Self::Length(value) => write!(f, "[{value} items]"),
+        }
+    }
+}
+macro_rules! impl_from_for_inspect_value {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for InspectValue {
+                fn from(value: $t) -> Self {
+                    Self::$variant(<$target>::from(value))
+                }
+            }
+        )*
+    };
+}
+impl_from_for_inspect_value!(Int, i128, i8, i16, i32, i64, i128);
+impl_from_for_inspect_value!(UInt, u128, u8, u16, u32, u64, u128);
+impl_from_for_inspect_value!(Float, f64, f32, f64);
+impl_from_for_inspect_value!(Bool, bool, bool);
+impl_from_for_inspect_value!(Char, char, char);
+impl_from_for_inspect_value!(String, String, String);
+impl From<isize> for InspectValue {
+    fn from(value: isize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+impl From<usize> for InspectValue {
+    fn from(value: usize) -> Self {
+        Self::UInt(value as u128)
+    }
+}
+
+/// A value that has been changed by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// The path of the changed field: `nested.priority`
+    pub path: String,
+    /// The value before the change, [None] if the field did not exist (e.g. a new item or the field of a different enum variant)
+    pub old: Option<InspectValue>,
+    /// The value after the change, [None] if the field no longer exists
+    pub new: Option<InspectValue>,
+}
+
+/// Collect the values of all primitives, enums and collections with their field paths
+///
+/// # Errors
+/// When the lock has been poisoned
+pub fn get_field_values(
+    r: &DynSyncInspectable,
+    path: &str,
+) -> Result<Vec<(String, InspectValue)>, Box<dyn std::error::Error>> {
+    let mut values = Vec::new();
+    collect_field_values(r, path, &mut values)?;
+    Ok(values)
+}
+fn collect_field_values(
+    r: &DynSyncInspectable,
+    path: &str,
+    values: &mut Vec<(String, InspectValue)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fields = {
+        let mut thing =
+            r.lock().map_err(|e| format!("Failed to lock mutex: {e}"))?;
+        if let Some(value) = get_own_value(&mut **thing) {
+            values.push((path.to_string(), value));
+        }
+        get_child_fields(&mut **thing)
+    };
+    for (name, field) in fields {
+        collect_field_values(
+            &field,
+            &InspectableViewState::get_child_path(path, &name),
+            values,
+        )?;
+    }
+    Ok(())
+}
+
+/// Get the value reported for the inspectable itself: the active variant of an enum, the amount of items of a collection or the value of a primitive
+fn get_own_value(thing: &mut dyn Inspectable) -> Option<InspectValue> {
+    if let Some(enumeration) = thing.as_enum() {
+        return enumeration
+            .variants()
+            .get(enumeration.current_index())
+            .copied()
+            .map(InspectValue::Variant);
+    }
+    if thing.as_collection().is_some() {
+        return Some(InspectValue::Length(thing.get_fields_mut().len()));
+    }
+    thing.get_value()
+}
+
+/// Compare two results of [`get_field_values`], fields are matched by their path
+#[must_use]
+pub fn get_field_changes(
+    old: &[(String, InspectValue)],
+    new: &[(String, InspectValue)],
+) -> Vec<FieldChange> {
+    let old_values: std::collections::HashMap<&str, &InspectValue> =
+        old.iter().map(|(path, value)| (path.as_str(), value)).collect();
+    let new_values: std::collections::HashMap<&str, &InspectValue> =
+        new.iter().map(|(path, value)| (path.as_str(), value)).collect();
+
+    let mut changes: Vec<FieldChange> = new
+        .iter()
+        .filter(|(path, value)| old_values.get(path.as_str()) != Some(&value))
+        .map(|(path, value)| FieldChange {
+            path: path.clone(),
+            old: old_values.get(path.as_str()).map(|x| (*x).clone()),
+            new: Some(value.clone()),
+        })
+        .collect();
+    changes.extend(
+        old.iter()
+            .filter(|(path, _)| !new_values.contains_key(path.as_str()))
+            .map(|(path, value)| FieldChange {
+                path: path.clone(),
+                old: Some(value.clone()),
+                new: None,
+            }),
+    );
+    changes
 }
 
 /// Get/Set enum values programmatically
@@ -75,48 +243,482 @@ pub trait InspectEnum {
     /// Get the fields/values the current variant may hold
     fn current_variant_fields(
         &mut self,
-    ) -> Vec<(&'static str, DynSyncInspectable)>;
+    ) -> Vec<(FieldName, DynSyncInspectable)>;
+}
+
+/// Add/Remove/Reorder the items of a collection programmatically
+///
+/// The items themselves are returned by [`Inspectable::get_fields_mut`]
+pub trait InspectCollection {
+    /// If items can be added and removed
+    fn can_resize(&self) -> bool;
+    /// If the order of the items can be changed
+    fn can_reorder(&self) -> bool;
+    /// Append a new default item
+    fn add(&mut self);
+    /// Remove the item at the given index
+    fn remove(&mut self, index: usize);
+    /// Swap the items at the given indices
+    fn swap(&mut self, a: usize, b: usize);
+    #[allow(unused_variables)]
+    /// MAP ONLY the input used for renaming the key of the item at the given index
+    fn get_key_input(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut crate::modules::TextInput> {
+        None
+    }
+    #[allow(unused_variables)]
+    /// MAP ONLY rename the item at the given index to the text of its key input, returns if it was renamed (the items may have been reordered)
+    ///
+    /// While [`get_key_error`](Self::get_key_error) reports a problem the input is reset to the current key instead
+    fn apply_key_input(&mut self, index: usize) -> bool {
+        false
+    }
+    #[allow(unused_variables)]
+    /// MAP ONLY why the text of the key input at the given index can't be used as a key (empty, already used by another item)
+    fn get_key_error(&self, index: usize) -> Option<&'static str> {
+        None
+    }
 }
 
 /// A reuseable helper for defining that an item is any Inspectable (thread safe)
 pub type DynSyncInspectable =
     std::sync::Arc<std::sync::Mutex<Box<dyn Inspectable>>>;
 
+/// The name of a field, owned for names only known at runtime (collection indices, map keys)
+pub type FieldName = std::borrow::Cow<'static, str>;
+
+/// Wrap a raw value into a [`DynSyncInspectable`]
+#[must_use]
+pub fn new_dyn_sync_inspectable<T: InspectableType>(
+    value: T,
+) -> Option<DynSyncInspectable>
+where
+    T::Inspectable: 'static,
+{
+    let inspectable: Box<dyn Inspectable> = Box::new(T::new_from_value(value)?);
+    Some(std::sync::Arc::new(std::sync::Mutex::new(inspectable)))
+}
+
+/// Sync a list of items with a newer list, if the lengths differ nothing is done and false is returned
+///
+/// # Errors
+/// When the lock has been poisoned or syncing an item failed
+pub fn sync_inspectables(
+    own: &[DynSyncInspectable],
+    newer: &[DynSyncInspectable],
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if own.len() != newer.len() {
+        return Ok(false);
+    }
+    for (own, newer) in own.iter().zip(newer) {
+        own.lock()
+            .map_err(|_| "Unable to lock sub".to_string())?
+            .sync(newer)?;
+    }
+    Ok(true)
+}
+
+/// What parts of an inspectable are shown, kept between frames
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InspectableViewState {
+    /// The paths of all collapsed fields: `nested`, `nested.inner`
+    pub collapsed: std::collections::HashSet<String>,
+    /// Only fields whose path contains this text (ignoring case) are shown, while filtering nothing is collapsed
+    pub filter: String,
+    /// Errors shown below the field at the given path, e.g. why an edit couldn't be applied
+    pub errors: std::collections::HashMap<String, String>,
+}
+impl InspectableViewState {
+    /// Get the path of a field inside the given parent path
+    #[must_use]
+    pub fn get_child_path(parent: &str, name: &str) -> String {
+        if parent.is_empty() {
+            name.to_string()
+        } else {
+            format!("{parent}.{name}")
+        }
+    }
+    /// If the fields at the given path are currently hidden
+    #[must_use]
+    pub fn is_collapsed(&self, path: &str) -> bool {
+        self.filter.is_empty() && self.collapsed.contains(path)
+    }
+    /// Collapse the field at the given path or expand it if already collapsed
+    pub fn toggle_collapsed(&mut self, path: &str) {
+        if !self.collapsed.remove(path) {
+            self.collapsed.insert(path.to_string());
+        }
+    }
+}
+
+/// Get the fields of a struct, enum variant or collection
+fn get_child_fields(
+    thing: &mut dyn Inspectable,
+) -> Vec<(FieldName, DynSyncInspectable)> {
+    if let Some(enumeration) = thing.as_enum() {
+        return enumeration.current_variant_fields();
+    }
+    thing.get_fields_mut()
+}
+
+/// Create the radio [`Selection`](crate::modules::Selection) used for switching between the variants of an enum
+#[must_use]
+pub fn get_variant_selection(
+    enumeration: &dyn InspectEnum,
+    formatting: &Formatting,
+) -> crate::modules::Selection {
+    let variants: Vec<String> =
+        enumeration.variants().iter().map(ToString::to_string).collect();
+    let current = enumeration.current_index();
+    let states = (0..variants.len()).map(|idx| idx == current).collect();
+    let mut selection =
+        crate::modules::Selection::new(&variants, true, Some(states))
+            .with_height(formatting.height);
+    selection.apply_new_formatting(formatting);
+    selection
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The buttons drawn next to each item of a collection
+enum CollectionControl {
+    MoveUp,
+    MoveDown,
+    Remove,
+    Add,
+}
+impl CollectionControl {
+    const fn get_symbol(self) -> &'static str {
+        match self {
+            Self::MoveUp => "^",
+            Self::MoveDown => "v",
+            Self::Remove => "x",
+            Self::Add => "+",
+        }
+    }
+}
+
+/// Everything needed for laying out an inspectable
+struct InspectableLayout {
+    fields: Vec<(FieldName, DynSyncInspectable)>,
+    variant_selection: Option<crate::modules::Selection>,
+    /// The controls next to each item when the inspectable is a collection
+    item_controls: Option<Vec<CollectionControl>>,
+    /// If the inspectable is a collection that can grow
+    can_add: bool,
+    /// The horizontal space the key inputs take up in front of each item when the inspectable is a map
+    key_width: isize,
+}
+impl InspectableLayout {
+    fn new(thing: &mut dyn Inspectable, formatting: &Formatting) -> Self {
+        if let Some(enumeration) = thing.as_enum() {
+            let selection = get_variant_selection(enumeration, formatting);
+            return Self {
+                fields: enumeration.current_variant_fields(),
+                variant_selection: Some(selection),
+                item_controls: None,
+                can_add: false,
+                key_width: 0,
+            };
+        }
+        let (item_controls, can_add, key_width) =
+            thing.as_collection().map_or((None, false, 0), |collection| {
+                let mut controls = Vec::new();
+                if collection.can_reorder() {
+                    controls.push(CollectionControl::MoveUp);
+                    controls.push(CollectionControl::MoveDown);
+                }
+                if collection.can_resize() {
+                    controls.push(CollectionControl::Remove);
+                }
+                let key_width = collection.get_key_input(0).map_or(0, |x| {
+                    x.get_width(formatting) as isize
+                        + formatting.horizontal_margin as isize
+                });
+                (Some(controls), collection.can_resize(), key_width)
+            });
+        Self {
+            fields: thing.get_fields_mut(),
+            variant_selection: None,
+            item_controls,
+            can_add,
+            key_width,
+        }
+    }
+    /// If this is a primitive that draws itself
+    fn is_leaf(&self) -> bool {
+        self.fields.is_empty()
+            && self.variant_selection.is_none()
+            && self.item_controls.is_none()
+    }
+    /// The horizontal space the controls and key inputs take up in front of each item
+    fn get_controls_width(&self, formatting: &Formatting) -> isize {
+        self.item_controls.as_ref().map_or(0, |controls| {
+            controls.len() as isize * get_control_stride(formatting)
+        }) + self.key_width
+    }
+}
+
+const fn get_control_stride(formatting: &Formatting) -> isize {
+    (formatting.height + formatting.horizontal_margin) as isize
+}
+
+fn draw_collection_control(
+    buffer: &mut Buffer,
+    control: CollectionControl,
+    position: (isize, isize),
+    formatting: &Formatting,
+) {
+    let size = formatting.height as isize;
+    render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+        buffer,
+        position,
+        (size, size),
+        formatting.foreground_color,
+    );
+    let symbol = control.get_symbol();
+    let symbol_width = render::get_text_width(
+        symbol,
+        formatting.height as f32,
+        &formatting.font,
+    ) as isize;
+    render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+        buffer,
+        symbol,
+        position.add(((size - symbol_width) / 2, 0)),
+        formatting.text_color,
+        formatting.height as f32,
+        &formatting.font,
+    );
+}
+
+/// Get the control below the (localized) mouse position if it has been clicked
+fn get_clicked_control(
+    controls: &[CollectionControl],
+    module_update_info: &ModuleUpdateInfo,
+    formatting: &Formatting,
+) -> (Option<CollectionControl>, crate::GuiOutput) {
+    let Some(mouse_pos) = module_update_info.mouse_pos else {
+        return (None, crate::GuiOutput::empty());
+    };
+    if module_update_info.focus_taken.is_focus_taken() {
+        return (None, crate::GuiOutput::empty());
+    }
+    let size = formatting.height as f32;
+    let stride = get_control_stride(formatting) as f32;
+    for (idx, control) in controls.iter().enumerate() {
+        let x = idx as f32 * stride;
+        if (x..x + size).contains(&mouse_pos.0)
+            && (0.0..size).contains(&mouse_pos.1)
+        {
+            let mut output =
+                crate::GuiOutput::default(crate::FocusTaken::VisuallyTaken);
+            output.new_cursor_style = Some(CursorStyle::Pointer);
+            if module_update_info.mouse_info.left.clicked {
+                output.focus_taken = crate::FocusTaken::FunctionallyTaken;
+                return (Some(*control), output);
+            }
+            return (None, output);
+        }
+    }
+    (None, crate::GuiOutput::empty())
+}
+
+/// Apply a clicked control to the collection behind the given inspectable
+///
+/// # Errors
+/// When the lock has been poisoned
+fn apply_collection_control(
+    r: &DynSyncInspectable,
+    control: CollectionControl,
+    index: usize,
+    item_count: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut thing =
+        r.lock().map_err(|e| format!("Failed to lock mutex: {e}",))?;
+    let Some(collection) = thing.as_collection() else {
+        return Ok(());
+    };
+    match control {
+        CollectionControl::MoveUp if index > 0 => {
+            collection.swap(index, index - 1);
+        }
+        CollectionControl::MoveDown if index + 1 < item_count => {
+            collection.swap(index, index + 1);
+        }
+        CollectionControl::Remove => collection.remove(index),
+        CollectionControl::Add => collection.add(),
+        _ => {}
+    }
+    drop(thing);
+    Ok(())
+}
+
+/// Draw the key input of the item at the given index, keys that can't be used get an outline in the error color
+///
+/// # Errors
+/// When the lock has been poisoned
+fn draw_key_input(
+    r: &DynSyncInspectable,
+    index: usize,
+    formatting: &Formatting,
+    module_draw_info: ModuleDrawInfo,
+) -> Result<Option<Buffer>, Box<dyn std::error::Error>> {
+    let mut thing =
+        r.lock().map_err(|e| format!("Failed to lock mutex: {e}",))?;
+    let Some(collection) = thing.as_collection() else {
+        return Ok(None);
+    };
+    let error = collection.get_key_error(index);
+    let Some(key_input) = collection.get_key_input(index) else {
+        return Ok(None);
+    };
+    let mut img = key_input.draw(formatting, &module_draw_info).0;
+    drop(thing);
+    let size = (img.width as isize, img.height as isize);
+    if error.is_some() {
+        crate::Theme {
+            border_color: formatting.theme.error_color,
+            border_thickness: formatting.theme.border_thickness.max(1),
+            ..formatting.theme
+        }
+        .draw_border(&mut img, (0, 0), size);
+    }
+    Ok(Some(img))
+}
+
+/// Update the key input of the item at the given index, the item is renamed when enter is pressed or the input is left
+///
+/// Returns if the item has been renamed
+///
+/// # Errors
+/// When the lock has been poisoned
+fn update_key_input(
+    r: &DynSyncInspectable,
+    index: usize,
+    module_update_info: &ModuleUpdateInfo,
+) -> Result<(crate::GuiOutput, bool), Box<dyn std::error::Error>> {
+    let mut thing =
+        r.lock().map_err(|e| format!("Failed to lock mutex: {e}",))?;
+    let Some(collection) = thing.as_collection() else {
+        return Ok((crate::GuiOutput::empty(), false));
+    };
+    let Some(key_input) = collection.get_key_input(index) else {
+        return Ok((crate::GuiOutput::empty(), false));
+    };
+    let was_selected = key_input.selected != 0;
+    let enter_pressed =
+        [KeyCode::Enter, KeyCode::KeyPadEnter].iter().any(|key| {
+            module_update_info.pressed_keys.contains(key)
+                && !key_input.last_keys_pressed.contains(key)
+        });
+    let output = key_input.update(module_update_info);
+    let committed = was_selected && (key_input.selected == 0 || enter_pressed);
+    let renamed = committed && collection.apply_key_input(index);
+    drop(thing);
+    Ok((output, renamed))
+}
+
+/// Get the header of an inspectable, everything that isn't a primitive gets an expand/collapse marker
+fn get_title(
+    type_name: &str,
+    name: Option<&str>,
+    collapsed: Option<bool>,
+) -> String {
+    let marker = match collapsed {
+        None => "",
+        Some(true) => "> ",
+        Some(false) => "v ",
+    };
+    name.map_or_else(
+        || format!("{marker}{type_name}:"),
+        |x| format!("{marker}{x} ({type_name}):"),
+    )
+}
+
+/// The precalculated size of an inspectable and its shown fields, see [`get_size_of_inspectable`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InspectableSize {
+    #[allow(missing_docs)]
+    pub width: isize,
+    #[allow(missing_docs)]
+    pub height: isize,
+    /// The sizes of the fields in order, [None] for fields hidden by the filter. Empty while collapsed
+    pub fields: Vec<Option<Self>>,
+}
+
 /// Draws an inspectable onto a buffer
 ///
+/// Enums get a radio selection of their variants followed by the fields of the active variant,
+/// collections get buttons for reordering, removing and adding items
+///
+/// `path` is the field path of the inspectable (empty for the root), used for looking up the collapsed state and the error shown below it
+///
+/// The sizes of all fields are calculated when no size is given, reuse the result of [`get_size_of_inspectable`] instead when possible
+///
 /// # Errors
 /// When the lock has been poisoned
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
 pub fn draw_inspectable(
     r: &DynSyncInspectable,
     depth: usize,
     formatting: &Formatting,
-    known_size: Option<(isize, isize)>,
+    known_size: Option<&InspectableSize>,
     name: Option<&str>,
+    path: &str,
+    view: &InspectableViewState,
     module_draw_info: &ModuleDrawInfo,
 ) -> Result<Buffer, Box<dyn std::error::Error>> {
-    let size =
-        known_size.unwrap_or(get_size_of_inspectable(r, formatting, name)?);
+    let Some(size) = known_size else {
+        let size = get_size_of_inspectable(r, formatting, name, path, view)?;
+        return draw_inspectable(
+            r,
+            depth,
+            formatting,
+            Some(&size),
+            name,
+            path,
+            view,
+            module_draw_info,
+        );
+    };
     let mut thing =
         r.lock().map_err(|e| format!("Failed to lock mutex: {e}",))?;
 
-    let fields = thing.get_fields_mut();
+    let mut layout = InspectableLayout::new(&mut **thing, formatting);
+    let collapsible = name.is_some() && !layout.is_leaf();
+    let collapsed = collapsible && view.is_collapsed(path);
 
     let mut buffer = Buffer::new_empty_with_color(
-        size.try_tuple_into().unwrap_or_default(),
+        (size.width, size.height).try_tuple_into().unwrap_or_default(),
         mirl::graphics::adjust_brightness_hsl_of_rgb(
             formatting.background_color,
             (5 * (1 + depth)) as f32,
         ),
     );
+    if let Some(error) = view.errors.get(path) {
+        // Below everything else
+        render::draw_text_antialiased_isize::<true>(
+            &mut buffer,
+            error,
+            (
+                formatting.horizontal_margin as isize,
+                size.height
+                    - (formatting.vertical_margin + formatting.height) as isize,
+            ),
+            formatting.theme.error_color,
+            formatting.height as f32,
+            &formatting.font,
+        );
+    }
     let mut offset = (
         formatting.horizontal_margin as isize,
         formatting.vertical_margin as isize,
     );
-    let type_name = thing.get_name();
-    let name = name.map_or_else(
-        || format!("{type_name}:"),
-        |x| format!("{x} ({type_name}):"),
-    );
+    let name =
+        get_title(thing.get_name(), name, collapsible.then_some(collapsed));
 
     // Draw name
     render::draw_text_antialiased_isize::<true>(
@@ -136,7 +738,10 @@ pub fn draw_inspectable(
 
     offset = offset.add((0, title_height));
 
-    if fields.is_empty() {
+    if collapsed {
+        return Ok(buffer);
+    }
+    if layout.is_leaf() {
         // It's a leaf node (primitive), draw it
         offset = offset.add((0, formatting.vertical_margin as isize));
 
@@ -146,33 +751,112 @@ pub fn draw_inspectable(
             &img,
             offset,
         );
-    } else {
-        // It's a struct, traverse its fields
-        drop(thing); // Release lock before recursing
+        return Ok(buffer);
+    }
+    if let Some(selection) = &mut layout.variant_selection {
+        // It's an enum, draw the variant picker above the variant fields
+        offset = offset.add((0, formatting.vertical_margin as isize));
 
-        for (name, field) in fields {
-            offset = offset.add((0, formatting.vertical_margin as isize));
+        let img = selection.draw(formatting, module_draw_info).0;
+        render::draw_buffer_on_buffer::<true, false, false, false>(
+            &mut buffer,
+            &img,
+            offset,
+        );
+        offset = offset.add((0, img.height as isize));
+    }
+
+    // It's a struct, enum variant or collection, traverse its fields
+    drop(thing); // Release lock before recursing
 
-            let img = draw_inspectable(
-                &field,
-                depth + 1,
+    let controls_width = layout.get_controls_width(formatting);
+    for (idx, (name, field)) in layout.fields.iter().enumerate() {
+        let Some(Some(field_size)) = size.fields.get(idx) else {
+            // Hidden by the filter
+            continue;
+        };
+        offset = offset.add((0, formatting.vertical_margin as isize));
+
+        draw_item_controls(
+            &mut buffer,
+            r,
+            idx,
+            &layout,
+            offset,
+            formatting,
+            *module_draw_info,
+        )?;
+        let img = draw_inspectable(
+            field,
+            depth + 1,
+            formatting,
+            Some(field_size),
+            Some(name),
+            &InspectableViewState::get_child_path(path, name),
+            view,
+            module_draw_info,
+        )?;
+        render::draw_buffer_on_buffer::<true, false, false, false>(
+            &mut buffer,
+            &img,
+            offset.add((controls_width, 0)),
+        );
+        offset = offset.add((0, img.height as isize));
+    }
+    if layout.can_add {
+        offset = offset.add((0, formatting.vertical_margin as isize));
+        draw_collection_control(
+            &mut buffer,
+            CollectionControl::Add,
+            offset,
+            formatting,
+        );
+    }
+
+    Ok(buffer)
+}
+/// Draw the controls and the key input in front of the item at the given index, does nothing when the inspectable isn't a collection
+///
+/// # Errors
+/// When the lock has been poisoned
+fn draw_item_controls(
+    buffer: &mut Buffer,
+    r: &DynSyncInspectable,
+    index: usize,
+    layout: &InspectableLayout,
+    offset: (isize, isize),
+    formatting: &Formatting,
+    module_draw_info: ModuleDrawInfo,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(controls) = &layout.item_controls {
+        for (control_idx, control) in controls.iter().enumerate() {
+            draw_collection_control(
+                buffer,
+                *control,
+                offset.add((
+                    control_idx as isize * get_control_stride(formatting),
+                    0,
+                )),
                 formatting,
-                None,
-                Some(name),
-                module_draw_info,
-            )?;
-            render::draw_buffer_on_buffer::<true, false, false, false>(
-                &mut buffer,
-                &img,
-                offset,
             );
-            offset = offset.add((0, img.height as isize));
         }
     }
-
-    Ok(buffer)
+    if layout.key_width > 0
+        && let Some(img) =
+            draw_key_input(r, index, formatting, module_draw_info)?
+    {
+        let controls_width = layout.get_controls_width(formatting);
+        render::draw_buffer_on_buffer::<true, false, false, false>(
+            buffer,
+            &img,
+            offset.add((controls_width - layout.key_width, 0)),
+        );
+    }
+    Ok(())
 }
-/// Precalculates the size of the inspectable before drawing it
+/// Precalculates the size of the inspectable and all of its shown fields before drawing it
+///
+/// Fields are shown when their path or the path of any field nested inside them contains the filter of the view (ignoring case)
 ///
 /// # Errors
 /// When the lock has been poisoned
@@ -180,16 +864,35 @@ pub fn get_size_of_inspectable(
     r: &DynSyncInspectable,
     formatting: &Formatting,
     name: Option<&str>,
-) -> Result<(isize, isize), Box<dyn std::error::Error>> {
+    path: &str,
+    view: &InspectableViewState,
+) -> Result<InspectableSize, Box<dyn std::error::Error>> {
+    let filter = view.filter.to_lowercase();
+    // The inspectable itself is always shown, only its fields are filtered
+    Ok(calculate_size(r, formatting, name, path, view, &filter, false)?.0)
+}
+/// Calculate the size and whether the inspectable matches the lowercase filter in a single pass
+///
+/// `parent_matches` is set when the path of a parent contains the filter, every nested path contains it as well then
+fn calculate_size(
+    r: &DynSyncInspectable,
+    formatting: &Formatting,
+    name: Option<&str>,
+    path: &str,
+    view: &InspectableViewState,
+    filter: &str,
+    parent_matches: bool,
+) -> Result<(InspectableSize, bool), Box<dyn std::error::Error>> {
+    let matches = parent_matches || path.to_lowercase().contains(filter);
     let mut thing =
         r.lock().map_err(|e| format!("Failed to lock mutex: {e}"))?;
 
-    let type_name = thing.get_name();
-    let name = name.map_or_else(
-        || format!("{type_name}:"),
-        |x| format!("{x} ({type_name}):"),
-    );
-    let fields = thing.get_fields_mut(); // Use immutable version if available
+    let mut layout = InspectableLayout::new(&mut **thing, formatting);
+    // Only the root has no name, it cannot be collapsed
+    let collapsible = name.is_some() && !layout.is_leaf();
+    let collapsed = collapsible && view.is_collapsed(path);
+    let name =
+        get_title(thing.get_name(), name, collapsible.then_some(collapsed));
 
     // Calculate title size
     let title_width = render::get_text_width(
@@ -205,8 +908,10 @@ pub fn get_size_of_inspectable(
 
     let mut max_width = title_width;
     let mut total_height = title_height;
+    let mut fields = Vec::new();
+    let mut any_field_matches = false;
 
-    if fields.is_empty() {
+    if layout.is_leaf() {
         // Leaf node - use custom size
         let (w, h) =
             (thing.get_width(formatting), thing.get_height(formatting))
@@ -214,71 +919,110 @@ pub fn get_size_of_inspectable(
                 .unwrap_or_default();
         max_width = max_width.max(w);
         total_height += formatting.vertical_margin as isize + h;
-    } else {
-        // Struct with fields - recurse
-        drop(thing); // Release lock before recursing
-
-        for (name, field) in fields {
-            let (w, h) =
-                get_size_of_inspectable(&field, formatting, Some(name))?;
-            max_width = max_width.max(w);
-            total_height += formatting.vertical_margin as isize + h;
+    }
+    if !collapsed && let Some(selection) = &mut layout.variant_selection {
+        // Enum - the variant picker comes before the variant fields
+        max_width = max_width.max(selection.get_width(formatting) as isize);
+        total_height += formatting.vertical_margin as isize
+            + selection.get_height(formatting) as isize;
+    }
+
+    // Struct with fields, enum variant or collection - recurse
+    drop(thing); // Release lock before recursing
+
+    let controls_width = layout.get_controls_width(formatting);
+    for (name, field) in layout.fields.iter().filter(|_| !collapsed) {
+        let (size, field_matches) = calculate_size(
+            field,
+            formatting,
+            Some(name),
+            &InspectableViewState::get_child_path(path, name),
+            view,
+            filter,
+            matches,
+        )?;
+        if field_matches {
+            any_field_matches = true;
+            max_width = max_width.max(controls_width + size.width);
+            total_height += formatting.vertical_margin as isize + size.height;
         }
+        fields.push(field_matches.then_some(size));
+    }
+    if layout.can_add && !collapsed {
+        max_width = max_width.max(formatting.height as isize);
+        total_height +=
+            (formatting.vertical_margin + formatting.height) as isize;
+    }
+    if let Some(error) = view.errors.get(path) {
+        max_width = max_width.max(render::get_text_width(
+            error,
+            formatting.height as f32,
+            &formatting.font,
+        ) as isize);
+        total_height +=
+            (formatting.vertical_margin + formatting.height) as isize;
     }
 
     // Add outer margins
     Ok((
-        max_width + (formatting.horizontal_margin * 2) as isize,
-        total_height + (formatting.vertical_margin * 2) as isize,
+        InspectableSize {
+            width: max_width + (formatting.horizontal_margin * 2) as isize,
+            height: total_height + (formatting.vertical_margin * 2) as isize,
+            fields,
+        },
+        matches || any_field_matches,
     ))
 }
 
 /// Updates all inspectable with new user data
 ///
+/// Switching the variant of an enum or changing the items of a collection replaces its fields, the new fields are used starting with the next update
+///
+/// Clicking the header of a nested field collapses/expands it in the given view
+///
+/// The sizes of all fields are calculated when no size is given, reuse the result of [`get_size_of_inspectable`] instead when possible
+///
 /// # Errors
 /// When the lock has been poisoned
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::only_used_in_recursion)] // Same parameters as draw_inspectable
 pub fn update_inspectable(
     r: &DynSyncInspectable,
     depth: usize,
     formatting: &Formatting,
-    known_size: Option<(isize, isize)>,
+    known_size: Option<&InspectableSize>,
     name: Option<&str>,
+    path: &str,
+    view: &mut InspectableViewState,
     module_update_info: &ModuleUpdateInfo,
 ) -> Result<crate::GuiOutput, Box<dyn std::error::Error>> {
-    let mut module_update_info = *module_update_info;
-    let size =
-        known_size.unwrap_or(get_size_of_inspectable(r, formatting, name)?);
+    let Some(size) = known_size else {
+        let size = get_size_of_inspectable(r, formatting, name, path, view)?;
+        return update_inspectable(
+            r,
+            depth,
+            formatting,
+            Some(&size),
+            name,
+            path,
+            view,
+            module_update_info,
+        );
+    };
     let mut thing =
         r.lock().map_err(|e| format!("Failed to lock mutex: {e}",))?;
 
-    let fields = thing.get_fields_mut();
+    let mut layout = InspectableLayout::new(&mut **thing, formatting);
+    let collapsible = name.is_some() && !layout.is_leaf();
+    let collapsed = collapsible && view.is_collapsed(path);
 
-    let mut buffer = Buffer::new_empty_with_color(
-        size.try_tuple_into().unwrap_or_default(),
-        mirl::graphics::adjust_brightness_hsl_of_rgb(
-            formatting.background_color,
-            (5 * (1 + depth)) as f32,
-        ),
-    );
     let mut offset = (
         formatting.horizontal_margin as isize,
         formatting.vertical_margin as isize,
     );
-    let type_name = thing.get_name();
-    let name = name.map_or_else(
-        || format!("{type_name}:"),
-        |x| format!("{x} ({type_name}):"),
-    );
-
-    // Draw name
-    render::draw_text_antialiased_isize::<true>(
-        &mut buffer,
-        &name,
-        offset,
-        formatting.text_color,
-        formatting.height as f32,
-        &formatting.font,
-    );
+    let name =
+        get_title(thing.get_name(), name, collapsible.then_some(collapsed));
 
     let title_height = render::get_text_height(
         &name,
@@ -286,51 +1030,163 @@ pub fn update_inspectable(
         &formatting.font,
     ) as isize;
 
+    let mut output = crate::GuiOutput::empty();
+
+    if collapsible
+        && !module_update_info.focus_taken.is_focus_taken()
+        && let Some(mouse_pos) = module_update_info.mouse_pos
+        && (0.0..size.width as f32).contains(&mouse_pos.0)
+        && (0.0..(offset.1 + title_height) as f32).contains(&mouse_pos.1)
+    {
+        // Hovering the header
+        output.new_cursor_style = Some(CursorStyle::Pointer);
+        output.focus_taken = crate::FocusTaken::VisuallyTaken;
+        if module_update_info.mouse_info.left.clicked {
+            output.focus_taken = crate::FocusTaken::FunctionallyTaken;
+            view.toggle_collapsed(path);
+            return Ok(output);
+        }
+    }
+
     offset = offset.add((0, title_height));
 
-    let mut output = crate::GuiOutput::empty();
+    if collapsed {
+        return Ok(output);
+    }
+    if layout.is_leaf() {
+        // It's a leaf node (primitive), update it
+        offset = offset.add((0, formatting.vertical_margin as isize));
 
-    if fields.is_empty() {
-        // It's a leaf node (primitive), draw it
+        output |=
+            thing.update(&localize_update_info(module_update_info, offset));
+        return Ok(output);
+    }
+    if let Some(selection) = &mut layout.variant_selection {
+        // It's an enum, let the user pick a variant
         offset = offset.add((0, formatting.vertical_margin as isize));
-        if let Some(pos) = module_update_info.mouse_pos {
-            module_update_info.mouse_pos =
-                Some(pos.sub(offset.try_tuple_into().unwrap_or_default()));
+
+        let before = selection.radio_button_get_idx();
+        output |=
+            selection.update(&localize_update_info(module_update_info, offset));
+        let after = selection.radio_button_get_idx();
+
+        if let Some(idx) = after
+            && after != before
+        {
+            if let Some(enumeration) = thing.as_enum() {
+                enumeration.set_variant(idx);
+            }
+            // The fields of the old variant are gone, start fresh next update
+            return Ok(output);
         }
+        offset = offset.add((0, selection.get_height(formatting) as isize));
+    }
 
-        output |= thing.update(&module_update_info);
-    } else {
-        // It's a struct, traverse its fields
-        drop(thing); // Release lock before recursing
+    // It's a struct, enum variant or collection, traverse its fields
+    drop(thing); // Release lock before recursing
 
-        for (name, field) in fields {
-            offset = offset.add((0, formatting.vertical_margin as isize));
+    let controls_width = layout.get_controls_width(formatting);
+    for (idx, (name, field)) in layout.fields.iter().enumerate() {
+        let Some(Some(field_size)) = size.fields.get(idx) else {
+            // Hidden by the filter
+            continue;
+        };
+        offset = offset.add((0, formatting.vertical_margin as isize));
 
-            output |= update_inspectable(
-                &field,
-                depth + 1,
-                formatting,
-                None,
-                Some(name),
-                &module_update_info,
-            )?;
-            let height;
-            match field.lock() {
-                Ok(mut val) => {
-                    height = val.get_height(formatting);
-                    drop(val);
-                }
-                Err(err) => {
-                    return Err(format!("Failed to lock mutex: {err}",).into());
-                }
-            }
+        if update_item_controls(
+            r,
+            idx,
+            &layout,
+            &localize_update_info(module_update_info, offset),
+            formatting,
+            &mut output,
+        )? {
+            // The items have changed, start fresh next update
+            return Ok(output);
+        }
+        output |= update_inspectable(
+            field,
+            depth + 1,
+            formatting,
+            Some(field_size),
+            Some(name),
+            &InspectableViewState::get_child_path(path, name),
+            view,
+            &localize_update_info(
+                module_update_info,
+                offset.add((controls_width, 0)),
+            ),
+        )?;
 
-            offset = offset.add((0, height as isize));
+        offset = offset.add((0, field_size.height));
+    }
+    if layout.can_add {
+        offset = offset.add((0, formatting.vertical_margin as isize));
+        let (clicked, control_output) = get_clicked_control(
+            &[CollectionControl::Add],
+            &localize_update_info(module_update_info, offset),
+            formatting,
+        );
+        output |= control_output;
+        if let Some(control) = clicked {
+            let item_count = layout.fields.len();
+            apply_collection_control(r, control, item_count, item_count)?;
         }
     }
     //println!("{output:?}");
     Ok(output)
 }
+/// Update the controls and the key input in front of the item at the given index, the info has to be localized to the start of the item
+///
+/// Returns if the items of the collection have changed
+///
+/// # Errors
+/// When the lock has been poisoned
+fn update_item_controls(
+    r: &DynSyncInspectable,
+    index: usize,
+    layout: &InspectableLayout,
+    module_update_info: &ModuleUpdateInfo,
+    formatting: &Formatting,
+    output: &mut crate::GuiOutput,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if let Some(controls) = &layout.item_controls {
+        let (clicked, control_output) =
+            get_clicked_control(controls, module_update_info, formatting);
+        *output |= control_output;
+        if let Some(control) = clicked {
+            apply_collection_control(r, control, index, layout.fields.len())?;
+            return Ok(true);
+        }
+    }
+    if layout.key_width > 0 {
+        let controls_width = layout.get_controls_width(formatting);
+        let (key_output, renamed) = update_key_input(
+            r,
+            index,
+            &localize_update_info(
+                module_update_info,
+                (controls_width - layout.key_width, 0),
+            ),
+        )?;
+        *output |= key_output;
+        return Ok(renamed);
+    }
+    Ok(false)
+}
+
+/// Move the mouse position into the space of a child drawn at the given offset
+fn localize_update_info<'a>(
+    module_update_info: &ModuleUpdateInfo<'a>,
+    offset: (isize, isize),
+) -> ModuleUpdateInfo<'a> {
+    let mut module_update_info = *module_update_info;
+    if let Some(pos) = module_update_info.mouse_pos {
+        module_update_info.mouse_pos =
+            Some(pos.sub(offset.try_tuple_into().unwrap_or_default()));
+    }
+    module_update_info
+}
 
 // /// Updates the internal value with a newer version
 // ///
@@ -370,3 +1226,85 @@ pub fn update_inspectable(
 // pub trait SyncWithSelf {
 //     fn sync_with_self(&mut self, other: &Self);
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(values: &[(&str, InspectValue)]) -> Vec<(String, InspectValue)> {
+        values
+            .iter()
+            .map(|(path, value)| ((*path).to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn field_changes_use_nested_paths() {
+        let old = values(&[
+            ("name", InspectValue::String("a".into())),
+            ("nested.priority", InspectValue::Int(1)),
+            ("nested.inner.enabled", InspectValue::Bool(false)),
+            ("items", InspectValue::Length(2)),
+            ("items.0", InspectValue::UInt(3)),
+            ("items.1", InspectValue::UInt(4)),
+        ]);
+        let new = values(&[
+            ("name", InspectValue::String("a".into())),
+            ("nested.priority", InspectValue::Int(2)),
+            ("nested.inner.enabled", InspectValue::Bool(true)),
+            ("items", InspectValue::Length(1)),
+            ("items.0", InspectValue::UInt(3)),
+        ]);
+        assert_eq!(
+            get_field_changes(&old, &new),
+            [
+                FieldChange {
+                    path: "nested.priority".into(),
+                    old: Some(InspectValue::Int(1)),
+                    new: Some(InspectValue::Int(2)),
+                },
+                FieldChange {
+                    path: "nested.inner.enabled".into(),
+                    old: Some(InspectValue::Bool(false)),
+                    new: Some(InspectValue::Bool(true)),
+                },
+                FieldChange {
+                    path: "items".into(),
+                    old: Some(InspectValue::Length(2)),
+                    new: Some(InspectValue::Length(1)),
+                },
+                FieldChange {
+                    path: "items.1".into(),
+                    old: Some(InspectValue::UInt(4)),
+                    new: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn field_changes_report_new_fields() {
+        let old = values(&[("shape", InspectValue::Variant("Circle"))]);
+        let new = values(&[
+            ("shape", InspectValue::Variant("Rect")),
+            ("shape.width", InspectValue::Float(2.0)),
+        ]);
+        let changes = get_field_changes(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].path, "shape.width");
+        assert_eq!(changes[1].old, None);
+        assert!(get_field_changes(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn child_paths_are_joined_with_dots() {
+        assert_eq!(
+            InspectableViewState::get_child_path("", "nested"),
+            "nested"
+        );
+        assert_eq!(
+            InspectableViewState::get_child_path("nested", "inner"),
+            "nested.inner"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, Inspectable, InspectableType,
+        new_dyn_sync_inspectable, sync_inspectables,
+    },
+};
+
+impl<T: InspectableType + 'static, const N: usize> InspectableType for [T; N]
+where
+    T::Inspectable: 'static,
+{
+    type Inspectable = InspectArray;
+    fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+        Some(InspectArray {
+            items: value
+                .into_iter()
+                .map(new_dyn_sync_inspectable)
+                .collect::<Option<Vec<_>>>()?,
+        })
+    }
+}
+/// The length is fixed so items cannot be added, removed or reordered
+#[derive(Debug, Clone)]
+pub struct InspectArray {
+    items: Vec<DynSyncInspectable>,
+}
+impl Inspectable for InspectArray {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (idx.to_string().into(), item.clone()))
+            .collect()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "array"
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>()
+            && !sync_inspectables(&self.items, &inspect.items)?
+        {
+            return Err("Array lengths do not match".into());
+        }
+
+        Ok(())
+    }
+}
+impl DearMirlGuiModule for InspectArray {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     DearMirlGuiModule,
     modules::support::struct_editing::{
-        DynSyncInspectable, Inspectable, InspectableType,
+        DynSyncInspectable, FieldName, InspectValue, Inspectable,
+        InspectableType,
     },
     prelude::*,
 };
@@ -26,12 +27,15 @@ pub struct InspectBool {
     state: crate::modules::CheckBox,
 }
 impl Inspectable for InspectBool {
-    fn get_fields_mut(&mut self) -> Vec<(&'static str, DynSyncInspectable)> {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
         vec![]
     }
     fn get_name(&mut self) -> &'static str {
         "bool"
     }
+    fn get_value(&mut self) -> Option<InspectValue> {
+        Some(InspectValue::Bool(self.state.checked != 0))
+    }
 
     fn sync(
         &mut self,
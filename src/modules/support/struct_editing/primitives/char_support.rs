@@ -0,0 +1,116 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, InspectValue, Inspectable,
+        InspectableType,
+    },
+    prelude::*,
+};
+
+impl InspectableType for char {
+    type Inspectable = InspectChar;
+    fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+        Some(InspectChar {
+            state: crate::modules::TextInput::new(
+                get_formatting().height * 2,
+                1,
+                Some(vec![value.to_string()]),
+            ),
+        })
+    }
+}
+/// Limited to a single character, typing more is refused until the current one is removed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InspectChar {
+    state: crate::modules::TextInput,
+}
+impl Inspectable for InspectChar {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        vec![]
+    }
+    fn get_name(&mut self) -> &'static str {
+        "char"
+    }
+    fn get_value(&mut self) -> Option<InspectValue> {
+        self.state.text.concat().chars().next().map(InspectValue::Char)
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>() {
+            *self = inspect.clone();
+            self.set_need_redraw(Vec::from([(0, true)]));
+        }
+
+        Ok(())
+    }
+}
+impl DearMirlGuiModule for InspectChar {
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        self.state.draw(formatting, info)
+    }
+
+    fn get_height(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.state.get_height(formatting)
+    }
+
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.state.get_width(formatting)
+    }
+
+    fn update(&mut self, inputs: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        let text = self.state.text.clone();
+        let caret = self.state.caret.clone();
+        let output = self.state.update(inputs);
+        // A char holds exactly one character, refuse input beyond that
+        if self.state.text.concat().chars().count() > 1 {
+            self.state.text = text;
+            self.state.caret = caret;
+            self.set_need_redraw(Vec::from([(0, true)]));
+        }
+        output
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        self.state.need_redraw()
+    }
+    fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
+        self.state.set_need_redraw(need_redraw);
+    }
+    fn added(&mut self, container_id: usize) {
+        self.state.added(container_id);
+    }
+    fn removed(&mut self, container_id: usize) {
+        self.state.removed(container_id);
+    }
+    fn apply_new_formatting(&mut self, formatting: &crate::Formatting) {
+        self.state.apply_new_formatting(formatting);
+    }
+    fn modify_offset_cursor(
+        &mut self,
+        modules: &[crate::gui::ModuleContainer],
+        used_idx: &Vec<usize>,
+        formatting: &crate::Formatting,
+        current: (
+            &mut crate::DearMirlGuiCoordinateType,
+            &mut crate::DearMirlGuiCoordinateType,
+        ),
+    ) {
+        self.state.modify_offset_cursor(modules, used_idx, formatting, current);
+    }
+}
@@ -1,27 +1,24 @@
-use mirl::{extensions::RangeExtension, misc::EasyUnwrapUnchecked};
-
 use crate::{
     DearMirlGuiModule,
     modules::support::struct_editing::{
-        DynSyncInspectable, Inspectable, InspectableType,
+        DynSyncInspectable, FieldName, InspectValue, Inspectable,
+        InspectableType,
     },
 };
 
 impl InspectableType for f64 {
     type Inspectable = InspectF64;
     fn new_from_value(value: Self) -> Option<Self::Inspectable> {
-        let range = Self::MIN..Self::MAX;
-        Some(InspectF64 {
-            state: crate::modules::Slider::new(
-                range.get_percent_from_value(value),
-                false,
-                Some(range),
-            )
-            .easy_unwrap_unchecked(),
-        })
+        let mut state = crate::modules::Slider::new(
+            None,
+            false,
+            Some(Self::MIN..Self::MAX),
+        )?;
+        state.set_value(value)?;
+        Some(InspectF64 { state })
     }
     // fn sync_from_value(&self, value: &mut Self::Inspectable) {
-    //     value.state.progress = value.state.range.get_percent_from_value(*self);
+    //     value.state.set_value(*self);
     // }
 }
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -29,12 +26,15 @@ pub struct InspectF64 {
     state: crate::modules::Slider<f32, f64>,
 }
 impl Inspectable for InspectF64 {
-    fn get_fields_mut(&mut self) -> Vec<(&'static str, DynSyncInspectable)> {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
         vec![]
     }
     fn get_name(&mut self) -> &'static str {
         "f64"
     }
+    fn get_value(&mut self) -> Option<InspectValue> {
+        self.state.get_value().map(InspectValue::from)
+    }
     fn sync(
         &mut self,
         new: &DynSyncInspectable,
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use crate::{
+    DearMirlGuiModule,
+    module_manager::get_formatting,
+    modules::{
+        TextInput,
+        support::struct_editing::{
+            DynSyncInspectable, FieldName, InspectCollection, Inspectable,
+            InspectableType, new_dyn_sync_inspectable, sync_inspectables,
+        },
+    },
+};
+
+impl<T: InspectableType + Default + 'static, S: std::hash::BuildHasher>
+    InspectableType for HashMap<String, T, S>
+where
+    T::Inspectable: 'static,
+{
+    type Inspectable = InspectHashMap<T>;
+    fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+        let mut entries = value
+            .into_iter()
+            .map(|(key, value)| Some((key, new_dyn_sync_inspectable(value)?)))
+            .collect::<Option<Vec<_>>>()?;
+        // Keep the order stable between frames
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let key_inputs =
+            entries.iter().map(|(key, _)| new_key_input(key)).collect();
+        Some(InspectHashMap {
+            entries,
+            key_inputs,
+            item_type: std::marker::PhantomData,
+        })
+    }
+}
+fn new_key_input(key: &str) -> TextInput {
+    TextInput::new(get_formatting().height * 4, 1, Some(vec![key.to_string()]))
+        .show_line_numbers(false)
+}
+/// The entries are sorted by key, new entries are called `new`, `new_1`, `new_2`...
+///
+/// Keys are renamed with the input in front of each entry, the new key is applied when pressing enter or leaving the input
+pub struct InspectHashMap<T> {
+    entries: Vec<(String, DynSyncInspectable)>,
+    /// One input per entry for renaming its key
+    key_inputs: Vec<TextInput>,
+    /// New entries are created from `T::default()`
+    item_type: std::marker::PhantomData<fn() -> T>,
+}
+impl<T> InspectHashMap<T> {
+    fn sort_entries(&mut self) {
+        let mut entries: Vec<_> =
+            self.entries.drain(..).zip(self.key_inputs.drain(..)).collect();
+        entries.sort_by(|((a, _), _), ((b, _), _)| a.cmp(b));
+        (self.entries, self.key_inputs) = entries.into_iter().unzip();
+    }
+    fn get_unused_key(&self) -> String {
+        let mut key = "new".to_string();
+        let mut counter = 0;
+        while self.entries.iter().any(|(existing, _)| *existing == key) {
+            counter += 1;
+            key = format!("new_{counter}");
+        }
+        key
+    }
+}
+impl<T> std::fmt::Debug for InspectHashMap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InspectHashMap")
+            .field("entries", &self.entries)
+            .finish_non_exhaustive()
+    }
+}
+impl<T> Clone for InspectHashMap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            key_inputs: self.key_inputs.clone(),
+            item_type: std::marker::PhantomData,
+        }
+    }
+}
+impl<T: InspectableType + Default + 'static> InspectCollection
+    for InspectHashMap<T>
+where
+    T::Inspectable: 'static,
+{
+    fn can_resize(&self) -> bool {
+        true
+    }
+    fn can_reorder(&self) -> bool {
+        false
+    }
+    fn add(&mut self) {
+        if let Some(item) = new_dyn_sync_inspectable(T::default()) {
+            let key = self.get_unused_key();
+            self.key_inputs.push(new_key_input(&key));
+            self.entries.push((key, item));
+            self.sort_entries();
+        }
+    }
+    fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+            self.key_inputs.remove(index);
+        }
+    }
+    fn swap(&mut self, _a: usize, _b: usize) {}
+    fn get_key_input(&mut self, index: usize) -> Option<&mut TextInput> {
+        self.key_inputs.get_mut(index)
+    }
+    fn apply_key_input(&mut self, index: usize) -> bool {
+        let (Some(key_input), Some((key, _))) =
+            (self.key_inputs.get(index), self.entries.get(index))
+        else {
+            return false;
+        };
+        let new_key = key_input.text.concat();
+        if new_key == *key {
+            return false;
+        }
+        if self.get_key_error(index).is_some() {
+            // Go back to the key that is still in use
+            self.key_inputs[index] = new_key_input(key);
+            return false;
+        }
+        self.entries[index].0 = new_key;
+        self.sort_entries();
+        true
+    }
+    fn get_key_error(&self, index: usize) -> Option<&'static str> {
+        let key = self.key_inputs.get(index)?.text.concat();
+        if key.is_empty() {
+            return Some("The key is empty");
+        }
+        self.entries
+            .iter()
+            .enumerate()
+            .any(|(idx, (existing, _))| idx != index && *existing == key)
+            .then_some("The key is already used")
+    }
+}
+impl<T: InspectableType + Default + 'static> Inspectable for InspectHashMap<T>
+where
+    T::Inspectable: 'static,
+{
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.entries
+            .iter()
+            .map(|(key, item)| (key.clone().into(), item.clone()))
+            .collect()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "HashMap"
+    }
+    fn as_collection(&mut self) -> Option<&mut dyn InspectCollection> {
+        Some(self)
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>() {
+            let same_keys = self
+                .entries
+                .iter()
+                .map(|(key, _)| key)
+                .eq(inspect.entries.iter().map(|(key, _)| key));
+            let own: Vec<_> =
+                self.entries.iter().map(|(_, item)| item.clone()).collect();
+            let other: Vec<_> =
+                inspect.entries.iter().map(|(_, item)| item.clone()).collect();
+            if !same_keys || !sync_inspectables(&own, &other)? {
+                // Entries were added, removed or renamed, take them over as they are
+                self.entries.clone_from(&inspect.entries);
+                self.key_inputs.clone_from(&inspect.key_inputs);
+            }
+        }
+
+        Ok(())
+    }
+}
+impl<T: 'static> DearMirlGuiModule for InspectHashMap<T> {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
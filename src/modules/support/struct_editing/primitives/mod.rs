@@ -1,7 +1,14 @@
 #![allow(missing_docs)]
+pub mod array_support;
 pub mod bool_support;
+pub mod char_support;
 pub mod f32_support;
 pub mod f64_support;
+pub mod hashmap_support;
 pub mod i32_support;
+pub mod number_support;
+pub mod option_support;
 pub mod string_support;
+pub mod tuple_support;
 pub mod u8_support;
+pub mod vec_support;
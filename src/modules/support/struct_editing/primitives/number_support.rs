@@ -0,0 +1,164 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, InspectValue, Inspectable,
+        InspectableType,
+    },
+    prelude::*,
+};
+
+macro_rules! impl_inspectable_integer {
+    ($($t:ty),*) => {
+        $(
+            impl InspectableType for $t {
+                type Inspectable = InspectNumber<$t>;
+                fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+                    Some(InspectNumber::new(value))
+                }
+            }
+        )*
+    };
+}
+// i32 and u8 keep their sliders
+impl_inspectable_integer!(
+    i8, i16, i64, i128, isize, u16, u32, u64, u128, usize
+);
+
+/// Edits any number as text, used for the integers without a slider and the numbers of serialized values as they have to keep their exact value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InspectNumber<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::math::ConstZero
+        + PartialEq
+        + Clone,
+> {
+    state: crate::modules::NumberInput<T>,
+}
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::math::ConstZero
+        + PartialEq
+        + Clone,
+> InspectNumber<T>
+{
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            state: crate::modules::NumberInput::new(value)
+                .with_width(get_formatting().height * 3),
+        }
+    }
+}
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::math::ConstZero
+        + PartialEq
+        + Clone
+        + Into<InspectValue>,
+> Inspectable for InspectNumber<T>
+{
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        vec![]
+    }
+    fn get_name(&mut self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+    fn get_value(&mut self) -> Option<InspectValue> {
+        Some(self.state.number.clone().into())
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>() {
+            self.state.set_number(inspect.state.number.clone());
+        }
+
+        Ok(())
+    }
+}
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::math::ConstZero
+        + PartialEq
+        + Clone,
+> DearMirlGuiModule for InspectNumber<T>
+{
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        self.state.draw(formatting, info)
+    }
+
+    fn get_height(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.state.get_height(formatting)
+    }
+
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.state.get_width(formatting)
+    }
+
+    fn update(&mut self, inputs: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        self.state.update(inputs)
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        self.state.need_redraw()
+    }
+    fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
+        self.state.set_need_redraw(need_redraw);
+    }
+    fn added(&mut self, container_id: usize) {
+        self.state.added(container_id);
+    }
+    fn removed(&mut self, container_id: usize) {
+        self.state.removed(container_id);
+    }
+    fn apply_new_formatting(&mut self, formatting: &crate::Formatting) {
+        self.state.apply_new_formatting(formatting);
+    }
+    fn modify_offset_cursor(
+        &mut self,
+        modules: &[crate::gui::ModuleContainer],
+        used_idx: &Vec<usize>,
+        formatting: &crate::Formatting,
+        current: (
+            &mut crate::DearMirlGuiCoordinateType,
+            &mut crate::DearMirlGuiCoordinateType,
+        ),
+    ) {
+        self.state.modify_offset_cursor(modules, used_idx, formatting, current);
+    }
+}
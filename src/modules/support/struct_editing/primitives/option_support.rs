@@ -0,0 +1,136 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, InspectEnum, Inspectable,
+        InspectableType, new_dyn_sync_inspectable,
+    },
+};
+
+impl<T: InspectableType + Default + 'static> InspectableType for Option<T>
+where
+    T::Inspectable: 'static,
+{
+    type Inspectable = InspectOption<T>;
+    fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+        Some(InspectOption {
+            value: match value {
+                Some(value) => Some(new_dyn_sync_inspectable(value)?),
+                None => None,
+            },
+            item_type: std::marker::PhantomData,
+        })
+    }
+}
+/// Edited like an enum with the variants `None` and `Some`
+pub struct InspectOption<T> {
+    value: Option<DynSyncInspectable>,
+    /// Switching to `Some` creates the value from `T::default()`
+    item_type: std::marker::PhantomData<fn() -> T>,
+}
+impl<T> std::fmt::Debug for InspectOption<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InspectOption").field("value", &self.value).finish()
+    }
+}
+impl<T> Clone for InspectOption<T> {
+    fn clone(&self) -> Self {
+        Self { value: self.value.clone(), item_type: std::marker::PhantomData }
+    }
+}
+impl<T: InspectableType + Default + 'static> InspectEnum for InspectOption<T>
+where
+    T::Inspectable: 'static,
+{
+    fn variants(&self) -> &'static [&'static str] {
+        &["None", "Some"]
+    }
+    fn current_index(&self) -> usize {
+        usize::from(self.value.is_some())
+    }
+    fn set_variant(&mut self, index: usize) {
+        if index == 0 {
+            self.value = None;
+        } else if self.value.is_none() {
+            self.value = new_dyn_sync_inspectable(T::default());
+        }
+    }
+    fn current_variant_fields(
+        &mut self,
+    ) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.value.iter().map(|value| ("0".into(), value.clone())).collect()
+    }
+}
+impl<T: InspectableType + Default + 'static> Inspectable for InspectOption<T>
+where
+    T::Inspectable: 'static,
+{
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.current_variant_fields()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "Option"
+    }
+    fn as_enum(&mut self) -> Option<&mut dyn InspectEnum> {
+        Some(self)
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>() {
+            if let (Some(own), Some(other)) =
+                (self.value.clone(), inspect.value.as_ref())
+            {
+                own.lock()
+                    .map_err(|_| "Unable to lock sub".to_string())?
+                    .sync(other)?;
+            } else {
+                self.value.clone_from(&inspect.value);
+            }
+        }
+
+        Ok(())
+    }
+}
+impl<T: 'static> DearMirlGuiModule for InspectOption<T> {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, Inspectable, InspectableType,
+        new_dyn_sync_inspectable, sync_inspectables,
+    },
+};
+
+/// The field names of tuples, tuples with more elements are not supported
+const TUPLE_FIELD_NAMES: [&str; 8] = ["0", "1", "2", "3", "4", "5", "6", "7"];
+
+macro_rules! impl_inspectable_tuple {
+    ($(($($t:ident $idx:tt),+)),*) => {
+        $(
+            impl<$($t: InspectableType + 'static),+> InspectableType
+                for ($($t,)+)
+            where
+                $($t::Inspectable: 'static),+
+            {
+                type Inspectable = InspectTuple;
+                fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+                    Some(InspectTuple {
+                        items: vec![
+                            $(new_dyn_sync_inspectable(value.$idx)?),+
+                        ],
+                    })
+                }
+            }
+        )*
+    };
+}
+impl_inspectable_tuple!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+);
+
+#[derive(Debug, Clone)]
+pub struct InspectTuple {
+    items: Vec<DynSyncInspectable>,
+}
+impl Inspectable for InspectTuple {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        TUPLE_FIELD_NAMES
+            .into_iter()
+            .map(FieldName::from)
+            .zip(self.items.iter().cloned())
+            .collect()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "tuple"
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>()
+            && !sync_inspectables(&self.items, &inspect.items)?
+        {
+            return Err("Tuple lengths do not match".into());
+        }
+
+        Ok(())
+    }
+}
+impl DearMirlGuiModule for InspectTuple {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, InspectCollection, Inspectable,
+        InspectableType, new_dyn_sync_inspectable, sync_inspectables,
+    },
+};
+
+impl<T: InspectableType + Default + 'static> InspectableType for Vec<T>
+where
+    T::Inspectable: 'static,
+{
+    type Inspectable = InspectVec<T>;
+    fn new_from_value(value: Self) -> Option<Self::Inspectable> {
+        Some(InspectVec {
+            items: value
+                .into_iter()
+                .map(new_dyn_sync_inspectable)
+                .collect::<Option<Vec<_>>>()?,
+            item_type: std::marker::PhantomData,
+        })
+    }
+}
+pub struct InspectVec<T> {
+    items: Vec<DynSyncInspectable>,
+    /// New rows are created from `T::default()`
+    item_type: std::marker::PhantomData<fn() -> T>,
+}
+impl<T> std::fmt::Debug for InspectVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InspectVec").field("items", &self.items).finish()
+    }
+}
+impl<T> Clone for InspectVec<T> {
+    fn clone(&self) -> Self {
+        Self { items: self.items.clone(), item_type: std::marker::PhantomData }
+    }
+}
+impl<T: InspectableType + Default + 'static> InspectCollection for InspectVec<T>
+where
+    T::Inspectable: 'static,
+{
+    fn can_resize(&self) -> bool {
+        true
+    }
+    fn can_reorder(&self) -> bool {
+        true
+    }
+    fn add(&mut self) {
+        if let Some(item) = new_dyn_sync_inspectable(T::default()) {
+            self.items.push(item);
+        }
+    }
+    fn remove(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.items.remove(index);
+        }
+    }
+    fn swap(&mut self, a: usize, b: usize) {
+        if a < self.items.len() && b < self.items.len() {
+            self.items.swap(a, b);
+        }
+    }
+}
+impl<T: InspectableType + Default + 'static> Inspectable for InspectVec<T>
+where
+    T::Inspectable: 'static,
+{
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (idx.to_string().into(), item.clone()))
+            .collect()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "Vec"
+    }
+    fn as_collection(&mut self) -> Option<&mut dyn InspectCollection> {
+        Some(self)
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>()
+            && !sync_inspectables(&self.items, &inspect.items)?
+        {
+            // Rows were added or removed, take them over as they are
+            self.items.clone_from(&inspect.items);
+        }
+
+        Ok(())
+    }
+}
+impl<T: 'static> DearMirlGuiModule for InspectVec<T> {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
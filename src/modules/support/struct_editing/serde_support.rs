@@ -0,0 +1,495 @@
+use mirl::render;
+use serde_json::Value;
+
+use crate::{
+    DearMirlGuiModule,
+    modules::support::struct_editing::{
+        DynSyncInspectable, FieldName, InspectValue, Inspectable,
+        new_dyn_sync_inspectable, primitives::number_support::InspectNumber,
+        sync_inspectables,
+    },
+};
+
+/// Serialize a value into a tree of inspectables built from the primitive implementations
+///
+/// Objects and arrays become nested fields, numbers are edited as [i64], [u64] or [f64] in a text input so they keep their exact value
+///
+/// # Errors
+/// When the value cannot be serialized
+pub fn inspectable_from_serialize<T: serde::Serialize>(
+    item: &T,
+) -> Result<DynSyncInspectable, Box<dyn std::error::Error>> {
+    inspectable_from_json(serde_json::to_value(item)?)
+        .ok_or_else(|| "Unable to build the editors for the value".into())
+}
+
+/// Build a tree of inspectables from a generic value tree
+#[must_use]
+pub fn inspectable_from_json(value: Value) -> Option<DynSyncInspectable> {
+    let inspectable: Box<dyn Inspectable> = match value {
+        Value::Null => Box::new(InspectSerdeNull),
+        Value::Bool(value) => return new_dyn_sync_inspectable(value),
+        Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                return new_dyn_sync_inspectable(value);
+            }
+            if let Some(value) = number.as_u64() {
+                return new_dyn_sync_inspectable(value);
+            }
+            // The f64 slider can't hold every value exactly
+            Box::new(InspectNumber::<f64>::new(number.as_f64()?))
+        }
+        Value::String(value) => return new_dyn_sync_inspectable(value),
+        Value::Array(items) => Box::new(InspectSerdeArray {
+            items: items
+                .into_iter()
+                .map(inspectable_from_json)
+                .collect::<Option<Vec<_>>>()?,
+        }),
+        Value::Object(fields) => Box::new(InspectSerdeObject {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| {
+                    Some((key.into(), inspectable_from_json(value)?))
+                })
+                .collect::<Option<Vec<_>>>()?,
+        }),
+    };
+    Some(std::sync::Arc::new(std::sync::Mutex::new(inspectable)))
+}
+
+/// Read the edited values back into a generic value tree
+///
+/// # Errors
+/// When the lock has been poisoned, an inspectable that wasn't built by [`inspectable_from_json`] is found, an integer doesn't fit into [i64]/[u64] or a float is not finite (NaN, infinity)
+pub fn json_from_inspectable(
+    r: &DynSyncInspectable,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut thing =
+        r.lock().map_err(|e| format!("Failed to lock mutex: {e}"))?;
+    if let Some(object) =
+        (**thing).as_any().downcast_ref::<InspectSerdeObject>()
+    {
+        let fields = object.fields.clone();
+        drop(thing); // Release lock before recursing
+        return Ok(Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| {
+                    Ok(((*key).to_string(), json_from_inspectable(field)?))
+                })
+                .collect::<Result<_, Box<dyn std::error::Error>>>()?,
+        ));
+    }
+    if let Some(array) = (**thing).as_any().downcast_ref::<InspectSerdeArray>()
+    {
+        let items = array.items.clone();
+        drop(thing); // Release lock before recursing
+        return Ok(Value::Array(
+            items
+                .iter()
+                .map(json_from_inspectable)
+                .collect::<Result<_, _>>()?,
+        ));
+    }
+    if (**thing).as_any().is::<InspectSerdeNull>() {
+        return Ok(Value::Null);
+    }
+    let name = thing.get_name();
+    match thing.get_value() {
+        Some(InspectValue::Bool(value)) => Ok(Value::Bool(value)),
+        // Never rounded through f64
+        Some(InspectValue::Int(value)) => i64::try_from(value)
+            .map(Value::from)
+            .map_err(|_| format!("{name} is out of range: {value}").into()),
+        Some(InspectValue::UInt(value)) => u64::try_from(value)
+            .map(Value::from)
+            .map_err(|_| format!("{name} is out of range: {value}").into()),
+        Some(InspectValue::Float(value)) => serde_json::Number::from_f64(value)
+            .map(Value::Number)
+            .ok_or_else(|| format!("{name} can't be {value}").into()),
+        Some(InspectValue::Char(value)) => Ok(Value::from(value.to_string())),
+        Some(InspectValue::String(value)) => Ok(Value::from(value)),
+        Some(InspectValue::Variant(value)) => Ok(Value::from(value)),
+        Some(InspectValue::Length(_)) | None => {
+            Err(format!("Unable to read a value from {name}").into())
+        }
+    }
+}
+
+/// Find the edited fields that keep the value from being deserialized, keyed by their field path
+///
+/// Every changed field is applied to the last valid value on its own, the ones that fail on their own are reported with their reason.
+/// When there is no valid value yet or only the combination of the edits fails, `error` is reported at every changed field
+#[must_use]
+pub fn locate_deserialize_error<T: serde::de::DeserializeOwned>(
+    valid: Option<&Value>,
+    edited: &Value,
+    error: &serde_json::Error,
+) -> std::collections::HashMap<String, String> {
+    let Some(valid) = valid else {
+        return std::collections::HashMap::from([(
+            String::new(),
+            error.to_string(),
+        )]);
+    };
+    let mut changed = Vec::new();
+    collect_changed_paths(valid, edited, &mut Vec::new(), &mut changed);
+
+    let mut errors: std::collections::HashMap<String, String> = changed
+        .iter()
+        .filter_map(|path| {
+            let mut candidate = valid.clone();
+            *get_value_mut(&mut candidate, path)? =
+                get_value(edited, path)?.clone();
+            serde_json::from_value::<T>(candidate)
+                .err()
+                .map(|error| (path.join("."), error.to_string()))
+        })
+        .collect();
+    if errors.is_empty() {
+        errors = changed
+            .iter()
+            .map(|path| (path.join("."), error.to_string()))
+            .collect();
+    }
+    errors
+}
+
+/// Collect the paths of all values that differ, objects with different keys and arrays with different lengths are reported as a whole
+fn collect_changed_paths(
+    old: &Value,
+    new: &Value,
+    path: &mut Vec<String>,
+    changed: &mut Vec<Vec<String>>,
+) {
+    match (old, new) {
+        (Value::Object(old_fields), Value::Object(new_fields))
+            if old_fields.len() == new_fields.len()
+                && old_fields
+                    .keys()
+                    .all(|key| new_fields.contains_key(key)) =>
+        {
+            for (key, old_value) in old_fields {
+                path.push(key.clone());
+                collect_changed_paths(
+                    old_value,
+                    &new_fields[key],
+                    path,
+                    changed,
+                );
+                path.pop();
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items))
+            if old_items.len() == new_items.len() =>
+        {
+            for (idx, (old_item, new_item)) in
+                old_items.iter().zip(new_items).enumerate()
+            {
+                path.push(idx.to_string());
+                collect_changed_paths(old_item, new_item, path, changed);
+                path.pop();
+            }
+        }
+        _ => {
+            if old != new {
+                changed.push(path.clone());
+            }
+        }
+    }
+}
+
+/// Get the value at the given path of object keys and array indices
+fn get_value<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Object(fields) => fields.get(segment),
+        Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// Get the value at the given path of object keys and array indices
+fn get_value_mut<'a>(
+    value: &'a mut Value,
+    path: &[String],
+) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Object(fields) => fields.get_mut(segment),
+        Value::Array(items) => items.get_mut(segment.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// An object/map of a serialized value
+#[derive(Debug, Clone)]
+pub struct InspectSerdeObject {
+    fields: Vec<(FieldName, DynSyncInspectable)>,
+}
+impl Inspectable for InspectSerdeObject {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.fields.clone()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "object"
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>() {
+            let same_keys = self
+                .fields
+                .iter()
+                .map(|(key, _)| key)
+                .eq(inspect.fields.iter().map(|(key, _)| key));
+            let own: Vec<_> =
+                self.fields.iter().map(|(_, item)| item.clone()).collect();
+            let other: Vec<_> =
+                inspect.fields.iter().map(|(_, item)| item.clone()).collect();
+            if !same_keys || !sync_inspectables(&own, &other)? {
+                self.fields.clone_from(&inspect.fields);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DearMirlGuiModule for InspectSerdeObject {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
+
+/// An array/sequence of a serialized value
+#[derive(Debug, Clone)]
+pub struct InspectSerdeArray {
+    items: Vec<DynSyncInspectable>,
+}
+impl Inspectable for InspectSerdeArray {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (idx.to_string().into(), item.clone()))
+            .collect()
+    }
+    fn get_name(&mut self) -> &'static str {
+        "array"
+    }
+    fn sync(
+        &mut self,
+        new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut val = new
+            .lock()
+            .map_err(|_| "Unable to lock".to_string())?
+            .as_any_cloned();
+        if let Some(inspect) = val.downcast_mut::<Self>()
+            && !sync_inspectables(&self.items, &inspect.items)?
+        {
+            self.items.clone_from(&inspect.items);
+        }
+
+        Ok(())
+    }
+}
+
+impl DearMirlGuiModule for InspectSerdeArray {
+    fn draw(
+        &mut self,
+        _formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        // The items are drawn by the struct editor
+        (
+            mirl::prelude::Buffer::new_empty((0, 0)),
+            crate::module_manager::InsertionMode::ReplaceAll,
+        )
+    }
+
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn get_width(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        mirl::math::ConstNumbers128::CONST_0
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
+
+/// A missing value, cannot be edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InspectSerdeNull;
+impl Inspectable for InspectSerdeNull {
+    fn get_fields_mut(&mut self) -> Vec<(FieldName, DynSyncInspectable)> {
+        vec![]
+    }
+    fn get_name(&mut self) -> &'static str {
+        "null"
+    }
+    fn sync(
+        &mut self,
+        _new: &DynSyncInspectable,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+impl DearMirlGuiModule for InspectSerdeNull {
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (mirl::prelude::Buffer, crate::module_manager::InsertionMode) {
+        let mut buffer = mirl::prelude::Buffer::new_empty((
+            self.get_width(formatting) as usize,
+            self.get_height(formatting) as usize,
+        ));
+        render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+            &mut buffer,
+            "null",
+            (0, 0),
+            formatting.text_color,
+            formatting.height as f32,
+            &formatting.font,
+        );
+        (buffer, crate::module_manager::InsertionMode::ReplaceAll)
+    }
+
+    fn get_height(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        formatting.height as crate::DearMirlGuiCoordinateType
+    }
+
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        render::get_text_width(
+            "null",
+            formatting.height as f32,
+            &formatting.font,
+        ) as crate::DearMirlGuiCoordinateType
+    }
+
+    fn update(
+        &mut self,
+        _inputs: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        crate::GuiOutput::empty()
+    }
+
+    fn need_redraw(&mut self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Offsets = std::collections::HashMap<String, Vec<i8>>;
+
+    fn errors_for(edited: &Value) -> Vec<String> {
+        let valid = serde_json::json!({ "a": [1, 2], "b": [3] });
+        // A valid edit has no errors, failing the comparison
+        serde_json::from_value::<Offsets>(edited.clone()).err().map_or_else(
+            Vec::new,
+            |error| {
+                let mut paths: Vec<String> =
+                    locate_deserialize_error::<Offsets>(
+                        Some(&valid),
+                        edited,
+                        &error,
+                    )
+                    .into_keys()
+                    .collect();
+                paths.sort();
+                paths
+            },
+        )
+    }
+
+    #[test]
+    fn deserialize_errors_are_reported_at_the_edited_field() {
+        let edited = serde_json::json!({ "a": [5, -200], "b": [3] });
+        assert_eq!(errors_for(&edited), ["a.1"]);
+        let edited = serde_json::json!({ "a": [300, 2], "b": [-300] });
+        assert_eq!(errors_for(&edited), ["a.0", "b.0"]);
+    }
+
+    #[test]
+    fn changed_lengths_are_reported_as_a_whole() {
+        let edited = serde_json::json!({ "a": [1, 2], "b": [3, 4, 500] });
+        assert_eq!(errors_for(&edited), ["b"]);
+    }
+
+    #[test]
+    fn errors_without_a_valid_value_are_reported_at_the_root() {
+        let edited = serde_json::json!({ "a": [300] });
+        let errors = serde_json::from_value::<Offsets>(edited.clone())
+            .err()
+            .map(|error| {
+                locate_deserialize_error::<Offsets>(None, &edited, &error)
+                    .into_keys()
+                    .collect::<Vec<_>>()
+            });
+        assert_eq!(errors, Some(vec![String::new()]));
+    }
+}
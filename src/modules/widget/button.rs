@@ -26,9 +26,9 @@ pub struct Button {
     pub scroll: f64,
     /// By how much the scroll should be scaled
     pub scroll_multiplier: f64,
-    /// By how much the color of the button will change when the mouse is hover over the button
+    /// By how much the brightness of the hover color of the [`Theme`](crate::Theme) will change when the mouse is hover over the button
     pub color_change_on_hover: f32,
-    /// By how much the color of the button will change when the mouse is clicking the button (In addition to the hover color change)
+    /// By how much the brightness of the active color of the [`Theme`](crate::Theme) will change when the mouse is clicking the button
     pub color_change_on_clicking: f32,
     /// At some point you just can't read the text anymore if it is scaled to miniature sizes so instead it'll scroll
     pub threshold_before_text_scrolls: f32,
@@ -36,8 +36,8 @@ pub struct Button {
     pub menus: Vec<(usize, bool)>,
 }
 impl Button {
-    const DEFAULT_COLOR_CHANGE_ON_HOVER: f32 = -5.0;
-    const DEFAULT_COLOR_CHANGE_ON_CLICKING: f32 = -5.0;
+    const DEFAULT_COLOR_CHANGE_ON_HOVER: f32 = 0.0;
+    const DEFAULT_COLOR_CHANGE_ON_CLICKING: f32 = 0.0;
     const DEFAULT_THRESHOLD_BEFORE_TEXT_SCROLLS: f32 = 0.6;
     const DEFAULT_SCROLL_MULTIPLIER: f64 = 0.1;
     #[allow(missing_docs)]
@@ -119,22 +119,30 @@ impl DearMirlGuiModule for Button {
         self.needs_redraw.set(false);
         let text_color = formatting.text_color;
 
-        let mut color = formatting.foreground_color;
         //println!("Hover: {} Drawing for: {}", self.hovering, info.container_id);
-        if self.hovering == info.container_id {
-            color = mirl::graphics::adjust_brightness_hsl_of_rgb(
-                color,
+        let color = if self.hovering != info.container_id {
+            formatting.foreground_color
+        } else if self.pressed.down {
+            mirl::graphics::adjust_brightness_hsl_of_rgb(
+                formatting.theme.active_color,
+                self.color_change_on_clicking,
+            )
+        } else {
+            mirl::graphics::adjust_brightness_hsl_of_rgb(
+                formatting.theme.hover_color,
                 self.color_change_on_hover,
-            );
-            if self.pressed.down {
-                color = mirl::graphics::adjust_brightness_hsl_of_rgb(
-                    color,
-                    self.color_change_on_clicking,
-                );
-            }
-        }
-        let mut buffer =
-            Buffer::new_empty_with_color((self.width, self.height), color);
+            )
+        };
+        let mut buffer = Buffer::new_empty_with_color(
+            (self.width, self.height),
+            formatting.background_color,
+        );
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (self.width as isize, self.height as isize),
+            color,
+        );
 
         let text_height = render::get_text_height(
             &self.text,
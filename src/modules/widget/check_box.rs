@@ -46,6 +46,8 @@ pub struct CheckBox {
     pub checked: usize,
     /// How many click states exist
     pub states: Vec<Buffer>,
+    /// If the states were given when creating the check box, otherwise the default marks are redrawn in the accent color of the theme
+    pub custom_states: bool,
     /// The text next to he check box
     pub text: String,
     #[allow(missing_docs)]
@@ -79,11 +81,9 @@ impl CheckBox {
             height,
             text,
             checked: checked.unwrap_or_default(),
+            custom_states: states.is_some(),
             states: states.unwrap_or_else(|| {
-                Vec::from([
-                    Buffer::new_empty((0, 0)),
-                    super::misc::draw_cross(anti_margin, 2),
-                ])
+                get_default_states(2, anti_margin, &formatting)
             }),
             margin: height / 10,
             needs_redraw: true,
@@ -105,10 +105,8 @@ impl CheckBox {
             height,
             text,
             checked: 0,
-            states: Vec::from([
-                Buffer::new_empty((0, 0)),
-                super::misc::draw_cross(anti_margin, 2),
-            ]),
+            states: get_default_states(2, anti_margin, &get_formatting()),
+            custom_states: false,
             margin: height / 10,
             needs_redraw: true,
         }
@@ -121,14 +119,8 @@ impl CheckBox {
             height,
             text,
             checked: 0,
-            states: Vec::from([
-                Buffer::new_empty((0, 0)),
-                super::misc::draw_cross(anti_margin, 2),
-                super::misc::draw_blocking(
-                    anti_margin,
-                    mirl::graphics::colors::WHITE,
-                ),
-            ]),
+            states: get_default_states(3, anti_margin, &get_formatting()),
+            custom_states: false,
             margin: height / 10,
             needs_redraw: true,
         }
@@ -140,6 +132,26 @@ impl CheckBox {
     }
 }
 
+/// Empty, a cross and a filled block in the accent color, cycling through them for more than 3 states
+fn get_default_state(
+    state: usize,
+    size: usize,
+    formatting: &crate::Formatting,
+) -> Buffer {
+    match state % 3 {
+        0 => Buffer::new_empty((0, 0)),
+        1 => super::misc::draw_cross(size, 2, formatting.theme.accent_color),
+        _ => super::misc::draw_blocking(size, formatting.theme.accent_color),
+    }
+}
+fn get_default_states(
+    count: usize,
+    size: usize,
+    formatting: &crate::Formatting,
+) -> Vec<Buffer> {
+    (0..count).map(|state| get_default_state(state, size, formatting)).collect()
+}
+
 impl DearMirlGuiModule for CheckBox {
     fn apply_new_formatting(&mut self, _formatting: &crate::Formatting) {}
     fn get_height(
@@ -172,7 +184,7 @@ impl DearMirlGuiModule for CheckBox {
             self.get_width(formatting) as usize,
             self.height,
         ));
-        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+        formatting.theme.draw_rectangle(
             &mut buffer,
             (0, 0),
             (self.height as isize, self.height as isize),
@@ -183,7 +195,7 @@ impl DearMirlGuiModule for CheckBox {
             &mut buffer,
             &self.text,
             (self.height + self.margin, 0),
-            mirl::graphics::colors::WHITE,
+            formatting.text_color,
             self.height as f32,
             &formatting.font,
         );
@@ -191,7 +203,15 @@ impl DearMirlGuiModule for CheckBox {
         let margin = self.height / 5;
         let anti_margin = self.height - margin;
 
-        let to_draw = &self.states[self.checked];
+        let default_state;
+        let to_draw = if self.custom_states {
+            &self.states[self.checked]
+        } else {
+            // Follows theme changes
+            default_state =
+                get_default_state(self.checked, anti_margin, formatting);
+            &default_state
+        };
         if buffer.width == anti_margin && buffer.height == anti_margin {
             render::draw_buffer_on_buffer::<true, true, false, true>(
                 &mut buffer,
@@ -248,6 +268,8 @@ impl DearMirlGuiModule for CheckBox {
                     hide_cursor: false,
                     new_cursor_position: None,
                     request_clipboard_data: false,
+                    selected_menu_item: None,
+                    window_events: Vec::new(),
                 };
             }
         }
@@ -231,6 +231,8 @@ impl DearMirlGuiModule for Crank {
                 hide_cursor: false,
                 text_input_selected: false,
                 request_clipboard_data: false,
+                selected_menu_item: None,
+                window_events: Vec::new(),
             };
         }
         self.cranking = 0;
@@ -243,6 +245,8 @@ impl DearMirlGuiModule for Crank {
                 hide_cursor: false,
                 text_input_selected: false,
                 request_clipboard_data: false,
+                selected_menu_item: None,
+                window_events: Vec::new(),
             };
         }
         crate::GuiOutput::empty()
@@ -1,5 +1,4 @@
 use mirl::{
-    graphics::rgba_to_u32,
     math::{get_center_position_of_object_for_object, interpolate},
     prelude::Buffer, render,
 };
@@ -72,8 +71,8 @@ impl DearMirlGuiModule for Lever {
             formatting.foreground_color,
             0.5,
         );
-        let hole_color = mirl::graphics::colors::BLACK;
-        let stick_color = rgba_to_u32(100, 60, 30, 255);
+        let hole_color = formatting.background_color;
+        let stick_color = formatting.theme.border_color;
 
         let base_width = ((self.width as f32) * self.base_size) as usize;
         let base_height =
@@ -106,7 +105,7 @@ impl DearMirlGuiModule for Lever {
             self.height,
         );
 
-        render::draw_rectangle::<true>(
+        formatting.theme.draw_rectangle(
             &mut buffer,
             (base_offset.0 as isize, base_offset.1 as isize),
             (base_width as isize, base_height as isize),
@@ -182,7 +181,7 @@ impl DearMirlGuiModule for Lever {
             );
         }
 
-        render::draw_rectangle::<true>(
+        formatting.theme.draw_rectangle(
             &mut buffer,
             (handle_offset.0 as isize, handle_pos),
             (handle_width as isize, handle_height as isize),
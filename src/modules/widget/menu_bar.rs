@@ -0,0 +1,786 @@
+use mirl::{
+    extensions::*,
+    platform::{CursorStyle, keycodes::KeyCode},
+    prelude::Buffer,
+    render,
+};
+
+use crate::{
+    DearMirlGuiModule, FocusTaken, ModulePath,
+    module_manager::{InsertionMode, get_formatting},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A clickable entry of a [`Menu`]
+pub struct MenuItem {
+    /// Returned in [`GuiOutput::selected_menu_item`](crate::GuiOutput::selected_menu_item) when the item is clicked
+    pub id: String,
+    /// The displayed text
+    pub label: String,
+    /// A keyboard shortcut displayed next to the label, the shortcut itself has to be handled by you
+    pub shortcut: Option<String>,
+    /// If the item can be checked - Is toggled when the item is clicked
+    pub checked: Option<bool>,
+    /// Disabled items are greyed out and cannot be clicked
+    pub enabled: bool,
+}
+impl MenuItem {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn new(id: &str, label: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            shortcut: None,
+            checked: None,
+            enabled: true,
+        }
+    }
+    #[must_use]
+    /// Show a keyboard shortcut next to the label (`Ctrl+S`)
+    pub fn with_shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+    #[must_use]
+    /// Make the item checkable with the given initial state
+    pub const fn checkable(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+    #[must_use]
+    /// Grey the item out so it cannot be clicked
+    pub const fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single row of a [`Menu`]
+pub enum MenuEntry {
+    /// A clickable item
+    Item(MenuItem),
+    /// A nested menu opened when hovering over it
+    Submenu(Menu),
+    /// A horizontal line between entries
+    Separator,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A menu of the [`MenuBar`] or a submenu of another menu
+pub struct Menu {
+    /// The text shown in the menu bar or the parent menu
+    pub label: String,
+    /// The rows of the menu
+    pub entries: Vec<MenuEntry>,
+}
+impl Menu {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn new(label: &str) -> Self {
+        Self { label: label.to_string(), entries: Vec::new() }
+    }
+    #[must_use]
+    /// Add a clickable item
+    pub fn with_item(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+    #[must_use]
+    /// Add a nested menu
+    pub fn with_submenu(mut self, menu: Self) -> Self {
+        self.entries.push(MenuEntry::Submenu(menu));
+        self
+    }
+    #[must_use]
+    /// Add a horizontal line
+    pub fn with_separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+    /// Find the item with the given id in this menu or any submenu
+    pub fn find_item_mut(&mut self, id: &str) -> Option<&mut MenuItem> {
+        self.entries.iter_mut().find_map(|entry| match entry {
+            MenuEntry::Item(item) if item.id == id => Some(item),
+            MenuEntry::Submenu(menu) => menu.find_item_mut(id),
+            _ => None,
+        })
+    }
+}
+
+/// An opened menu: Its entries, position and size
+type Popup<'a> = (&'a [MenuEntry], (isize, isize), (usize, usize));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// What happened to a [`PopupMenu`] during an update
+pub enum PopupEvent {
+    /// The mouse is somewhere else
+    None,
+    /// The mouse is above the menu
+    Hovered,
+    /// An item was clicked, contains the id of the item
+    Selected(String),
+    /// The user clicked somewhere else or pressed Escape
+    Dismissed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// The state of an opened menu and its opened submenus, used by the [`MenuBar`] and for context menus
+pub struct PopupMenu {
+    /// Where the top left corner of the menu is
+    pub position: (isize, isize),
+    /// The indices of the opened submenus
+    pub open: Vec<usize>,
+    /// The entry the mouse is above, (depth, index)
+    pub hovered_entry: Option<(usize, usize)>,
+}
+impl PopupMenu {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub const fn new(position: (isize, isize)) -> Self {
+        Self { position, open: Vec::new(), hovered_entry: None }
+    }
+    fn get_text_width(text: &str, formatting: &crate::Formatting) -> usize {
+        render::get_text_width(text, formatting.height as f32, &formatting.font)
+            as usize
+    }
+    const fn get_entry_height(
+        entry: &MenuEntry,
+        formatting: &crate::Formatting,
+    ) -> usize {
+        match entry {
+            MenuEntry::Separator => formatting.vertical_margin * 2 + 1,
+            _ => formatting.height + formatting.vertical_margin * 2,
+        }
+    }
+    /// Size of a popup: Check column, label, shortcut and submenu arrow
+    fn get_popup_size(
+        entries: &[MenuEntry],
+        formatting: &crate::Formatting,
+    ) -> (usize, usize) {
+        let mut label_width = 0;
+        let mut shortcut_width = 0;
+        for entry in entries {
+            match entry {
+                MenuEntry::Item(item) => {
+                    label_width = label_width
+                        .max(Self::get_text_width(&item.label, formatting));
+                    if let Some(shortcut) = &item.shortcut {
+                        shortcut_width = shortcut_width
+                            .max(Self::get_text_width(shortcut, formatting));
+                    }
+                }
+                MenuEntry::Submenu(menu) => {
+                    label_width = label_width
+                        .max(Self::get_text_width(&menu.label, formatting));
+                }
+                MenuEntry::Separator => {}
+            }
+        }
+        if shortcut_width > 0 {
+            shortcut_width += formatting.height;
+        }
+        (
+            formatting.height * 2
+                + label_width
+                + shortcut_width
+                + formatting.horizontal_margin * 2,
+            entries
+                .iter()
+                .map(|entry| Self::get_entry_height(entry, formatting))
+                .sum::<usize>()
+                + formatting.vertical_margin * 2,
+        )
+    }
+    /// The menu followed by all opened submenus
+    fn get_popups<'a>(
+        &self,
+        entries: &'a [MenuEntry],
+        formatting: &crate::Formatting,
+    ) -> Vec<Popup<'a>> {
+        let mut entries = entries;
+        let mut position = self.position;
+        let mut popups: Vec<Popup<'a>> = Vec::from([(
+            entries,
+            position,
+            Self::get_popup_size(entries, formatting),
+        )]);
+        for submenu_idx in &self.open {
+            let Some(MenuEntry::Submenu(submenu)) = entries.get(*submenu_idx)
+            else {
+                break;
+            };
+            let parent_width = popups[popups.len() - 1].2.0;
+            let row_y: usize = entries[..*submenu_idx]
+                .iter()
+                .map(|entry| Self::get_entry_height(entry, formatting))
+                .sum();
+            position = position.add((parent_width as isize, row_y as isize));
+            entries = submenu.entries.as_slice();
+            popups.push((
+                entries,
+                position,
+                Self::get_popup_size(entries, formatting),
+            ));
+        }
+        popups
+    }
+    /// The popup and entry at a position, deepest popups are on top
+    fn get_entry_at(
+        &self,
+        entries: &[MenuEntry],
+        pos: (f32, f32),
+        formatting: &crate::Formatting,
+    ) -> Option<(usize, Option<usize>)> {
+        for (depth, (entries, position, size)) in
+            self.get_popups(entries, formatting).iter().enumerate().rev()
+        {
+            let local = pos.sub((position.0 as f32, position.1 as f32));
+            if local.0 < 0.0
+                || local.1 < 0.0
+                || local.0 >= size.0 as f32
+                || local.1 >= size.1 as f32
+            {
+                continue;
+            }
+            let mut y = formatting.vertical_margin as f32;
+            for (idx, entry) in entries.iter().enumerate() {
+                let height = Self::get_entry_height(entry, formatting) as f32;
+                if (y..y + height).contains(&local.1) {
+                    return Some((depth, Some(idx)));
+                }
+                y += height;
+            }
+            return Some((depth, None));
+        }
+        None
+    }
+    /// The entries of the opened (sub)menu at the given depth
+    fn get_open_entries_mut<'a>(
+        &self,
+        entries: &'a mut [MenuEntry],
+        depth: usize,
+    ) -> Option<&'a mut [MenuEntry]> {
+        let mut entries = entries;
+        for submenu_idx in self.open.get(..depth)? {
+            let MenuEntry::Submenu(submenu) = entries.get_mut(*submenu_idx)?
+            else {
+                return None;
+            };
+            entries = submenu.entries.as_mut_slice();
+        }
+        Some(entries)
+    }
+    /// Draw the menu and all opened submenus, each at their position
+    #[must_use]
+    pub fn draw(
+        &self,
+        entries: &[MenuEntry],
+        formatting: &crate::Formatting,
+    ) -> Vec<(Buffer, (isize, isize))> {
+        self.get_popups(entries, formatting)
+            .into_iter()
+            .enumerate()
+            .map(|(depth, (entries, position, size))| {
+                (self.draw_popup(depth, entries, size, formatting), position)
+            })
+            .collect()
+    }
+    #[allow(clippy::too_many_lines)]
+    fn draw_popup(
+        &self,
+        depth: usize,
+        entries: &[MenuEntry],
+        size: (usize, usize),
+        formatting: &crate::Formatting,
+    ) -> Buffer {
+        let mut buffer =
+            Buffer::new_empty_with_color(size, formatting.foreground_color);
+        let border_color = formatting.theme.border_color;
+        for (pos, rect) in [
+            ((0, 0), (size.0, 1)),
+            ((0, size.1 - 1), (size.0, 1)),
+            ((0, 0), (1, size.1)),
+            ((size.0 - 1, 0), (1, size.1)),
+        ] {
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                (pos.0 as isize, pos.1 as isize),
+                (rect.0 as isize, rect.1 as isize),
+                border_color,
+            );
+        }
+        let disabled_color = formatting.theme.disabled_color;
+        let height = formatting.height as isize;
+        let mut y = formatting.vertical_margin as isize;
+        for (idx, entry) in entries.iter().enumerate() {
+            let entry_height =
+                Self::get_entry_height(entry, formatting) as isize;
+            let highlighted = self.hovered_entry == Some((depth, idx))
+                || self.open.get(depth) == Some(&idx);
+            if highlighted && !matches!(entry, MenuEntry::Separator) {
+                render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                    &mut buffer,
+                    (1, y),
+                    (size.0 as isize - 2, entry_height),
+                    formatting.theme.hover_color,
+                );
+            }
+            let text_y = y + formatting.vertical_margin as isize;
+            let label_x = formatting.horizontal_margin as isize + height;
+            match entry {
+                MenuEntry::Separator => {
+                    render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                        &mut buffer,
+                        (
+                            formatting.horizontal_margin as isize,
+                            y + entry_height / 2,
+                        ),
+                        (
+                            (size.0 - formatting.horizontal_margin * 2)
+                                as isize,
+                            1,
+                        ),
+                        border_color,
+                    );
+                }
+                MenuEntry::Item(item) => {
+                    let color = if item.enabled {
+                        formatting.text_color
+                    } else {
+                        disabled_color
+                    };
+                    if item.checked == Some(true) {
+                        let mark = height / 2;
+                        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                            &mut buffer,
+                            (
+                                formatting.horizontal_margin as isize
+                                    + (height - mark) / 2,
+                                text_y + (height - mark) / 2,
+                            ),
+                            (mark, mark),
+                            color,
+                        );
+                    }
+                    render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                        &mut buffer,
+                        &item.label,
+                        (label_x, text_y),
+                        color,
+                        formatting.height as f32,
+                        &formatting.font,
+                    );
+                    if let Some(shortcut) = &item.shortcut {
+                        render::draw_text_antialiased_isize::<
+                            { crate::DRAW_SAFE },
+                        >(
+                            &mut buffer,
+                            shortcut,
+                            (
+                                (size.0
+                                    - formatting.horizontal_margin
+                                    - formatting.height
+                                    - Self::get_text_width(
+                                        shortcut, formatting,
+                                    )) as isize,
+                                text_y,
+                            ),
+                            disabled_color,
+                            formatting.height as f32,
+                            &formatting.font,
+                        );
+                    }
+                }
+                MenuEntry::Submenu(menu) => {
+                    render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                        &mut buffer,
+                        &menu.label,
+                        (label_x, text_y),
+                        formatting.text_color,
+                        formatting.height as f32,
+                        &formatting.font,
+                    );
+                    render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                        &mut buffer,
+                        ">",
+                        (
+                            (size.0 - formatting.horizontal_margin) as isize
+                                - height,
+                            text_y,
+                        ),
+                        formatting.text_color,
+                        formatting.height as f32,
+                        &formatting.font,
+                    );
+                }
+            }
+            y += entry_height;
+        }
+        buffer
+    }
+    /// Update the menu, the mouse position has to be in the same space as [`position`](Self::position)
+    ///
+    /// Checkable items are toggled when clicked
+    pub fn update(
+        &mut self,
+        entries: &mut [MenuEntry],
+        mouse_pos: Option<(f32, f32)>,
+        info: &crate::ModuleUpdateInfo,
+        formatting: &crate::Formatting,
+    ) -> (PopupEvent, Option<CursorStyle>) {
+        if info.pressed_keys.contains(&KeyCode::Escape) {
+            return (PopupEvent::Dismissed, None);
+        }
+        let hovered = mouse_pos
+            .and_then(|pos| self.get_entry_at(entries, pos, formatting));
+        self.hovered_entry =
+            hovered.and_then(|(depth, idx)| idx.map(|idx| (depth, idx)));
+        let Some((depth, entry_idx)) = hovered else {
+            if info.mouse_info.left.clicked
+                || info.mouse_info.right.clicked
+                || info.mouse_info.middle.clicked
+            {
+                return (PopupEvent::Dismissed, None);
+            }
+            return (PopupEvent::None, None);
+        };
+        let Some(entry_idx) = entry_idx else {
+            return (PopupEvent::Hovered, None);
+        };
+        let Some(depth_entries) = self.get_open_entries_mut(entries, depth)
+        else {
+            return (PopupEvent::Hovered, None);
+        };
+        let mut cursor_style = None;
+        let mut selected = None;
+        let is_submenu = match &mut depth_entries[entry_idx] {
+            MenuEntry::Submenu(_) => true,
+            MenuEntry::Item(item) => {
+                cursor_style = Some(if item.enabled {
+                    CursorStyle::Pointer
+                } else {
+                    CursorStyle::NotAllowed
+                });
+                if item.enabled && info.mouse_info.left.clicked {
+                    if let Some(checked) = &mut item.checked {
+                        *checked = !*checked;
+                    }
+                    selected = Some(item.id.clone());
+                }
+                false
+            }
+            MenuEntry::Separator => false,
+        };
+        if let Some(selected) = selected {
+            return (PopupEvent::Selected(selected), cursor_style);
+        }
+        // Hovering a submenu opens it, hovering anything else closes deeper submenus
+        self.open.truncate(depth);
+        if is_submenu {
+            self.open.push(entry_idx);
+        }
+        (PopupEvent::Hovered, cursor_style)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A classic "File / Edit / View" menu bar, best used as a toolbar module
+///
+/// The opened menus are drawn above all windows by the [`DearMirlGuiManager`](crate::DearMirlGuiManager), the id of the clicked item is returned in [`GuiOutput::selected_menu_item`](crate::GuiOutput::selected_menu_item)
+pub struct MenuBar {
+    /// The menus shown in the bar
+    pub menus: Vec<Menu>,
+    /// The index of the opened menu
+    pub open: Option<usize>,
+    /// The opened menu, positioned relative to the menu bar
+    pub popup: PopupMenu,
+    /// The menu in the bar the mouse is above
+    pub hovered: Option<usize>,
+    /// Where the menu bar is on screen
+    pub screen_position: (f32, f32),
+    #[allow(missing_docs)]
+    pub height: usize,
+    #[allow(missing_docs)]
+    pub needs_redraw: std::cell::Cell<bool>,
+}
+impl MenuBar {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn new(menus: Vec<Menu>) -> Self {
+        Self {
+            menus,
+            open: None,
+            popup: PopupMenu::default(),
+            hovered: None,
+            screen_position: (0.0, 0.0),
+            height: get_formatting().height,
+            needs_redraw: std::cell::Cell::new(true),
+        }
+    }
+    /// Open the menu at the given index below its label
+    pub fn open_menu(&mut self, idx: usize) {
+        let formatting = get_formatting();
+        self.open = Some(idx);
+        self.popup = PopupMenu::new((
+            self.get_menu_x(idx, &formatting) as isize,
+            self.height as isize,
+        ));
+        self.needs_redraw.set(true);
+    }
+    /// Close all opened menus
+    pub fn close(&mut self) {
+        if self.open.take().is_some() {
+            self.needs_redraw.set(true);
+        }
+    }
+    /// Find the item with the given id in any menu
+    pub fn find_item_mut(&mut self, id: &str) -> Option<&mut MenuItem> {
+        self.menus.iter_mut().find_map(|menu| menu.find_item_mut(id))
+    }
+    /// The width of a menu in the bar
+    fn get_menu_width(menu: &Menu, formatting: &crate::Formatting) -> usize {
+        PopupMenu::get_text_width(&menu.label, formatting)
+            + formatting.horizontal_margin * 4
+    }
+    /// Where a menu in the bar starts horizontally
+    fn get_menu_x(&self, idx: usize, formatting: &crate::Formatting) -> usize {
+        self.menus[..idx]
+            .iter()
+            .map(|menu| Self::get_menu_width(menu, formatting))
+            .sum()
+    }
+    /// The menu in the bar at the given horizontal position
+    fn get_menu_at(
+        &self,
+        x: f32,
+        formatting: &crate::Formatting,
+    ) -> Option<usize> {
+        let mut start = 0.0;
+        for (idx, menu) in self.menus.iter().enumerate() {
+            let end = start + Self::get_menu_width(menu, formatting) as f32;
+            if (start..end).contains(&x) {
+                return Some(idx);
+            }
+            start = end;
+        }
+        None
+    }
+}
+
+impl DearMirlGuiModule for MenuBar {
+    fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
+        self.needs_redraw.set(super::misc::determine_need_redraw(need_redraw));
+    }
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> (Buffer, InsertionMode) {
+        self.needs_redraw.set(false);
+        let mut buffer = Buffer::new_empty_with_color(
+            (
+                self.get_width(formatting) as usize,
+                self.get_height(formatting) as usize,
+            ),
+            formatting.foreground_color,
+        );
+        let mut x = 0;
+        for (idx, menu) in self.menus.iter().enumerate() {
+            let width = Self::get_menu_width(menu, formatting);
+            if self.hovered == Some(idx) || self.open == Some(idx) {
+                render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                    &mut buffer,
+                    (x as isize, 0),
+                    (width as isize, self.height as isize),
+                    formatting.theme.hover_color,
+                );
+            }
+            render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                &menu.label,
+                (
+                    (x + formatting.horizontal_margin * 2) as isize,
+                    (self.height as isize - formatting.height as isize) / 2,
+                ),
+                formatting.text_color,
+                formatting.height as f32,
+                &formatting.font,
+            );
+            x += width;
+        }
+        (buffer, InsertionMode::ReplaceAll)
+    }
+    fn get_height(
+        &mut self,
+        _formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.height as crate::DearMirlGuiCoordinateType
+    }
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.get_menu_x(self.menus.len(), formatting)
+            as crate::DearMirlGuiCoordinateType
+    }
+    fn update(&mut self, info: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        if let (Some(mouse_pos), Some(real_mouse_pos)) =
+            (info.mouse_pos, info.real_mouse_pos)
+        {
+            self.screen_position = real_mouse_pos.sub(mouse_pos);
+        }
+        if info.focus_taken.is_focus_taken() {
+            if self.hovered.take().is_some() {
+                self.needs_redraw.set(true);
+            }
+            return crate::GuiOutput::empty();
+        }
+        let formatting = get_formatting();
+        let hovered = info.mouse_pos.and_then(|pos| {
+            if (0.0..self.height as f32).contains(&pos.1) {
+                self.get_menu_at(pos.0, &formatting)
+            } else {
+                None
+            }
+        });
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.needs_redraw.set(true);
+        }
+        let Some(hovered) = hovered else {
+            return crate::GuiOutput::empty();
+        };
+        if info.mouse_info.left.clicked {
+            if self.open == Some(hovered) {
+                self.close();
+            } else {
+                self.open_menu(hovered);
+            }
+            return crate::GuiOutput::default(FocusTaken::FunctionallyTaken)
+                .with_cursor(CursorStyle::Pointer);
+        }
+        // Moving over another menu while one is open switches to it
+        if self.open.is_some_and(|open| open != hovered) {
+            self.open_menu(hovered);
+        }
+        crate::GuiOutput::default(FocusTaken::VisuallyTaken)
+            .with_cursor(CursorStyle::Pointer)
+    }
+    fn need_redraw(&mut self) -> bool {
+        if self.needs_redraw.get() {
+            self.needs_redraw.set(false);
+            true
+        } else {
+            false
+        }
+    }
+    fn draw_overlay(
+        &mut self,
+        formatting: &crate::Formatting,
+        _info: &crate::ModuleDrawInfo,
+    ) -> Vec<(Buffer, (isize, isize))> {
+        let Some(open) = self.open else {
+            return Vec::new();
+        };
+        let screen_position =
+            (self.screen_position.0 as isize, self.screen_position.1 as isize);
+        self.popup
+            .draw(&self.menus[open].entries, formatting)
+            .into_iter()
+            .map(|(buffer, position)| (buffer, position.add(screen_position)))
+            .collect()
+    }
+    fn update_overlay(
+        &mut self,
+        info: &crate::ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        let Some(open) = self.open else {
+            return crate::GuiOutput::empty();
+        };
+        let formatting = get_formatting();
+        let local = info.mouse_pos.map(|pos| pos.sub(self.screen_position));
+        let previous = self.popup.clone();
+        let (event, cursor_style) = self.popup.update(
+            &mut self.menus[open].entries,
+            local,
+            info,
+            &formatting,
+        );
+        if previous != self.popup {
+            self.needs_redraw.set(true);
+        }
+        let output = crate::GuiOutput::default(FocusTaken::FunctionallyTaken)
+            .with_optional_cursor(cursor_style);
+        match event {
+            PopupEvent::None => crate::GuiOutput::empty(),
+            PopupEvent::Hovered => output,
+            PopupEvent::Selected(id) => {
+                self.close();
+                output.with_selected_menu_item(Some(id))
+            }
+            PopupEvent::Dismissed => {
+                let over_bar = !info.pressed_keys.contains(&KeyCode::Escape)
+                    && local.is_some_and(|pos| {
+                        (0.0..self.height as f32).contains(&pos.1)
+                            && self.get_menu_at(pos.0, &formatting).is_some()
+                    });
+                // The bar itself toggles the menus
+                if over_bar {
+                    return crate::GuiOutput::empty();
+                }
+                self.close();
+                output
+            }
+        }
+    }
+}
+
+/// A trait to get/set values using the path instead of manually getting the module
+pub trait MenuBarModulePathSupport {
+    /// Check if the item with the given id is checked, is None when the item doesn't exist or isn't checkable
+    fn is_checked(&self, id: &str) -> Option<bool>;
+    /// Set the checked state of the item with the given id
+    fn set_checked(&self, id: &str, checked: bool);
+    /// Enable or disable the item with the given id
+    fn set_enabled(&self, id: &str, enabled: bool);
+    /// Close all opened menus
+    fn close(&self);
+}
+
+impl MenuBarModulePathSupport for ModulePath<MenuBar> {
+    fn is_checked(&self, id: &str) -> Option<bool> {
+        crate::module_manager::get_module_as_mut::<_, Option<bool>>(
+            self,
+            |menu_bar| menu_bar.find_item_mut(id)?.checked,
+        )
+        .unwrap_or_default()
+    }
+    fn set_checked(&self, id: &str, checked: bool) {
+        let _ = crate::module_manager::get_module_as_mut::<_, ()>(
+            self,
+            |menu_bar| {
+                if let Some(item) = menu_bar.find_item_mut(id) {
+                    item.checked = Some(checked);
+                }
+            },
+        );
+    }
+    fn set_enabled(&self, id: &str, enabled: bool) {
+        let _ = crate::module_manager::get_module_as_mut::<_, ()>(
+            self,
+            |menu_bar| {
+                if let Some(item) = menu_bar.find_item_mut(id) {
+                    item.enabled = enabled;
+                }
+            },
+        );
+    }
+    fn close(&self) {
+        let _ = crate::module_manager::get_module_as_mut::<_, ()>(
+            self,
+            MenuBar::close,
+        );
+    }
+}
@@ -20,20 +20,20 @@ pub fn adjust_progress_by_mouse<
 
 /// A simple function to draw a diagonal cross
 #[must_use]
-pub fn draw_cross(size: usize, thickness: isize) -> Buffer {
+pub fn draw_cross(size: usize, thickness: isize, color: u32) -> Buffer {
     let mut buffer = Buffer::new_empty((size, size));
     render::draw_line::<true>(
         &mut buffer,
         (0, 0),
         (size, size),
-        mirl::graphics::colors::WHITE,
+        color,
         thickness,
     );
     render::draw_line::<true>(
         &mut buffer,
         (0, size),
         (size, 0),
-        mirl::graphics::colors::WHITE,
+        color,
         thickness,
     );
 
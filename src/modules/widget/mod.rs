@@ -10,6 +10,8 @@ pub mod image;
 pub mod image_button;
 /// A lever you can vertically drag that can be either on or off
 pub mod lever;
+/// A menu bar with nested dropdown menus
+pub mod menu_bar;
 /// Display numbers in a seven segment display style
 pub mod number_display;
 /// A number input field
@@ -29,6 +31,8 @@ pub mod text;
 ///
 /// This is currently in a very basic state and will be subject for improvement later
 pub mod text_input;
+/// Labelled number inputs in a row for editing vectors and colors
+pub mod vector_input;
 
 pub use button::Button;
 pub use check_box::CheckBox;
@@ -36,15 +40,21 @@ pub use crank::Crank;
 pub use image::Image;
 pub use image_button::ImageButton;
 pub use lever::Lever;
+pub use menu_bar::{
+    Menu, MenuBar, MenuEntry, MenuItem, PopupEvent, PopupMenu,
+};
 pub use number_display::NumberDisplay;
 pub use number_input::NumberInput;
 pub use progress_bar::ProgressBar;
 pub use selection::Selection;
-pub use sliders::Slider;
+pub use sliders::{RangeSlider, Slider, SliderMapping, SliderValue};
 #[cfg(feature = "experimental")]
-pub use struct_editor::StructEditor;
+pub use struct_editor::{FieldCallback, StructEditor};
 pub use text::TextDisplay;
 pub use text_input::TextInput;
+pub use vector_input::{
+    RgbaInput, Vec2Input, Vec3Input, Vec4Input, VectorInput,
+};
 
 /// Misc function the modules use
 pub mod misc;
@@ -57,12 +57,17 @@ impl<T: NumberDisplayNumberType> DearMirlGuiModule for NumberDisplay<T> {
         formatting: &crate::Formatting,
         _info: &crate::ModuleDrawInfo,
     ) -> (Buffer, InsertionMode) {
-        let text_color = mirl::graphics::colors::WHITE;
-        let mut buffer = Buffer::new_empty_with_color(
-            (
-                self.get_width(formatting) as usize,
-                self.get_height(formatting) as usize,
-            ),
+        let text_color = formatting.text_color;
+        let size = (
+            self.get_width(formatting) as usize,
+            self.get_height(formatting) as usize,
+        );
+        let mut buffer =
+            Buffer::new_empty_with_color(size, formatting.background_color);
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (size.0 as isize, size.1 as isize),
             formatting.foreground_color,
         );
         let text_height = self.height - (formatting.vertical_margin * 2) as f32;
@@ -1,5 +1,5 @@
 use mirl::{
-    extensions::*, graphics::rgb_to_u32, math::ConstZero, misc::keybinds::{KeyBind, sort_actions}, platform::{
+    extensions::*, math::ConstZero, misc::keybinds::{KeyBind, sort_actions}, platform::{
         CursorStyle,
         keycodes::{KeyCode, StringToKeyCodes},
     }, prelude::Buffer, render, text::position::TextPosition
@@ -1406,11 +1406,16 @@ impl<
         let caret_color = formatting.text_color;
         let caret_width = self.height / 10.0;
 
-        let highlight_color = rgb_to_u32(30, 20, 200);
+        let highlight_color = formatting.theme.selection_color;
 
         // Code
-        let mut buffer = Buffer::new_empty_with_color(
-            (self.width, self.get_height(formatting) as usize),
+        let size = (self.width, self.get_height(formatting) as usize);
+        let mut buffer =
+            Buffer::new_empty_with_color(size, formatting.background_color);
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (size.0 as isize, size.1 as isize),
             mirl::graphics::adjust_brightness_hsl_of_rgb(
                 background_color,
                 background_color_change,
@@ -1682,6 +1687,8 @@ impl<
             text_input_selected: self.selected == info.container_id, // TODO: IS THIS CORRECT?
             new_cursor_style: cursor_style,
             request_clipboard_data,
+            selected_menu_item: None,
+            window_events: Vec::new(),
         }
     }
     fn need_redraw(&mut self) -> bool {
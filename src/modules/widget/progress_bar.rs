@@ -2,7 +2,6 @@ use mirl::prelude::Buffer;
 use mirl::render;
 use mirl::{
     //extensions::*,
-    graphics::rgba_to_u32,
     math::interpolate,
 };
 
@@ -89,9 +88,14 @@ impl DearMirlGuiModule for ProgressBar {
                 original
             }
         }
-        let color_change = -10.0;
         let mut buffer = Buffer::new_empty_with_color(
             (self.width, self.height),
+            formatting.background_color,
+        );
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (self.width as isize, self.height as isize),
             formatting.foreground_color,
         );
 
@@ -121,10 +125,12 @@ impl DearMirlGuiModule for ProgressBar {
             &mut buffer,
             (0, y_pos),
             (width, height),
-            mirl::graphics::adjust_brightness_hsl_of_rgb(
-                formatting.foreground_color,
-                color_change,
-            ),
+            formatting.theme.accent_color,
+        );
+        formatting.theme.draw_border(
+            &mut buffer,
+            (0, 0),
+            (self.width as isize, self.height as isize),
         );
         let worst_case_text = "100.00%";
         let max_text_width = mirl::render::get_text_width(
@@ -152,7 +158,7 @@ impl DearMirlGuiModule for ProgressBar {
             &mut buffer,
             &text,
             (coord, 0),
-            rgba_to_u32(255, 255, 255, 255),
+            formatting.text_color,
             self.height as f32 * scaling,
             &formatting.font,
             draw_or_invert,
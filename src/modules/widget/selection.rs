@@ -9,8 +9,8 @@ pub struct Selection {
     pub text: Vec<String>,
     #[allow(missing_docs)]
     pub height: usize,
-    /// The color used
-    pub color: u32,
+    /// The color of the text, None = the text color of the formatting
+    pub color: Option<u32>,
     #[allow(missing_docs)]
     pub needs_redraw: std::cell::Cell<bool>,
     /// What buttons are currently selected, can't state more than the obvious with this one
@@ -35,7 +35,7 @@ impl Selection {
         Self {
             text: text.to_vec(),
             height,
-            color: mirl::graphics::colors::WHITE,
+            color: None,
             needs_redraw: std::cell::Cell::new(true),
             currently_selected: initial_states
                 .unwrap_or_else(|| false.repeat_value(text.len())),
@@ -96,7 +96,7 @@ impl DearMirlGuiModule for Selection {
     ) -> (Buffer, InsertionMode) {
         // Button alignment
         let margin_divider = 5;
-        let inner_button_color = mirl::graphics::colors::WHITE;
+        let inner_button_color = formatting.theme.accent_color;
 
         let mut buffer = Buffer::new_empty((self.width, self.total_height));
         let mut offset = 0;
@@ -121,7 +121,7 @@ impl DearMirlGuiModule for Selection {
                     );
                 }
             } else {
-                render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                formatting.theme.draw_rectangle(
                     &mut buffer,
                     (0, offset as isize),
                     (self.height as isize, self.height as isize),
@@ -140,7 +140,7 @@ impl DearMirlGuiModule for Selection {
                 &mut buffer,
                 i,
                 (self.height + formatting.horizontal_margin, offset),
-                self.color,
+                self.color.unwrap_or(formatting.text_color),
                 self.height as f32,
                 &formatting.font,
             );
@@ -173,6 +173,7 @@ impl DearMirlGuiModule for Selection {
             //     println!(">{mouse_pos:?}");
             // }
             for (idx, _) in self.text.iter().enumerate() {
+                #[allow(clippy::overly_complex_bool_expr)] // Disabled until the circle collision works
                 let collides = if self.radio_buttons && false {
                     // TODO: THIS IS CAUSING PROBLEMS. It could be because of how the Circle has been split into a pos and a shape
                     mirl::math::geometry::Pos2D::<
@@ -224,6 +225,8 @@ impl DearMirlGuiModule for Selection {
             new_clipboard_data: None,
             text_input_selected: false,
             request_clipboard_data: false,
+            selected_menu_item: None,
+            window_events: Vec::new(),
         }
     }
 
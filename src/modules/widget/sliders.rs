@@ -1,5 +1,5 @@
 use mirl::{
-    extensions::*, graphics::rgba_to_u32, math::{Bounded, ConstOne, ConstZero}, platform::CursorStyle, prelude::Buffer, render
+    extensions::*, math::{Bounded, ConstOne, ConstZero}, platform::{CursorStyle, keycodes::KeyCode}, prelude::Buffer, render
 };
 
 use crate::{
@@ -20,6 +20,216 @@ use crate::{
 //     Value(LimitType),
 // }
 
+/// How the progress of a [Slider] is mapped onto its range
+#[derive(Debug, Clone, Copy, Eq, Default)]
+pub enum SliderMapping {
+    #[default]
+    /// Progress is mapped linearly onto the range
+    Linear,
+    /// Progress is mapped exponentially onto the range so each equal distance multiplies the value by the same amount (Audio gains, zoom levels, ...)
+    ///
+    /// Falls back to [`SliderMapping::Linear`] when the range isn't fully above 0
+    Logarithmic,
+    /// A custom curve, both functions take and return values from 0.0 to 1.0
+    Custom {
+        /// Progress -> Normalized value
+        to_value: fn(f64) -> f64,
+        /// Normalized value -> Progress, the inverse of `to_value`
+        to_progress: fn(f64) -> f64,
+    },
+}
+// Function pointers are compared and hashed by address, spelled out so it's visible
+impl PartialEq for SliderMapping {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Linear, Self::Linear)
+            | (Self::Logarithmic, Self::Logarithmic) => true,
+            (
+                Self::Custom { to_value, to_progress },
+                Self::Custom {
+                    to_value: other_value,
+                    to_progress: other_progress,
+                },
+            ) => {
+                core::ptr::fn_addr_eq(*to_value, *other_value)
+                    && core::ptr::fn_addr_eq(*to_progress, *other_progress)
+            }
+            _ => false,
+        }
+    }
+}
+impl core::hash::Hash for SliderMapping {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        if let Self::Custom { to_value, to_progress } = self {
+            (*to_value as usize).hash(state);
+            (*to_progress as usize).hash(state);
+        }
+    }
+}
+impl SliderMapping {
+    #[must_use]
+    /// Map a progress from 0.0 to 1.0 onto the given range
+    ///
+    /// Integers are interpolated without going through [f64] for [`SliderMapping::Linear`] so the ends of large ranges (`i64::MIN..i64::MAX`) stay reachable
+    pub fn progress_to_value<T: SliderValue>(
+        &self,
+        progress: f64,
+        min: T,
+        max: T,
+    ) -> T {
+        let (min_f64, max_f64) = (min.to_f64(), max.to_f64());
+        match self {
+            Self::Logarithmic if min_f64 > 0.0 && max_f64 > 0.0 => {
+                T::from_f64(min_f64 * (max_f64 / min_f64).powf(progress))
+            }
+            Self::Custom { to_value, .. } => {
+                T::lerp(to_value(progress), min, max)
+            }
+            _ => T::lerp(progress, min, max),
+        }
+    }
+    #[must_use]
+    /// Map a value within the given range back to a progress from 0.0 to 1.0
+    pub fn value_to_progress<T: SliderValue>(
+        &self,
+        value: T,
+        min: T,
+        max: T,
+    ) -> f64 {
+        if max == min {
+            return 0.0;
+        }
+        let (value_f64, min_f64, max_f64) =
+            (value.to_f64(), min.to_f64(), max.to_f64());
+        match self {
+            Self::Logarithmic
+                if min_f64 > 0.0 && max_f64 > 0.0 && value_f64 > 0.0 =>
+            {
+                (value_f64 / min_f64).log(max_f64 / min_f64)
+            }
+            Self::Custom { to_progress, .. } => {
+                to_progress(T::inverse_lerp(value, min, max))
+            }
+            _ => T::inverse_lerp(value, min, max),
+        }
+    }
+}
+
+/// A number a [Slider] can map its progress onto
+pub trait SliderValue: Copy + PartialOrd {
+    #[must_use]
+    /// Interpolate between min and max, progress goes from 0.0 to 1.0
+    fn lerp(progress: f64, min: Self, max: Self) -> Self;
+    #[must_use]
+    /// The inverse of [`SliderValue::lerp`], returns 0.0 when min and max are the same
+    fn inverse_lerp(value: Self, min: Self, max: Self) -> f64;
+    #[must_use]
+    /// Snap the value onto the closest multiple of step starting at min, does nothing when step isn't above 0
+    fn snap(self, min: Self, step: Self) -> Self;
+    #[must_use]
+    /// Convert the value to an [f64], large integers may lose precision
+    fn to_f64(self) -> f64;
+    #[must_use]
+    /// Convert an [f64] to the closest value, saturating at the bounds of the type
+    fn from_f64(value: f64) -> Self;
+}
+macro_rules! impl_slider_value_int {
+    ($($t:ty => $unsigned:ty),*) => {$(
+        // `as` since the conversions differ per type
+        #[allow(clippy::cast_lossless)]
+        impl SliderValue for $t {
+            fn lerp(progress: f64, min: Self, max: Self) -> Self {
+                if max < min {
+                    return Self::lerp(1.0 - progress, max, min);
+                }
+                // The distance always fits into the unsigned type of the same size
+                let span = max.wrapping_sub(min) as $unsigned;
+                let offset =
+                    (span as f64 * progress.clamp(0.0, 1.0)).round() as $unsigned;
+                min.wrapping_add(offset.min(span) as Self)
+            }
+            fn inverse_lerp(value: Self, min: Self, max: Self) -> f64 {
+                if max < min {
+                    return 1.0 - Self::inverse_lerp(value, max, min);
+                }
+                if max == min {
+                    return 0.0;
+                }
+                let span = max.wrapping_sub(min) as $unsigned;
+                let offset = value.clamp(min, max).wrapping_sub(min) as $unsigned;
+                offset as f64 / span as f64
+            }
+            fn snap(self, min: Self, step: Self) -> Self {
+                if step <= 0 as Self || self < min {
+                    return self;
+                }
+                let (offset, step) =
+                    (self.wrapping_sub(min) as $unsigned, step as $unsigned);
+                let down = offset - offset % step;
+                // Rounding up must not go past the end of the type
+                let room = Self::MAX.wrapping_sub(min) as $unsigned;
+                let snapped = match down.checked_add(step) {
+                    Some(up)
+                        if up <= room && offset - down >= step - step / 2 =>
+                    {
+                        up
+                    }
+                    _ => down,
+                };
+                min.wrapping_add(snapped as Self)
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn from_f64(value: f64) -> Self {
+                value.round() as Self
+            }
+        }
+    )*};
+}
+impl_slider_value_int!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+macro_rules! impl_slider_value_float {
+    ($($t:ty),*) => {$(
+        impl SliderValue for $t {
+            fn lerp(progress: f64, min: Self, max: Self) -> Self {
+                lerp(progress, min.into(), max.into()) as Self
+            }
+            fn inverse_lerp(value: Self, min: Self, max: Self) -> f64 {
+                if max.to_bits() == min.to_bits() {
+                    return 0.0;
+                }
+                inverse_lerp(value.into(), min.into(), max.into())
+            }
+            fn snap(self, min: Self, step: Self) -> Self {
+                if step > 0.0 {
+                    step.mul_add(((self - min) / step).round(), min)
+                } else {
+                    self
+                }
+            }
+            fn to_f64(self) -> f64 {
+                self.into()
+            }
+            fn from_f64(value: f64) -> Self {
+                value as Self
+            }
+        }
+    )*};
+}
+impl_slider_value_float!(f32, f64);
+/// Interpolate between min and max without overflowing when the range spans more than the type can hold (`f64::MIN..f64::MAX`)
+fn lerp(progress: f64, min: f64, max: f64) -> f64 {
+    max.mul_add(progress, min * (1.0 - progress))
+}
+/// The inverse of [lerp], halving first so the size of the range can't overflow
+fn inverse_lerp(value: f64, min: f64, max: f64) -> f64 {
+    (value / 2.0 - min / 2.0) / (max / 2.0 - min / 2.0)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
 /// A slider/progress module
 pub struct Slider<ProgressType, LimitType: mirl::math::NumberWithMonotoneOps> {
@@ -41,6 +251,30 @@ pub struct Slider<ProgressType, LimitType: mirl::math::NumberWithMonotoneOps> {
     pub needs_redraw: bool,
     /// The range it is limited to
     pub range: std::ops::Range<LimitType>,
+    /// How the progress is mapped onto the range
+    pub mapping: SliderMapping,
+    /// If set, the value snaps to multiples of this step (Starting at the start of the range)
+    pub step: Option<LimitType>,
+    /// How many tick marks are drawn under the track, 0 for none
+    pub ticks: usize,
+    /// If the value of each tick mark should be written under it
+    pub tick_labels: bool,
+    /// If the current value should be drawn on the handle instead of the progress in the center
+    pub value_label: bool,
+    /// How many decimal places the tick and value labels show
+    pub label_precision: usize,
+    /// The unsnapped progress while dragging so small mouse movements still add up to a step
+    pub drag_progress: ProgressType,
+    /// Draw the slider vertically with 0.0 at the bottom, `width` and `height` then still describe the length and thickness of the track
+    pub vertical: bool,
+    /// The container id the slider has been selected in, used for keyboard control
+    pub selected: usize,
+    /// Used for detecting new key strokes
+    pub last_keys_pressed: Vec<KeyCode>,
+    /// How far the arrow keys move the progress when no step is set
+    pub keyboard_step: ProgressType,
+    /// How far PageUp/PageDown move the progress when no step is set
+    pub keyboard_page_step: ProgressType,
 }
 impl<
     ProgressType: TryFromPatch<LimitType>
@@ -86,6 +320,18 @@ impl<
             range: range.unwrap_or_else(|| {
                 LimitType::min_value()..LimitType::max_value()
             }),
+            mapping: SliderMapping::Linear,
+            step: None,
+            ticks: 0,
+            tick_labels: false,
+            value_label: false,
+            label_precision: 2,
+            drag_progress: progress.unwrap_or(eps),
+            vertical: false,
+            selected: 0,
+            last_keys_pressed: Vec::new(),
+            keyboard_step: ProgressType::try_from_value(0.01)?,
+            keyboard_page_step: ProgressType::try_from_value(0.1)?,
         })
     }
     #[must_use]
@@ -112,37 +358,585 @@ impl<
         self.slider_width = width;
         self
     }
+    #[must_use]
+    /// Set how the progress is mapped onto the range
+    pub const fn with_mapping(mut self, mapping: SliderMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+    #[must_use]
+    /// Snap the value to multiples of the given step
+    pub const fn with_step(mut self, step: LimitType) -> Self {
+        self.step = Some(step);
+        self
+    }
+    #[must_use]
+    /// Draw tick marks under the track, optionally labelled with their value
+    pub const fn with_ticks(mut self, ticks: usize, labels: bool) -> Self {
+        self.ticks = ticks;
+        self.tick_labels = labels;
+        self
+    }
+    #[must_use]
+    /// Draw the current value on the handle
+    pub const fn with_value_label(mut self, value_label: bool) -> Self {
+        self.value_label = value_label;
+        self
+    }
+    #[must_use]
+    /// Set how many decimal places the tick and value labels show
+    pub const fn with_label_precision(mut self, label_precision: usize) -> Self {
+        self.label_precision = label_precision;
+        self
+    }
+    #[must_use]
+    /// Draw the slider vertically with 0.0 at the bottom
+    pub const fn with_vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+    #[must_use]
+    /// Set how far the arrow keys and PageUp/PageDown move the progress when no step is set
+    pub const fn with_keyboard_steps(
+        mut self,
+        keyboard_step: ProgressType,
+        keyboard_page_step: ProgressType,
+    ) -> Self {
+        self.keyboard_step = keyboard_step;
+        self.keyboard_page_step = keyboard_page_step;
+        self
+    }
 }
 
-const impl<
-    ProgressType: [const] TryFromPatch<LimitType>
-        + [const] TryFromPatch<f64>
-        + [const] mirl::extensions::Round
-        + [const] core::ops::Mul<Output = ProgressType>
-        + [const] core::ops::Div<Output = ProgressType>
-        + Copy,
-    LimitType: [const] mirl::math::NumberWithMonotoneOps
-        + [const] Bounded
-        + [const] TryFromPatch<ProgressType>
-        + Copy
-        + [const] core::ops::Mul<Output = LimitType>
-        + [const] core::ops::Div<Output = LimitType>
-        + [const] mirl::extensions::Round,
+impl<
+    ProgressType: TryFromPatch<f64> + Copy,
+    LimitType: mirl::math::NumberWithMonotoneOps + SliderValue,
 > Slider<ProgressType, LimitType>
+where
+    f64: TryFromPatch<ProgressType>,
 {
-    #[allow(clippy::missing_const_for_fn)] // Mate, it's already const
-    /// Based on the given range, get the value associated
+    /// Get the value a progress would result in, including mapping and snapping
+    #[must_use]
+    pub fn progress_to_value(
+        &self,
+        progress: ProgressType,
+    ) -> Option<LimitType> {
+        let (min, max) = (self.range.start, self.range.end);
+        let value = self.mapping.progress_to_value(
+            f64::try_from_value(progress)?,
+            min,
+            max,
+        );
+        Some(self.snap_value(value))
+    }
+    /// Get the progress a value would result in, including mapping and snapping
+    #[must_use]
+    pub fn value_to_progress(&self, value: LimitType) -> Option<ProgressType> {
+        let (min, max) = (self.range.start, self.range.end);
+        ProgressType::try_from_value(
+            self.mapping
+                .value_to_progress(self.snap_value(value), min, max)
+                .clamp(0.0, 1.0),
+        )
+    }
+    /// Snap a value to the closest step and keep it within the range
+    fn snap_value(&self, value: LimitType) -> LimitType {
+        let (min, max) = (self.range.start, self.range.end);
+        let (low, high) = if max < min { (max, min) } else { (min, max) };
+        let value = self.step.map_or(value, |step| value.snap(low, step));
+        if value < low {
+            low
+        } else if value > high {
+            high
+        } else {
+            value
+        }
+    }
+    /// Based on the given range and mapping, get the value associated
+    #[must_use]
     pub fn get_value(&self) -> Option<LimitType> {
-        self.range.get_value_from_percent(self.progress)
+        self.progress_to_value(self.progress)
     }
-    #[allow(clippy::missing_const_for_fn)] // Mate, it's already const as well
-    /// Based on the given range, get the value associated. Returns None when it fails
+    /// Based on the given range and mapping, set the progress to represent the given value. Returns None when it fails
     pub fn set_value(&mut self, value: LimitType) -> Option<()> {
-        self.progress = self.range.get_percent_from_value(value)?;
+        self.progress = self.value_to_progress(value)?;
+        self.drag_progress = self.progress;
+        self.needs_redraw = true;
         Some(())
     }
 }
 
+impl<
+    ProgressType: TryFromPatch<f64> + Copy,
+    LimitType: mirl::math::NumberWithMonotoneOps + TryFromPatch<f64> + Copy,
+> Slider<ProgressType, LimitType>
+where
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
+{
+    fn get_range_f64(&self) -> Option<(f64, f64)> {
+        Some((
+            f64::try_from_value(self.range.start)?,
+            f64::try_from_value(self.range.end)?,
+        ))
+    }
+    /// Snap a value to the closest step and keep it within the range
+    fn snap_value_f64(&self, value: f64) -> Option<f64> {
+        let (min, max) = self.get_range_f64()?;
+        let value = match self.step.and_then(f64::try_from_value) {
+            Some(step) if step > 0.0 => {
+                step.mul_add(((value - min) / step).round(), min)
+            }
+            _ => value,
+        };
+        Some(value.clamp(min.min(max), min.max(max)))
+    }
+    /// Get the value a progress would result in, including mapping and snapping
+    #[must_use]
+    pub fn progress_to_value_f64(&self, progress: ProgressType) -> Option<f64> {
+        let (min, max) = self.get_range_f64()?;
+        self.snap_value_f64(self.mapping.progress_to_value(
+            f64::try_from_value(progress)?,
+            min,
+            max,
+        ))
+    }
+    /// Get the progress a value would result in, including mapping and snapping
+    #[must_use]
+    pub fn value_to_progress_f64(&self, value: f64) -> Option<ProgressType> {
+        let (min, max) = self.get_range_f64()?;
+        let value = self.snap_value_f64(value)?;
+        ProgressType::try_from_value(
+            self.mapping.value_to_progress(value, min, max).clamp(0.0, 1.0),
+        )
+    }
+    /// Move the given progress onto the closest step, does nothing when no step is set
+    #[must_use]
+    pub fn snap_progress(&self, progress: ProgressType) -> ProgressType {
+        if self.step.is_none() {
+            return progress;
+        }
+        self.progress_to_value_f64(progress)
+            .and_then(|value| self.value_to_progress_f64(value))
+            .unwrap_or(progress)
+    }
+    /// Move the given progress by a number of steps, returns None when no step is set
+    #[must_use]
+    pub fn step_progress(
+        &self,
+        progress: ProgressType,
+        steps: f64,
+    ) -> Option<ProgressType> {
+        let step = f64::try_from_value(self.step?)?;
+        let value = self.progress_to_value_f64(progress)?;
+        self.value_to_progress_f64(step.mul_add(steps, value))
+    }
+    /// Get the text for a label of the given value
+    fn format_value(&self, value: f64) -> String {
+        format!("{value:.*}", self.label_precision)
+    }
+    /// The size of the area next to the track containing tick marks and their labels
+    ///
+    /// This is below the track when horizontal and to the right when vertical
+    #[must_use]
+    pub fn get_tick_area_size(&self, formatting: &crate::Formatting) -> usize {
+        if self.ticks == 0 {
+            return 0;
+        }
+        let mut size = self.height / 4;
+        if self.tick_labels {
+            size += if self.vertical {
+                (0..self.ticks)
+                    .filter_map(|tick| self.get_tick_value(tick))
+                    .map(|value| {
+                        render::get_text_width(
+                            &self.format_value(value),
+                            self.height as f32 / 2.0,
+                            &formatting.font,
+                        ) as usize
+                    })
+                    .max()
+                    .unwrap_or_default()
+            } else {
+                self.height / 2
+            };
+        }
+        size
+    }
+    /// Get the progress at which a tick mark is drawn
+    #[must_use]
+    pub fn get_tick_progress(&self, tick: usize) -> f64 {
+        if self.ticks <= 1 {
+            0.5
+        } else {
+            tick as f64 / (self.ticks - 1) as f64
+        }
+    }
+    fn get_tick_value(&self, tick: usize) -> Option<f64> {
+        ProgressType::try_from_value(self.get_tick_progress(tick))
+            .and_then(|progress| self.progress_to_value_f64(progress))
+    }
+    /// The (width, height) of the module after considering the orientation
+    #[must_use]
+    pub fn get_oriented_size(
+        &self,
+        formatting: &crate::Formatting,
+    ) -> (usize, usize) {
+        let thickness = self.height + self.get_tick_area_size(formatting);
+        if self.vertical {
+            (thickness, self.width)
+        } else {
+            (self.width, thickness)
+        }
+    }
+    /// Convert a position from module space into track space where the track always goes from left to right
+    #[must_use]
+    pub fn to_track_space(&self, position: (f32, f32)) -> (f32, f32) {
+        if self.vertical {
+            (self.width as f32 - position.1, position.0)
+        } else {
+            position
+        }
+    }
+    /// Convert the mouse position and delta of the given info into track space
+    #[must_use]
+    pub fn localize_info<'a>(
+        &self,
+        info: &crate::ModuleUpdateInfo<'a>,
+    ) -> crate::ModuleUpdateInfo<'a> {
+        let mut info = *info;
+        if self.vertical {
+            info.mouse_pos = info.mouse_pos.map(|pos| self.to_track_space(pos));
+            info.mouse_pos_delta =
+                (-info.mouse_pos_delta.1, info.mouse_pos_delta.0);
+        }
+        info
+    }
+}
+
+/// Drawing shared between [Slider] and [RangeSlider]
+impl<
+    ProgressType: std::fmt::Debug
+        + ConstZero
+        + ConstOne
+        + 'static
+        + mirl::extensions::TryFromPatch<usize>
+        + Copy
+        + core::ops::Add<Output = ProgressType>
+        + core::ops::Sub<Output = ProgressType>
+        + core::ops::Mul<Output = ProgressType>
+        + TryFromPatch<f64>,
+    LimitType: mirl::math::NumberWithMonotoneOps + TryFromPatch<f64> + Copy,
+> Slider<ProgressType, LimitType>
+where
+    isize: mirl::extensions::TryFromPatch<ProgressType>,
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
+{
+    /// Get the horizontal start of a handle at the given progress
+    ///
+    /// # Errors
+    /// When the progress or size cannot be converted
+    pub fn get_handle_position(
+        &self,
+        progress: ProgressType,
+    ) -> Result<isize, String> {
+        let Some(pos_end) = (self.width - self.slider_width).try_into_value()
+        else {
+            return Err(format!(
+                "Unable to convert from usize to {} (Value: {})",
+                progress.what_am_i(),
+                (self.width - self.slider_width)
+            ));
+        };
+
+        let position =
+            mirl::math::interpolate(ProgressType::ZERO, pos_end, progress);
+
+        isize::try_from_value(position).ok_or_else(|| {
+            format!(
+                "Unable to convert from {} to isize (Value: {:?})",
+                progress.what_am_i(),
+                position
+            )
+        })
+    }
+    /// Draw the background of the track including the tick marks and their labels
+    ///
+    /// The track is always drawn horizontally, use [`orient`](Self::orient) once all handles have been drawn
+    #[must_use]
+    pub fn draw_track(&self, formatting: &crate::Formatting) -> Buffer {
+        let mut buffer = Buffer::new_empty((
+            self.width,
+            self.height + self.get_tick_area_size(formatting),
+        ));
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (self.width as isize, self.height as isize),
+            formatting.foreground_color,
+        );
+
+        let travel = (self.width - self.slider_width) as f32;
+        let half_handle = self.slider_width as f32 / 2.0;
+
+        // Tick marks and their labels
+        let tick_height = self.height / 4;
+        let label_size = self.height as f32 / 2.0;
+        for tick in 0..self.ticks {
+            let x = (self.get_tick_progress(tick) as f32)
+                .mul_add(travel, half_handle);
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                (x as isize, self.height as isize),
+                (1, tick_height as isize),
+                formatting.text_color,
+            );
+            // Vertical labels are drawn in orient as text cannot be rotated
+            if !self.tick_labels || self.vertical {
+                continue;
+            }
+            let Some(value) = self.get_tick_value(tick) else {
+                continue;
+            };
+            let text = self.format_value(value);
+            let text_width =
+                render::get_text_width(&text, label_size, &formatting.font);
+            render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                &text,
+                (
+                    (x - text_width / 2.0)
+                        .clamp(0.0, (self.width as f32 - text_width).max(0.0))
+                        as isize,
+                    (self.height + tick_height) as isize,
+                ),
+                formatting.text_color,
+                label_size,
+                &formatting.font,
+            );
+        }
+        buffer
+    }
+    /// Draw a handle starting at the given horizontal position
+    pub fn draw_handle(&self, buffer: &mut Buffer, position: isize, color: u32) {
+        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+            buffer,
+            (position, 0),
+            (self.slider_width as isize, self.height as isize),
+            color,
+        );
+    }
+    /// Rotate a buffer drawn by [`draw_track`](Self::draw_track) into the orientation of the slider and add what couldn't be drawn before rotating
+    #[must_use]
+    pub fn orient(&self, buffer: Buffer, formatting: &crate::Formatting) -> Buffer {
+        if !self.vertical {
+            return buffer;
+        }
+        let mut buffer = buffer.rotate_90();
+        if self.tick_labels {
+            let travel = (self.width - self.slider_width) as f32;
+            let half_handle = self.slider_width as f32 / 2.0;
+            let label_size = self.height as f32 / 2.0;
+            for tick in 0..self.ticks {
+                let Some(value) = self.get_tick_value(tick) else {
+                    continue;
+                };
+                let y = self.width as f32
+                    - (self.get_tick_progress(tick) as f32)
+                        .mul_add(travel, half_handle);
+                render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                    &mut buffer,
+                    &self.format_value(value),
+                    (
+                        (self.height + self.height / 4) as isize,
+                        (y - label_size / 2.0)
+                            .clamp(0.0, (self.width as f32 - label_size).max(0.0))
+                            as isize,
+                    ),
+                    formatting.text_color,
+                    label_size,
+                    &formatting.font,
+                );
+            }
+        }
+        buffer
+    }
+    /// Draw the value of the given progress centered on the handle starting at the given horizontal position
+    pub fn draw_value_label(
+        &self,
+        buffer: &mut Buffer,
+        formatting: &crate::Formatting,
+        progress: ProgressType,
+        position: isize,
+    ) {
+        let text = self
+            .progress_to_value_f64(progress)
+            .map(|value| self.format_value(value))
+            .unwrap_or_default();
+        let center = position as f32 + self.slider_width as f32 / 2.0;
+        if self.vertical {
+            // Expects an already oriented buffer, the text is shrunk to fit the thickness of the track
+            let full_width = mirl::render::get_text_width(
+                &text,
+                self.height as f32,
+                &formatting.font,
+            );
+            let size = if full_width > self.height as f32 {
+                self.height as f32 * self.height as f32 / full_width
+            } else {
+                self.height as f32
+            };
+            let text_width =
+                mirl::render::get_text_width(&text, size, &formatting.font);
+            let y = (self.width as f32 - center - size / 2.0)
+                .clamp(0.0, (self.width as f32 - size).max(0.0));
+            render::draw_text_antialiased_execute_at::<false>(
+                buffer,
+                &text,
+                (
+                    ((self.height as f32 - text_width) / 2.0).max(0.0) as usize,
+                    y as usize,
+                ),
+                formatting.text_color,
+                size,
+                &formatting.font,
+                draw_or_invert,
+            );
+            return;
+        }
+        let text_width = mirl::render::get_text_width(
+            &text,
+            self.height as f32,
+            &formatting.font,
+        );
+        let x = (center - text_width / 2.0)
+            .clamp(0.0, (self.width as f32 - text_width).max(0.0));
+        render::draw_text_antialiased_execute_at::<false>(
+            buffer,
+            &text,
+            (x as usize, 0),
+            formatting.text_color,
+            self.height as f32,
+            &formatting.font,
+            draw_or_invert,
+        );
+    }
+    /// The color of a handle, selected sliders get a handle in the accent color of the theme
+    #[must_use]
+    pub const fn get_handle_color(
+        &self,
+        container_id: usize,
+        formatting: &crate::Formatting,
+    ) -> u32 {
+        if self.selected == container_id {
+            formatting.theme.accent_color
+        } else {
+            formatting.text_color
+        }
+    }
+}
+
+/// Keyboard control
+impl<
+    ProgressType: ConstOne
+        + ConstZero
+        + Copy
+        + core::ops::Add<Output = ProgressType>
+        + core::ops::Sub<Output = ProgressType>
+        + TryFromPatch<f64>
+        + core::cmp::PartialOrd
+        + mirl::extensions::Clamp,
+    LimitType: mirl::math::NumberWithMonotoneOps + TryFromPatch<f64> + Copy,
+> Slider<ProgressType, LimitType>
+where
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
+{
+    /// How many steps PageUp/PageDown move when a step is set
+    pub const PAGE_STEPS: f64 = 10.0;
+    /// Move the progress according to newly pressed keys, returns None when no key did anything
+    ///
+    /// - Left/Down                     -> Small step down
+    /// - Right/Up                      -> Small step up
+    /// - PageDown                      -> Large step down
+    /// - PageUp                        -> Large step up
+    /// - Home                          -> Start of the range
+    /// - End                           -> End of the range
+    pub fn handle_keyboard(
+        &mut self,
+        pressed_keys: &[KeyCode],
+    ) -> Option<ProgressType> {
+        self.handle_keyboard_from(self.progress, pressed_keys)
+    }
+    /// Like [`Slider::handle_keyboard`] but starting at the given progress instead of the current one
+    pub fn handle_keyboard_from(
+        &mut self,
+        mut progress: ProgressType,
+        pressed_keys: &[KeyCode],
+    ) -> Option<ProgressType> {
+        let new_keys: Vec<KeyCode> = self
+            .last_keys_pressed
+            .get_old_items(pressed_keys)
+            .iter()
+            .map(|x| **x)
+            .collect();
+        self.last_keys_pressed = pressed_keys.to_vec();
+
+        let mut changed = false;
+        for key in new_keys {
+            let (steps, fallback) = match key {
+                KeyCode::LeftArrow | KeyCode::DownArrow => {
+                    (-1.0, ProgressType::ZERO - self.keyboard_step)
+                }
+                KeyCode::RightArrow | KeyCode::UpArrow => {
+                    (1.0, self.keyboard_step)
+                }
+                KeyCode::PageDown => (
+                    -Self::PAGE_STEPS,
+                    ProgressType::ZERO - self.keyboard_page_step,
+                ),
+                KeyCode::PageUp => (Self::PAGE_STEPS, self.keyboard_page_step),
+                KeyCode::Home => {
+                    progress = self.snap_progress(ProgressType::ZERO);
+                    changed = true;
+                    continue;
+                }
+                KeyCode::End => {
+                    progress = self.snap_progress(ProgressType::ONE);
+                    changed = true;
+                    continue;
+                }
+                _ => continue,
+            };
+            changed = true;
+            progress = self.step_progress(progress, steps).unwrap_or_else(|| {
+                (progress + fallback).clamped(ProgressType::ZERO, ProgressType::ONE)
+            });
+        }
+        changed.then_some(progress)
+    }
+    /// Move the given progress by one scroll tick, one step when a step is set
+    fn scroll_progress(
+        &self,
+        progress: ProgressType,
+        direction: f32,
+    ) -> Option<ProgressType> {
+        let direction = f64::from(direction.sign());
+        self.step_progress(progress, direction).or_else(|| {
+            let delta = ProgressType::try_from_value(0.05 * direction)?;
+            Some((progress + delta).clamped(ProgressType::ZERO, ProgressType::ONE))
+        })
+    }
+}
+
+#[inline(always)]
+#[allow(clippy::inline_always)]
+const fn draw_or_invert(original: u32, under: u32) -> u32 {
+    if under == original {
+        mirl::graphics::invert_color(original)
+    } else {
+        original
+    }
+}
+
 impl<
     ProgressType: std::fmt::Debug
         + ConstOne
@@ -157,6 +951,7 @@ impl<
         + TryIntoPatch<f32>
         + TryFromPatch<f32>
         + core::ops::Div<Output = ProgressType>
+        + TryFromPatch<f64>
         + core::cmp::PartialOrd
         + mirl::extensions::Clamp,
     // + core::cmp::Ord,
@@ -164,104 +959,87 @@ impl<
         + ConstZero
         + std::fmt::Debug
         + std::marker::Send
+        + TryFromPatch<f64>
+        + Copy
         + 'static,
 > DearMirlGuiModule for Slider<ProgressType, LimitType>
 where
     isize: mirl::extensions::TryFromPatch<ProgressType>,
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
 {
     fn apply_new_formatting(&mut self, _formatting: &crate::Formatting) {}
     fn get_width(
         &mut self,
-        _formatting: &crate::Formatting,
+        formatting: &crate::Formatting,
     ) -> crate::DearMirlGuiCoordinateType {
-        self.width as crate::DearMirlGuiCoordinateType
+        self.get_oriented_size(formatting).0 as crate::DearMirlGuiCoordinateType
     }
     fn get_height(
         &mut self,
-        _formatting: &crate::Formatting,
+        formatting: &crate::Formatting,
     ) -> crate::DearMirlGuiCoordinateType {
-        self.height as crate::DearMirlGuiCoordinateType
+        self.get_oriented_size(formatting).1 as crate::DearMirlGuiCoordinateType
     }
     fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
         self.needs_redraw = super::misc::determine_need_redraw(need_redraw);
     }
-    #[allow(clippy::unwrap_used)] // It ain't gonna crash
-    #[allow(clippy::too_many_lines)] // Really? What.
     fn draw(
         &mut self,
         formatting: &crate::Formatting,
-        _info: &crate::ModuleDrawInfo,
+        info: &crate::ModuleDrawInfo,
     ) -> (Buffer, InsertionMode) {
-        #[inline(always)]
-        #[allow(clippy::inline_always)]
-        const fn draw_or_invert(original: u32, under: u32) -> u32 {
-            if under == original {
-                mirl::graphics::invert_color(original)
-            } else {
-                original
-            }
-        }
-        let mut buffer = Buffer::new_empty_with_color(
-            (self.width, self.height),
-            formatting.foreground_color,
-        );
-        let Some(pos_end) = (self.width - self.slider_width).try_into_value()
-        else {
-            return new_buffer_error(&format!(
-                "Unable to convert from usize to {} (Value: {})",
-                self.progress.what_am_i(),
-                (self.width - self.slider_width)
-            ));
+        let mut buffer = self.draw_track(formatting);
+        let draw_pos_start = match self.get_handle_position(self.progress) {
+            Ok(position) => position,
+            Err(error) => return new_buffer_error(&error),
         };
-
-        let position =
-            mirl::math::interpolate(ProgressType::ZERO, pos_end, self.progress);
-
-        let Some(draw_pos_start) = isize::try_from_value(position) else {
-            return new_buffer_error(&format!(
-                "Unable to convert from {} to isize (Value: {:?})",
-                self.progress.what_am_i(),
-                position
-            ));
-        };
-        render ::draw_rectangle::<{ crate::DRAW_SAFE }>(
+        self.draw_handle(
             &mut buffer,
-            (draw_pos_start, 0),
-            (self.slider_width as isize, self.height as isize),
-            rgba_to_u32(255, 255, 255, 255),
+            draw_pos_start,
+            self.get_handle_color(info.container_id, formatting),
         );
+        let mut buffer = self.orient(buffer, formatting);
 
-        let worst_case_text = "1.0000";
-        let max_text_width = mirl::render::get_text_width(
-            worst_case_text,
-            self.height as f32,
-            &formatting.font,
-        );
-        let desired_max_width = self.width as f32 / 2.0;
+        if self.value_label {
+            self.draw_value_label(
+                &mut buffer,
+                formatting,
+                self.progress,
+                draw_pos_start,
+            );
+        } else if !self.vertical {
+            let worst_case_text = "1.0000";
+            let max_text_width = mirl::render::get_text_width(
+                worst_case_text,
+                self.height as f32,
+                &formatting.font,
+            );
+            let desired_max_width = self.width as f32 / 2.0;
 
-        let scaling = if max_text_width > desired_max_width {
-            desired_max_width / max_text_width
-        } else {
-            1.0
-        };
-        let text = format!("{:.4?}", self.progress);
+            let scaling = if max_text_width > desired_max_width {
+                desired_max_width / max_text_width
+            } else {
+                1.0
+            };
+            let text = format!("{:.4?}", self.progress);
 
-        let actual_text_width = mirl::render::get_text_width(
-            &text,
-            self.height as f32 * scaling,
-            &formatting.font,
-        );
-        let coord = self.width / 2 - actual_text_width as usize / 2;
+            let actual_text_width = mirl::render::get_text_width(
+                &text,
+                self.height as f32 * scaling,
+                &formatting.font,
+            );
+            let coord = self.width / 2 - actual_text_width as usize / 2;
 
-        render::draw_text_antialiased_execute_at::<false>(
-            &mut buffer,
-            &text,
-            (coord, 0),
-            rgba_to_u32(255, 255, 255, 255),
-            self.height as f32 * scaling,
-            &formatting.font,
-            draw_or_invert,
-        );
+            render::draw_text_antialiased_execute_at::<false>(
+                &mut buffer,
+                &text,
+                (coord, 0),
+                formatting.text_color,
+                self.height as f32 * scaling,
+                &formatting.font,
+                draw_or_invert,
+            );
+        }
 
         (buffer, InsertionMode::ReplaceAll)
     }
@@ -270,9 +1048,22 @@ where
     fn update(&mut self, info: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
         // TODO: Put mouse dragging into its own function
 
+        if info.focus_taken == FocusTaken::FunctionallyTaken
+            && self.selected == info.container_id
+        {
+            self.selected = 0;
+            self.needs_redraw = true;
+        }
         if info.focus_taken.is_focus_taken() {
             return crate::GuiOutput::empty();
         }
+        // Everything below works as if the slider was horizontal
+        let info = &self.localize_info(info);
+        let drag_cursor = if self.vertical {
+            CursorStyle::ResizeVertically
+        } else {
+            CursorStyle::ResizeHorizontally
+        };
         let mut took_focus = if self.dragging == info.container_id {
             FocusTaken::FunctionallyTaken
         } else {
@@ -311,10 +1102,14 @@ where
             let mut new_progress = self.progress;
 
             if already_dragging || starting_to_drag {
+                if starting_to_drag {
+                    self.drag_progress = self.progress;
+                }
                 self.dragging = info.container_id;
-                cursor_style = Some(CursorStyle::ResizeHorizontally);
+                cursor_style = Some(drag_cursor);
+                // Dragging works on the unsnapped progress, otherwise movements smaller than a step would get lost
                 new_progress = super::misc::adjust_progress_by_mouse(
-                    self.progress,
+                    self.drag_progress,
                     ProgressType::try_from_value(info.mouse_pos_delta.0)
                         .unwrap(),
                     ProgressType::try_from_value(self.width).unwrap(),
@@ -337,7 +1132,25 @@ where
             )
             .does_area_contain_point(mouse_pos)
             {
-                new_progress = if info.mouse_scroll.0 != 0.0 {
+                if info.mouse_info.left.clicked && self.selected != info.container_id {
+                    self.selected = info.container_id;
+                    self.last_keys_pressed.clone_from(info.pressed_keys);
+                    self.needs_redraw = true;
+                }
+                let scroll = if info.mouse_scroll.0 == 0.0 {
+                    info.mouse_scroll.1
+                } else {
+                    info.mouse_scroll.0
+                };
+                new_progress = if scroll != 0.0
+                    && let Some(stepped) = self.step_progress(
+                        new_progress,
+                        f64::from(scroll.sign()),
+                    ) {
+                    // With steps, one scroll tick is one step
+                    took_focus = FocusTaken::FunctionallyTaken;
+                    stepped
+                } else if info.mouse_scroll.0 != 0.0 {
                     took_focus = FocusTaken::FunctionallyTaken;
                     new_progress
                         + ProgressType::try_from_value(
@@ -354,6 +1167,11 @@ where
                 } else {
                     new_progress
                 };
+            } else if info.mouse_info.left.clicked
+                && self.selected == info.container_id
+            {
+                self.selected = 0;
+                self.needs_redraw = true;
             }
             if self.wrap {
                 let eps = self.eps;
@@ -366,11 +1184,27 @@ where
                     p
                 };
             }
+            let new_progress =
+                new_progress.clamped(ProgressType::ZERO, ProgressType::ONE);
             if self.dragging > 0 {
                 self.needs_redraw = true;
+                self.drag_progress = new_progress;
             }
-            self.progress =
-                new_progress.clamped(ProgressType::ZERO, ProgressType::ONE);
+            let new_progress = self.snap_progress(new_progress);
+            if new_progress != self.progress {
+                self.needs_redraw = true;
+            }
+            self.progress = new_progress;
+        }
+        if self.selected == info.container_id
+            && let Some(new_progress) = self.handle_keyboard(info.pressed_keys)
+        {
+            took_focus = FocusTaken::FunctionallyTaken;
+            if new_progress != self.progress {
+                self.needs_redraw = true;
+            }
+            self.progress = new_progress;
+            self.drag_progress = new_progress;
         }
         crate::GuiOutput {
             new_cursor_style: cursor_style,
@@ -380,6 +1214,8 @@ where
             hide_cursor: false,
             text_input_selected: false,
             request_clipboard_data: false,
+            selected_menu_item: None,
+            window_events: Vec::new(),
         }
     }
     fn need_redraw(&mut self) -> bool {
@@ -396,3 +1232,691 @@ where
 pub fn calculate_slider_width(width: f32, height: f32) -> f32 {
     ((width + height).sqrt() * (width + height)) / (width + height)
 }
+
+/// What part of a [`RangeSlider`] is being dragged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum RangeSliderPart {
+    #[default]
+    /// The lower handle
+    Lower,
+    /// The upper handle
+    Upper,
+    /// The span between both handles, moving both at once
+    Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+/// A slider with two handles selecting a range within its range
+///
+/// The handles cannot cross and the span between them can be dragged to move both at once.
+/// Size, range, mapping, steps and tick marks are all taken from the underlying [Slider], the progress of which is used for the lower handle
+pub struct RangeSlider<ProgressType, LimitType: mirl::math::NumberWithMonotoneOps>
+{
+    /// The slider providing everything except the upper handle
+    pub base: Slider<ProgressType, LimitType>,
+    /// From 0.0 to 1.0, never below the progress of the base slider
+    pub upper_progress: ProgressType,
+    /// The unsnapped upper progress while dragging
+    pub upper_drag_progress: ProgressType,
+    /// What is being dragged, only valid while [`Slider::dragging`] of the base is set
+    pub dragged_part: RangeSliderPart,
+    /// What the keyboard and mouse wheel move while selected, the last part that was clicked
+    pub selected_part: RangeSliderPart,
+}
+impl<
+    ProgressType: TryFromPatch<LimitType>
+        + TryFromPatch<f64>
+        + mirl::extensions::Round
+        + core::ops::Mul<Output = ProgressType>
+        + core::ops::Div<Output = ProgressType>
+        + core::cmp::PartialOrd
+        + Copy,
+    LimitType: mirl::math::NumberWithMonotoneOps
+        + Bounded
+        + TryFromPatch<ProgressType>
+        + Copy
+        + core::ops::Mul<Output = LimitType>
+        + core::ops::Div<Output = LimitType>
+        + mirl::extensions::Round,
+> RangeSlider<ProgressType, LimitType>
+{
+    #[must_use]
+    #[allow(missing_docs)]
+    pub fn new(
+        progress: Option<(ProgressType, ProgressType)>,
+        range: Option<std::ops::Range<LimitType>>,
+    ) -> Option<Self> {
+        let base = Slider::new(progress.map(|x| x.0), false, range)?;
+        let upper = progress.map_or(ProgressType::try_from_value(1.0)?, |x| x.1);
+        Some(Self::from_slider(base, upper))
+    }
+}
+impl<ProgressType: core::cmp::PartialOrd + Copy, LimitType: mirl::math::NumberWithMonotoneOps>
+    RangeSlider<ProgressType, LimitType>
+{
+    /// Use an already configured slider as the base, the progress of the slider becomes the lower handle
+    #[must_use]
+    pub fn from_slider(
+        mut base: Slider<ProgressType, LimitType>,
+        upper_progress: ProgressType,
+    ) -> Self {
+        base.wrap = false;
+        let upper_progress = if upper_progress < base.progress {
+            base.progress
+        } else {
+            upper_progress
+        };
+        Self {
+            base,
+            upper_progress,
+            upper_drag_progress: upper_progress,
+            dragged_part: RangeSliderPart::Lower,
+            selected_part: RangeSliderPart::Lower,
+        }
+    }
+}
+impl<
+    ProgressType: TryFromPatch<f64> + core::cmp::PartialOrd + Copy,
+    LimitType: mirl::math::NumberWithMonotoneOps + SliderValue,
+> RangeSlider<ProgressType, LimitType>
+where
+    f64: TryFromPatch<ProgressType>,
+{
+    /// Based on the given range and mapping, get the selected range
+    #[must_use]
+    pub fn get_value(&self) -> Option<std::ops::Range<LimitType>> {
+        Some(
+            self.base.progress_to_value(self.base.progress)?
+                ..self.base.progress_to_value(self.upper_progress)?,
+        )
+    }
+    /// Based on the given range and mapping, move the handles to represent the given range. Returns None when it fails
+    ///
+    /// A reversed range is swapped
+    pub fn set_value(&mut self, value: std::ops::Range<LimitType>) -> Option<()> {
+        let start = self.base.value_to_progress(value.start)?;
+        let end = self.base.value_to_progress(value.end)?;
+        let (lower, upper) = if end < start { (end, start) } else { (start, end) };
+        self.base.progress = lower;
+        self.base.drag_progress = lower;
+        self.upper_progress = upper;
+        self.upper_drag_progress = upper;
+        self.base.needs_redraw = true;
+        Some(())
+    }
+}
+
+impl<
+    ProgressType: std::fmt::Debug
+        + ConstOne
+        + ConstZero
+        + 'static
+        + std::marker::Send
+        + mirl::extensions::TryFromPatch<usize>
+        + Copy
+        + core::ops::Add<Output = ProgressType>
+        + core::ops::Sub<Output = ProgressType>
+        + core::ops::Mul<Output = ProgressType>
+        + TryIntoPatch<f32>
+        + TryFromPatch<f32>
+        + core::ops::Div<Output = ProgressType>
+        + TryFromPatch<f64>
+        + core::cmp::PartialOrd
+        + mirl::extensions::Clamp,
+    LimitType: mirl::math::NumberWithMonotoneOps
+        + ConstZero
+        + std::fmt::Debug
+        + std::marker::Send
+        + TryFromPatch<f64>
+        + Copy
+        + 'static,
+> RangeSlider<ProgressType, LimitType>
+where
+    isize: mirl::extensions::TryFromPatch<ProgressType>,
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
+{
+    /// The progress of the handle of a part, the span uses the lower handle
+    const fn get_part_progress(&self, part: RangeSliderPart) -> ProgressType {
+        match part {
+            RangeSliderPart::Lower | RangeSliderPart::Span => self.base.progress,
+            RangeSliderPart::Upper => self.upper_progress,
+        }
+    }
+    /// Move a part from its current progress to the new one, keeping the handles in order and on the track
+    fn move_part(
+        &mut self,
+        part: RangeSliderPart,
+        current: ProgressType,
+        new_progress: ProgressType,
+    ) {
+        let (zero, one) = (ProgressType::ZERO, ProgressType::ONE);
+        let (lower, upper) = match part {
+            RangeSliderPart::Lower => (
+                new_progress.clamped(zero, self.upper_progress),
+                self.upper_progress,
+            ),
+            RangeSliderPart::Upper => (
+                self.base.progress,
+                new_progress.clamped(self.base.progress, one),
+            ),
+            RangeSliderPart::Span => {
+                let delta = (new_progress - current).clamped(
+                    zero - self.base.progress,
+                    one - self.upper_progress,
+                );
+                (
+                    self.base.snap_progress(self.base.progress + delta),
+                    self.base.snap_progress(self.upper_progress + delta),
+                )
+            }
+        };
+        if lower > upper {
+            return;
+        }
+        if lower != self.base.progress || upper != self.upper_progress {
+            self.base.needs_redraw = true;
+        }
+        self.base.progress = lower;
+        self.base.drag_progress = lower;
+        self.upper_progress = upper;
+        self.upper_drag_progress = upper;
+    }
+    /// If the mouse is within the track and which part it hovers
+    fn get_hovered_part(
+        &self,
+        mouse_pos: (f32, f32),
+        lower: f32,
+        upper: f32,
+    ) -> (bool, Option<RangeSliderPart>) {
+        let slider_width = self.base.slider_width as f32;
+
+        let in_track = mouse_pos.1 >= 0.0
+            && mouse_pos.1 < self.base.height as f32
+            && mouse_pos.0 >= 0.0
+            && mouse_pos.0 < self.base.width as f32;
+        let hits_lower = in_track
+            && mouse_pos.0 >= lower
+            && mouse_pos.0 < lower + slider_width;
+        let hits_upper = in_track
+            && mouse_pos.0 >= upper
+            && mouse_pos.0 < upper + slider_width;
+        let hovered = if hits_lower && hits_upper {
+            // Overlapping handles, pick the one that is still able to move
+            if self.upper_progress >= ProgressType::ONE {
+                Some(RangeSliderPart::Lower)
+            } else {
+                Some(RangeSliderPart::Upper)
+            }
+        } else if hits_upper {
+            Some(RangeSliderPart::Upper)
+        } else if hits_lower {
+            Some(RangeSliderPart::Lower)
+        } else if in_track
+            && mouse_pos.0 >= lower + slider_width
+            && mouse_pos.0 < upper
+        {
+            Some(RangeSliderPart::Span)
+        } else {
+            None
+        };
+        (in_track, hovered)
+    }
+    /// Dragging, selecting and scrolling, the info has to be localized already
+    fn update_mouse(
+        &mut self,
+        info: &crate::ModuleUpdateInfo,
+        mouse_pos: (f32, f32),
+    ) -> (FocusTaken, Option<CursorStyle>) {
+        let drag_cursor = if self.base.vertical {
+            CursorStyle::ResizeVertically
+        } else {
+            CursorStyle::ResizeHorizontally
+        };
+        let (Ok(lower), Ok(upper)) = (
+            self.base.get_handle_position(self.base.progress),
+            self.base.get_handle_position(self.upper_progress),
+        ) else {
+            return (FocusTaken::FocusFree, None);
+        };
+        let (in_track, hovered) =
+            self.get_hovered_part(mouse_pos, lower as f32, upper as f32);
+
+        let already_dragging =
+            self.base.dragging == info.container_id && info.mouse_info.left.down;
+        let starting_to_drag = info.mouse_info.left.clicked
+            && hovered.is_some()
+            && self.base.dragging == 0;
+
+        if info.mouse_info.left.clicked {
+            self.select(info, hovered, in_track, mouse_pos.0);
+        }
+        let cursor_style;
+        let mut took_focus = FocusTaken::FocusFree;
+        if starting_to_drag && let Some(part) = hovered {
+            self.dragged_part = part;
+            self.base.drag_progress = self.base.progress;
+            self.upper_drag_progress = self.upper_progress;
+        }
+        if already_dragging || starting_to_drag {
+            self.base.dragging = info.container_id;
+            took_focus = FocusTaken::FunctionallyTaken;
+            let Some(delta) = ProgressType::try_from_value(
+                info.mouse_pos_delta.0 / self.base.width as f32,
+            ) else {
+                return (FocusTaken::FocusFree, None);
+            };
+            let (zero, one) = (ProgressType::ZERO, ProgressType::ONE);
+            match self.dragged_part {
+                RangeSliderPart::Lower => {
+                    cursor_style = Some(drag_cursor);
+                    self.base.drag_progress = (self.base.drag_progress + delta)
+                        .clamped(zero, self.upper_progress);
+                }
+                RangeSliderPart::Upper => {
+                    cursor_style = Some(drag_cursor);
+                    self.upper_drag_progress = (self.upper_drag_progress
+                        + delta)
+                        .clamped(self.base.progress, one);
+                }
+                RangeSliderPart::Span => {
+                    cursor_style = Some(CursorStyle::HandClosed);
+                    // Moving as a unit means neither end may leave the track
+                    let delta = delta.clamped(
+                        zero - self.base.drag_progress,
+                        one - self.upper_drag_progress,
+                    );
+                    self.base.drag_progress = self.base.drag_progress + delta;
+                    self.upper_drag_progress = self.upper_drag_progress + delta;
+                }
+            }
+            let new_lower = self.base.snap_progress(self.base.drag_progress);
+            let new_upper = self.base.snap_progress(self.upper_drag_progress);
+            if new_lower <= new_upper {
+                if new_lower != self.base.progress
+                    || new_upper != self.upper_progress
+                {
+                    self.base.needs_redraw = true;
+                }
+                self.base.progress = new_lower;
+                self.upper_progress = new_upper;
+            }
+        } else {
+            self.base.dragging = 0;
+            cursor_style = match hovered {
+                Some(RangeSliderPart::Span) => Some(CursorStyle::HandOpen),
+                Some(_) => Some(CursorStyle::CenteredPointer),
+                None => None,
+            };
+        }
+
+        if in_track && self.base.dragging == 0 && self.scroll(info, hovered) {
+            took_focus = FocusTaken::FunctionallyTaken;
+        }
+
+        (took_focus, cursor_style)
+    }
+    /// Move the hovered part, or the selected one over the span, by one scroll tick. Returns if anything was scrolled
+    fn scroll(
+        &mut self,
+        info: &crate::ModuleUpdateInfo,
+        hovered: Option<RangeSliderPart>,
+    ) -> bool {
+        let scroll = if info.mouse_scroll.0 == 0.0 {
+            info.mouse_scroll.1
+        } else {
+            info.mouse_scroll.0
+        };
+        if scroll == 0.0 {
+            return false;
+        }
+        let part = hovered.unwrap_or(self.selected_part);
+        let current = self.get_part_progress(part);
+        let Some(new_progress) = self.base.scroll_progress(current, scroll)
+        else {
+            return false;
+        };
+        self.move_part(part, current, new_progress);
+        true
+    }
+    /// Select or deselect the slider on click, selecting the clicked part or the closest handle
+    fn select(
+        &mut self,
+        info: &crate::ModuleUpdateInfo,
+        hovered: Option<RangeSliderPart>,
+        in_track: bool,
+        mouse_x: f32,
+    ) {
+        let container_id = info.container_id;
+        if !in_track {
+            if self.base.selected == container_id {
+                self.base.selected = 0;
+                self.base.needs_redraw = true;
+            }
+            return;
+        }
+        let selected_part = hovered.unwrap_or_else(|| {
+            let progress = f64::from(mouse_x) / self.base.width as f64;
+            let to_lower = f64::try_from_value(self.base.progress)
+                .map_or(f64::MAX, |lower| (progress - lower).abs());
+            let to_upper = f64::try_from_value(self.upper_progress)
+                .map_or(f64::MAX, |upper| (progress - upper).abs());
+            if to_upper < to_lower {
+                RangeSliderPart::Upper
+            } else {
+                RangeSliderPart::Lower
+            }
+        });
+        if selected_part != self.selected_part {
+            self.selected_part = selected_part;
+            self.base.needs_redraw = true;
+        }
+        if self.base.selected != container_id {
+            self.base.selected = container_id;
+            self.base.last_keys_pressed.clone_from(info.pressed_keys);
+            self.base.needs_redraw = true;
+        }
+    }
+}
+
+impl<
+    ProgressType: std::fmt::Debug
+        + ConstOne
+        + ConstZero
+        + 'static
+        + std::marker::Send
+        + mirl::extensions::TryFromPatch<usize>
+        + Copy
+        + core::ops::Add<Output = ProgressType>
+        + core::ops::Sub<Output = ProgressType>
+        + core::ops::Mul<Output = ProgressType>
+        + TryIntoPatch<f32>
+        + TryFromPatch<f32>
+        + core::ops::Div<Output = ProgressType>
+        + TryFromPatch<f64>
+        + core::cmp::PartialOrd
+        + mirl::extensions::Clamp,
+    LimitType: mirl::math::NumberWithMonotoneOps
+        + ConstZero
+        + std::fmt::Debug
+        + std::marker::Send
+        + TryFromPatch<f64>
+        + Copy
+        + 'static,
+> DearMirlGuiModule for RangeSlider<ProgressType, LimitType>
+where
+    isize: mirl::extensions::TryFromPatch<ProgressType>,
+    f64: TryFromPatch<ProgressType> + TryFromPatch<LimitType>,
+{
+    fn apply_new_formatting(&mut self, formatting: &crate::Formatting) {
+        self.base.apply_new_formatting(formatting);
+    }
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.base.get_width(formatting)
+    }
+    fn get_height(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.base.get_height(formatting)
+    }
+    fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
+        self.base.set_need_redraw(need_redraw);
+    }
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> (Buffer, InsertionMode) {
+        let base = &self.base;
+        let mut buffer = base.draw_track(formatting);
+        let (lower, upper) = match (
+            base.get_handle_position(base.progress),
+            base.get_handle_position(self.upper_progress),
+        ) {
+            (Ok(lower), Ok(upper)) => (lower, upper),
+            (Err(error), _) | (_, Err(error)) => {
+                return new_buffer_error(&error);
+            }
+        };
+
+        // The selected span
+        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+            &mut buffer,
+            (lower, 0),
+            (upper - lower + base.slider_width as isize, base.height as isize),
+            mirl::graphics::adjust_brightness_hsl_of_rgb(
+                formatting.foreground_color,
+                20.0,
+            ),
+        );
+        // Only the handles the keyboard moves are highlighted
+        let handle_color = base.get_handle_color(info.container_id, formatting);
+        let (lower_color, upper_color) = match self.selected_part {
+            RangeSliderPart::Lower => (handle_color, formatting.text_color),
+            RangeSliderPart::Upper => (formatting.text_color, handle_color),
+            RangeSliderPart::Span => (handle_color, handle_color),
+        };
+        base.draw_handle(&mut buffer, lower, lower_color);
+        base.draw_handle(&mut buffer, upper, upper_color);
+        let mut buffer = base.orient(buffer, formatting);
+
+        if base.value_label {
+            base.draw_value_label(&mut buffer, formatting, base.progress, lower);
+            base.draw_value_label(
+                &mut buffer,
+                formatting,
+                self.upper_progress,
+                upper,
+            );
+        } else if !base.vertical {
+            let text = format!(
+                "{} - {}",
+                base.progress_to_value_f64(base.progress)
+                    .map(|value| base.format_value(value))
+                    .unwrap_or_default(),
+                base.progress_to_value_f64(self.upper_progress)
+                    .map(|value| base.format_value(value))
+                    .unwrap_or_default(),
+            );
+            let text_width = mirl::render::get_text_width(
+                &text,
+                base.height as f32,
+                &formatting.font,
+            );
+            render::draw_text_antialiased_execute_at::<false>(
+                &mut buffer,
+                &text,
+                (
+                    ((base.width as f32 - text_width) / 2.0).max(0.0) as usize,
+                    0,
+                ),
+                formatting.text_color,
+                base.height as f32,
+                &formatting.font,
+                draw_or_invert,
+            );
+        }
+
+        (buffer, InsertionMode::ReplaceAll)
+    }
+    fn update(&mut self, info: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        if info.focus_taken == FocusTaken::FunctionallyTaken
+            && self.base.selected == info.container_id
+        {
+            self.base.selected = 0;
+            self.base.needs_redraw = true;
+        }
+        if info.focus_taken.is_focus_taken() {
+            return crate::GuiOutput::empty();
+        }
+        // Everything below works as if the slider was horizontal
+        let info = &self.base.localize_info(info);
+        let (mut took_focus, cursor_style) =
+            info.mouse_pos.map_or((FocusTaken::FocusFree, None), |mouse_pos| {
+                self.update_mouse(info, mouse_pos)
+            });
+
+        if self.base.selected == info.container_id {
+            let current = self.get_part_progress(self.selected_part);
+            if let Some(new_progress) =
+                self.base.handle_keyboard_from(current, info.pressed_keys)
+            {
+                took_focus = FocusTaken::FunctionallyTaken;
+                self.move_part(self.selected_part, current, new_progress);
+            }
+        }
+
+        crate::GuiOutput::default(took_focus).with_optional_cursor(cursor_style)
+    }
+    fn need_redraw(&mut self) -> bool {
+        self.base.need_redraw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A slider without a formatting, [`Slider::new`] requires one to be set
+    fn slider<LimitType: mirl::math::NumberWithMonotoneOps>(
+        range: std::ops::Range<LimitType>,
+    ) -> Slider<f64, LimitType> {
+        Slider {
+            width: 30,
+            height: 10,
+            progress: 0.0,
+            slider_width: 2,
+            dragging: 0,
+            wrap: false,
+            eps: 1e-6,
+            needs_redraw: true,
+            range,
+            mapping: SliderMapping::Linear,
+            step: None,
+            ticks: 0,
+            tick_labels: false,
+            value_label: false,
+            label_precision: 2,
+            drag_progress: 0.0,
+            vertical: false,
+            selected: 0,
+            last_keys_pressed: Vec::new(),
+            keyboard_step: 0.01,
+            keyboard_page_step: 0.1,
+        }
+    }
+
+    fn assert_extremes<T: SliderValue + core::fmt::Debug>(min: T, max: T) {
+        assert_eq!(T::lerp(0.0, min, max), min);
+        assert_eq!(T::lerp(1.0, min, max), max);
+        assert!(T::inverse_lerp(min, min, max).abs() < f64::EPSILON);
+        assert!((T::inverse_lerp(max, min, max) - 1.0).abs() < f64::EPSILON);
+        assert_eq!(T::lerp(T::inverse_lerp(min, min, max), min, max), min);
+        assert_eq!(T::lerp(T::inverse_lerp(max, min, max), min, max), max);
+    }
+
+    #[test]
+    fn lerp_reaches_the_type_extremes() {
+        assert_extremes(u8::MIN, u8::MAX);
+        assert_extremes(i8::MIN, i8::MAX);
+        assert_extremes(u64::MIN, u64::MAX);
+        assert_extremes(i64::MIN, i64::MAX);
+        assert_extremes(u128::MIN, u128::MAX);
+        assert_extremes(i128::MIN, i128::MAX);
+        assert_extremes(f32::MIN, f32::MAX);
+        assert_extremes(f64::MIN, f64::MAX);
+    }
+
+    #[test]
+    fn lerp_handles_reversed_ranges() {
+        assert_eq!(i64::lerp(0.0, i64::MAX, i64::MIN), i64::MAX);
+        assert_eq!(i64::lerp(1.0, i64::MAX, i64::MIN), i64::MIN);
+        assert!((u8::inverse_lerp(0, 200, 100) - 1.0).abs() < f64::EPSILON);
+        assert!(u8::inverse_lerp(5, 5, 5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn small_integer_ranges_round_trip_every_value() {
+        for value in u8::MIN..=u8::MAX {
+            assert_eq!(
+                u8::lerp(u8::inverse_lerp(value, 0, 255), 0, 255),
+                value
+            );
+        }
+        for value in i8::MIN..=i8::MAX {
+            let progress = i8::inverse_lerp(value, i8::MIN, i8::MAX);
+            assert_eq!(i8::lerp(progress, i8::MIN, i8::MAX), value);
+        }
+    }
+
+    #[test]
+    fn snapping_stays_within_the_type() {
+        assert_eq!(7_u8.snap(0, 5), 5);
+        assert_eq!(8_u8.snap(0, 5), 10);
+        assert_eq!(255_u8.snap(0, 100), 200);
+        assert_eq!((-3_i8).snap(-10, 4), -2);
+        assert_eq!(i64::MAX.snap(i64::MIN, i64::MAX), i64::MAX - 1);
+        assert_eq!(7_u8.snap(0, 0), 7);
+        assert!((0.26_f64.snap(0.0, 0.25) - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn slider_value_round_trips_at_the_extremes() {
+        let mut signed = slider(i64::MIN..i64::MAX);
+        for value in [i64::MIN, 0, i64::MAX] {
+            assert_eq!(signed.set_value(value), Some(()));
+            assert_eq!(signed.get_value(), Some(value));
+        }
+        let mut unsigned = slider(u64::MIN..u64::MAX);
+        for value in [u64::MIN, u64::MAX] {
+            assert_eq!(unsigned.set_value(value), Some(()));
+            assert_eq!(unsigned.get_value(), Some(value));
+        }
+        let mut wide = slider(i128::MIN..i128::MAX);
+        for value in [i128::MIN, i128::MAX] {
+            assert_eq!(wide.set_value(value), Some(()));
+            assert_eq!(wide.get_value(), Some(value));
+        }
+    }
+
+    #[test]
+    fn slider_value_respects_steps_and_mapping() {
+        let mut stepped = slider(0_u32..100).with_step(10);
+        assert_eq!(stepped.set_value(44), Some(()));
+        assert_eq!(stepped.get_value(), Some(40));
+
+        let mut logarithmic =
+            slider(1.0_f64..1000.0).with_mapping(SliderMapping::Logarithmic);
+        assert_eq!(logarithmic.set_value(10.0), Some(()));
+        assert!((logarithmic.progress - 1.0 / 3.0).abs() < 1e-9);
+        assert!(
+            logarithmic.get_value().is_some_and(|v| (v - 10.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn range_slider_keyboard_moves_the_selected_part() {
+        let mut range = RangeSlider::from_slider(slider(0_u32..100), 0.0);
+        range.upper_progress = 0.5;
+        range.selected_part = RangeSliderPart::Upper;
+
+        let current = range.get_part_progress(range.selected_part);
+        let end = range.base.handle_keyboard_from(current, &[KeyCode::End]);
+        assert_eq!(end, Some(1.0));
+        range.move_part(RangeSliderPart::Upper, current, 1.0);
+        assert_eq!(range.get_value(), Some(0..100));
+
+        // The lower handle can't pass the upper one
+        range.upper_progress = 0.5;
+        range.move_part(RangeSliderPart::Lower, 0.0, 0.8);
+        assert_eq!(range.get_value(), Some(50..50));
+
+        // The span keeps its size and stays on the track
+        range.base.progress = 0.25;
+        range.move_part(RangeSliderPart::Span, 0.25, 1.0);
+        assert_eq!(range.get_value(), Some(75..100));
+    }
+}
@@ -1,15 +1,25 @@
-use mirl::render::draw_buffer_on_buffer_1_to_1;
+use mirl::{
+    extensions::*,
+    render::{self, draw_buffer_on_buffer},
+};
 
 use crate::{
-    Buffer, DearMirlGuiModule, InsertionMode, WhatAmI, get_formatting,
-    modules::support::struct_editing::{
-        DynSyncInspectable, Inspectable, draw_inspectable,
-        get_size_of_inspectable, update_inspectable,
+    Buffer, DearMirlGuiModule, FocusTaken, InsertionMode, WhatAmI,
+    get_formatting,
+    modules::{
+        TextInput,
+        support::struct_editing::{
+            DynSyncInspectable, FieldChange, InspectValue, Inspectable,
+            InspectableSize, InspectableViewState, draw_inspectable,
+            get_field_changes, get_field_values, get_size_of_inspectable,
+            update_inspectable,
+        },
     },
 };
 
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone)]
+/// A function called with a change the user made, see [`StructEditor::with_field_callback`]
+pub type FieldCallback = Box<dyn FnMut(&FieldChange) + Send + Sync>;
+
 /// A module with which you can visually edit structs
 pub struct StructEditor {
     /// If the held struct was edited
@@ -20,8 +30,69 @@ pub struct StructEditor {
     pub compare: Option<DynSyncInspectable>,
     #[allow(missing_docs)]
     pub needs_redraw: std::cell::Cell<bool>,
-    /// The cached size of the struct
-    pub size: (isize, isize),
+    /// The cached size of the struct and its fields, recalculated when the shown fields change
+    pub size: InspectableSize,
+    /// What nested fields are collapsed and what the fields are filtered by
+    pub view: InspectableViewState,
+    /// The search box used for filtering fields, see [`with_filter_box`](Self::with_filter_box)
+    pub filter_input: Option<TextInput>,
+    /// When the struct is taller than this it can be scrolled
+    pub max_height: Option<usize>,
+    /// The camera used for scrolling when the struct is taller than [`max_height`](Self::max_height)
+    pub camera: mirl::misc::ScrollableCamera,
+    /// The field values after the last edit, used for detecting what the user changed
+    pub last_values: Vec<(String, InspectValue)>,
+    /// Changes made by the user since the last [`take_changes`](Self::take_changes)
+    pub changes: Vec<FieldChange>,
+    /// Functions called when the field at the given path or any field nested inside it is changed by the user, not copied when cloning the editor
+    pub field_callbacks: Vec<(String, FieldCallback)>,
+    /// The serialized value after the last sync, see [`sync_serde`](Self::sync_serde)
+    #[cfg(feature = "serde")]
+    pub serde_snapshot: Option<serde_json::Value>,
+}
+impl std::fmt::Debug for StructEditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let callback_paths: Vec<&String> =
+            self.field_callbacks.iter().map(|(path, _)| path).collect();
+        let mut debug = f.debug_struct("StructEditor");
+        debug
+            .field("struct_edited_by_editor", &self.struct_edited_by_editor)
+            .field("storage", &self.storage)
+            .field("compare", &self.compare)
+            .field("needs_redraw", &self.needs_redraw)
+            .field("size", &self.size)
+            .field("view", &self.view)
+            .field("filter_input", &self.filter_input)
+            .field("max_height", &self.max_height)
+            .field("camera", &self.camera)
+            .field("last_values", &self.last_values)
+            .field("changes", &self.changes)
+            .field("field_callbacks", &callback_paths);
+        #[cfg(feature = "serde")]
+        debug.field("serde_snapshot", &self.serde_snapshot);
+        debug.finish()
+    }
+}
+impl Clone for StructEditor {
+    /// The clone has no field callbacks, they cannot be copied
+    fn clone(&self) -> Self {
+        Self {
+            struct_edited_by_editor: self.struct_edited_by_editor,
+            storage: self.storage.clone(),
+            compare: self.compare.clone(),
+            needs_redraw: self.needs_redraw.clone(),
+            size: self.size.clone(),
+            view: self.view.clone(),
+            filter_input: self.filter_input.clone(),
+            max_height: self.max_height,
+            camera: self.camera.clone(),
+            last_values: self.last_values.clone(),
+            changes: self.changes.clone(),
+            field_callbacks: Vec::new(),
+            #[cfg(feature = "serde")]
+            serde_snapshot: self.serde_snapshot.clone(),
+        }
+    }
 }
 impl StructEditor {
     #[allow(missing_docs)]
@@ -30,19 +101,166 @@ impl StructEditor {
     pub fn new<T: Inspectable + Clone + 'static>(
         item: &T,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        Ok(Self {
+        Ok(Self::with_size(get_size_of_inspectable(
+            &std::sync::Arc::new(std::sync::Mutex::new(Box::new(item.clone()))),
+            &get_formatting(),
+            None,
+            "",
+            &InspectableViewState::default(),
+        )?))
+    }
+    /// An editor without any storage yet
+    fn with_size(size: InspectableSize) -> Self {
+        Self {
             storage: None,
             compare: None,
             needs_redraw: std::cell::Cell::new(true),
             struct_edited_by_editor: false,
-            size: get_size_of_inspectable(
-                &std::sync::Arc::new(std::sync::Mutex::new(Box::new(
-                    item.clone(),
-                ))),
+            size,
+            view: InspectableViewState::default(),
+            filter_input: None,
+            max_height: None,
+            camera: mirl::misc::ScrollableCamera {
+                container_width: 0.0,
+                container_height: 0.0,
+                content_width: 0.0,
+                content_height: 0.0,
+                offset_x: 0.0,
+                offset_y: 0.0,
+                scroll_multiplier_x: 1.0,
+                scroll_multiplier_y: 1.0,
+                horizontal_context_switch_multipliers: true,
+                allow_free_scroll: false,
+            },
+            last_values: Vec::new(),
+            changes: Vec::new(),
+            field_callbacks: Vec::new(),
+            #[cfg(feature = "serde")]
+            serde_snapshot: None,
+        }
+    }
+    /// Call the given function whenever the user changes the field at the given path (`nested.priority`) or any field nested inside it, an empty path matches every field
+    #[must_use]
+    pub fn with_field_callback(
+        mut self,
+        path: &str,
+        callback: impl FnMut(&FieldChange) + Send + Sync + 'static,
+    ) -> Self {
+        self.field_callbacks.push((path.to_string(), Box::new(callback)));
+        self
+    }
+    /// Get all changes the user made since the last call, oldest first
+    pub fn take_changes(&mut self) -> Vec<FieldChange> {
+        std::mem::take(&mut self.changes)
+    }
+    /// Compare the current values with the ones after the last edit, report what changed
+    ///
+    /// Returns if anything changed
+    fn collect_changes(&mut self) -> bool {
+        let Some(store) = &self.storage else {
+            return false;
+        };
+        let values = match get_field_values(store, "") {
+            Err(error) => {
+                println!("ERROR: {error}");
+                return false;
+            }
+            Ok(values) => values,
+        };
+        let changes = get_field_changes(&self.last_values, &values);
+        self.last_values = values;
+        for change in &changes {
+            for (path, callback) in &mut self.field_callbacks {
+                if path.is_empty()
+                    || change.path == *path
+                    || change.path.starts_with(&format!("{path}."))
+                {
+                    callback(change);
+                }
+            }
+        }
+        let changed = !changes.is_empty();
+        self.changes.extend(changes);
+        changed
+    }
+    /// Forget about the current values being different, used when the struct was changed by your code
+    fn refresh_values(&mut self) {
+        if let Some(store) = &self.storage {
+            match get_field_values(store, "") {
+                Err(error) => println!("ERROR: {error}"),
+                Ok(values) => self.last_values = values,
+            }
+        }
+    }
+    /// Show a search box above the struct, only fields whose path contains the searched text are shown
+    #[must_use]
+    pub fn with_filter_box(mut self) -> Self {
+        let formatting = get_formatting();
+        self.filter_input = Some(
+            TextInput::new(
+                (self.size.width as usize).max(formatting.height * 6),
+                1,
+                None,
+            )
+            .with_placeholder_text("Filter...")
+            .show_line_numbers(false),
+        );
+        self
+    }
+    /// Limit the height of the module, the struct can be scrolled when it is taller
+    #[must_use]
+    pub const fn with_max_height(mut self, max_height: usize) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+    /// Only show fields whose path contains the given text, an empty text shows everything
+    pub fn set_filter(&mut self, filter: &str) {
+        if let Some(filter_input) = &mut self.filter_input {
+            filter_input.text = Vec::from([filter.to_string()]);
+            filter_input.set_need_redraw(Vec::from([(0, true)]));
+        }
+        self.view.filter = filter.to_string();
+        self.camera.offset_y = 0.0;
+        self.recalculate_size();
+    }
+    /// Collapse or expand the nested field at the given path (`nested`, `nested.inner`)
+    pub fn set_collapsed(&mut self, path: &str, collapsed: bool) {
+        if collapsed {
+            self.view.collapsed.insert(path.to_string());
+        } else {
+            self.view.collapsed.remove(path);
+        }
+        self.recalculate_size();
+    }
+    /// Recalculate the cached size after the shown fields changed
+    pub fn recalculate_size(&mut self) {
+        if let Some(store) = &self.storage {
+            match get_size_of_inspectable(
+                store,
                 &get_formatting(),
                 None,
-            )?,
-        })
+                "",
+                &self.view,
+            ) {
+                Err(error) => println!("ERROR: {error}"),
+                Ok(size) => self.size = size,
+            }
+        }
+        self.camera.container_height = self.get_visible_height() as f32;
+        self.camera.content_height = self.size.height as f32;
+        self.camera.clamp_to_bounds();
+        self.needs_redraw.set(true);
+    }
+    /// The height of everything above the struct
+    fn get_header_height(&mut self, formatting: &crate::Formatting) -> isize {
+        self.filter_input
+            .as_mut()
+            .map_or(0, |x| x.get_height(formatting) as isize)
+    }
+    /// The height of the area the struct is visible in
+    fn get_visible_height(&self) -> isize {
+        self.max_height
+            .map_or(self.size.height, |max| self.size.height.min(max as isize))
     }
     /// Sync the given struct with the struct in the local storage, local edits are prioritized
     ///
@@ -97,23 +315,107 @@ impl StructEditor {
                 if !compare_contents.is_same(item) {
                     //println!("Update!");
                     storage_contents.sync(&t)?;
+                    drop(storage_contents);
+                    drop(compare_contents);
+                    self.refresh_values();
+                    self.recalculate_size();
                     //println!("Done");
                 }
             } else {
-                self.size =
-                    get_size_of_inspectable(&t, &get_formatting(), None)?;
+                self.size = get_size_of_inspectable(
+                    &t,
+                    &get_formatting(),
+                    None,
+                    "",
+                    &self.view,
+                )?;
 
                 // store an owned boxed trait object
                 self.storage = Some(t);
                 self.compare = Some(std::sync::Arc::new(
                     std::sync::Mutex::new(Box::new(item.clone())),
                 ));
+                self.refresh_values();
             }
         }
         Ok(())
     }
 }
 use crate::modules::support::struct_editing::InspectableType;
+#[cfg(feature = "serde")]
+use crate::modules::support::struct_editing::serde_support;
+
+#[cfg(feature = "serde")]
+impl StructEditor {
+    /// Create an editor for any serializable value, no [`Inspectable`] implementation needed
+    ///
+    /// Use [`sync_serde`](Self::sync_serde) instead of [`sync`](Self::sync) to keep the value up to date
+    ///
+    /// # Errors
+    /// When the value cannot be serialized
+    pub fn new_serde<T: serde::Serialize>(
+        item: &T,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut editor = Self::with_size(InspectableSize::default());
+        editor.store_serde(serde_json::to_value(item)?)?;
+        Ok(editor)
+    }
+    /// Replace the local storage with editors for the given value
+    fn store_serde(
+        &mut self,
+        value: serde_json::Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.storage = Some(
+            serde_support::inspectable_from_json(value.clone())
+                .ok_or("Unable to build the editors for the value")?,
+        );
+        self.serde_snapshot = Some(value);
+        self.recalculate_size();
+        self.refresh_values();
+        Ok(())
+    }
+    /// Sync the given value with the value in the local storage, local edits are prioritized
+    ///
+    /// When the edited value can't be deserialized back into the type (e.g. a number is out of range) the value is left untouched and the reason is shown below the offending fields (see [`InspectableViewState::errors`]) until the edit is fixed
+    ///
+    /// # Errors
+    /// When the value cannot be serialized
+    /// When the MutexGuard lock has been poisoned
+    pub fn sync_serde<T: serde::Serialize + serde::de::DeserializeOwned>(
+        &mut self,
+        item: &mut T,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.struct_edited_by_editor {
+            self.struct_edited_by_editor = false;
+            let Some(store) = &self.storage else {
+                return Ok(());
+            };
+            let value = serde_support::json_from_inspectable(store)?;
+            let errors = match serde_json::from_value::<T>(value.clone()) {
+                Ok(new) => {
+                    *item = new;
+                    self.serde_snapshot = Some(value);
+                    std::collections::HashMap::new()
+                }
+                Err(error) => serde_support::locate_deserialize_error::<T>(
+                    self.serde_snapshot.as_ref(),
+                    &value,
+                    &error,
+                ),
+            };
+            if errors != self.view.errors {
+                self.view.errors = errors;
+                self.recalculate_size();
+            }
+        } else {
+            let value = serde_json::to_value(&*item)?;
+            if self.serde_snapshot.as_ref() != Some(&value) {
+                self.store_serde(value)?;
+            }
+        }
+        Ok(())
+    }
+}
 
 impl DearMirlGuiModule for StructEditor {
     fn apply_new_formatting(&mut self, _formatting: &crate::Formatting) {}
@@ -129,76 +431,158 @@ impl DearMirlGuiModule for StructEditor {
             self.get_width(formatting) as usize,
             self.get_height(formatting) as usize,
         ));
+        let header_height = self.get_header_height(formatting);
         if let Some(store) = &mut self.storage {
             match draw_inspectable(
                 store,
                 0,
                 formatting,
-                Some(self.size),
+                Some(&self.size),
                 None,
+                "",
+                &self.view,
                 info,
             ) {
                 Err(error) => println!("ERROR: {error}"),
                 Ok(img) => {
-                    draw_buffer_on_buffer_1_to_1::<true, false, false, false>(
+                    draw_buffer_on_buffer::<true, false, false, false>(
                         &mut buffer,
                         &img,
-                        (0, 0),
+                        (0, header_height + self.camera.offset_y as isize),
                     );
-                    //println!("{:?}", (img.width, img.height));
-                    // render::draw_text::<true>(
-                    //     &mut buffer,
-                    //     "test",
-                    //     (0, 0),
-                    //     mirl::graphics::colors::RED,
-                    //     20.0,
-                    //     &formatting.font,
-                    // );
                 }
             }
         }
+        // Drawn last so the scrolled struct doesn't cover it
+        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+            &mut buffer,
+            (0, 0),
+            (buffer.width as isize, header_height),
+            formatting.background_color,
+        );
+        if let Some(filter_input) = &mut self.filter_input {
+            let img = filter_input.draw(formatting, info).0;
+            draw_buffer_on_buffer::<true, false, false, false>(
+                &mut buffer,
+                &img,
+                (0, 0),
+            );
+        }
 
         (buffer, InsertionMode::ReplaceAll)
     }
     fn get_height(
         &mut self,
-        _formatting: &crate::Formatting,
+        formatting: &crate::Formatting,
     ) -> crate::DearMirlGuiCoordinateType {
-        self.size.1 as crate::DearMirlGuiCoordinateType
+        (self.get_header_height(formatting) + self.get_visible_height())
+            as crate::DearMirlGuiCoordinateType
     }
     fn get_width(
         &mut self,
-        _formatting: &crate::Formatting,
+        formatting: &crate::Formatting,
     ) -> crate::DearMirlGuiCoordinateType {
-        self.size.0 as crate::DearMirlGuiCoordinateType
+        let filter_width = self
+            .filter_input
+            .as_mut()
+            .map_or(0, |x| x.get_width(formatting) as isize);
+        self.size.width.max(filter_width) as crate::DearMirlGuiCoordinateType
     }
     fn update(&mut self, info: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        let formatting = get_formatting();
+        let mut info = *info;
+        let mut output = crate::GuiOutput::empty();
+
+        let header_height = self.get_header_height(&formatting);
+        if let Some(filter_input) = &mut self.filter_input {
+            output |= filter_input.update(&info);
+            let filter = filter_input.text.join("");
+            if filter != self.view.filter {
+                self.view.filter = filter;
+                self.camera.offset_y = 0.0;
+                self.recalculate_size();
+            }
+            info.focus_taken |= output.focus_taken;
+        }
+
+        // Only the visible part of the struct can be interacted with
+        let visible_height = self.get_visible_height();
+        if let Some(pos) = info.mouse_pos {
+            info.mouse_pos = if (header_height as f32
+                ..(header_height + visible_height) as f32)
+                .contains(&pos.1)
+            {
+                Some(
+                    pos.sub((0.0, header_height as f32 + self.camera.offset_y)),
+                )
+            } else {
+                None
+            };
+        }
+
         if let Some(store) = &mut self.storage {
+            let collapsed_count = self.view.collapsed.len();
             match update_inspectable(
                 store,
                 0,
-                &get_formatting(),
-                Some(self.size),
+                &formatting,
+                Some(&self.size),
                 None,
-                info,
+                "",
+                &mut self.view,
+                &info,
             ) {
                 Err(error) => {
                     println!("ERROR: {error}");
-                    crate::GuiOutput::empty()
                 }
-                Ok(img) => img,
+                Ok(struct_output) => {
+                    // Only used for reporting, not every field can tell its value
+                    let changed = struct_output.focus_taken.is_focus_taken()
+                        && self.collect_changes();
+                    if changed {
+                        self.needs_redraw.set(true);
+                    }
+                    let toggled = collapsed_count != self.view.collapsed.len();
+                    if struct_output.focus_taken
+                        == FocusTaken::FunctionallyTaken
+                        && !toggled
+                    {
+                        self.struct_edited_by_editor = true;
+                    }
+                    // Switching an enum variant, changing the items of a collection or collapsing a field changes the shown fields
+                    if changed || toggled {
+                        self.recalculate_size();
+                    }
+                    output |= struct_output;
+                }
             }
-        } else {
-            crate::GuiOutput::empty()
         }
+
+        // Scroll when the struct doesn't fit
+        if info.mouse_pos.is_some()
+            && info.mouse_scroll != (0.0, 0.0)
+            && output.focus_taken != FocusTaken::FunctionallyTaken
+            && self.size.height > visible_height
+        {
+            self.camera.container_height = visible_height as f32;
+            self.camera.content_height = self.size.height as f32;
+            self.camera.scroll(info.mouse_scroll, true);
+            self.needs_redraw.set(true);
+            output.focus_taken = FocusTaken::FunctionallyTaken;
+        }
+        output
     }
 
     fn need_redraw(&mut self) -> bool {
+        let filter_needs_redraw = self
+            .filter_input
+            .as_mut()
+            .is_some_and(DearMirlGuiModule::need_redraw);
         if self.needs_redraw.get() {
             self.needs_redraw.set(false);
             true
         } else {
-            false
+            filter_needs_redraw
         }
     }
 }
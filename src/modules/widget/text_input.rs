@@ -1,6 +1,5 @@
 use mirl::{
     extensions::*,
-    graphics::rgb_to_u32,
     misc::keybinds::{KeyBind, sort_actions},
     platform::{
         CursorStyle,
@@ -13,7 +12,9 @@ use mirl::{
 
 use crate::{
     DRAW_SAFE, DearMirlGuiModule, FocusTaken, ModuleUpdateInfo,
-    module_manager::InsertionMode, modules::misc::shimmer, prelude::get_formatting,
+    module_manager::InsertionMode,
+    modules::{Menu, MenuItem, misc::shimmer},
+    prelude::get_formatting,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -575,6 +576,8 @@ pub struct TextInput {
     /// Drag to select
     /// TODO: Implement drag to move
     pub dragging: bool,
+    /// The scrollbars shown when the text doesn't fit
+    pub scrollbars: crate::Scrollbars,
 }
 /// Available menus
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -1351,6 +1354,14 @@ impl TextInput {
         }
         amount
     }
+    /// Set the container and content size of the camera to the current size of the input and its text
+    pub fn update_camera_bounds(&mut self, formatting: &crate::Formatting) {
+        self.camera.container_width = self.get_width(formatting) as f32;
+        self.camera.container_height = self.get_height(formatting) as f32;
+        let size = self.get_content_size(&formatting.font);
+        self.camera.content_height = size.1;
+        self.camera.content_width = size.0;
+    }
     /// Applies the scroll to the camera
     pub fn handle_scroll(
         &mut self,
@@ -1358,11 +1369,7 @@ impl TextInput {
         switch: bool,
         formatting: &crate::Formatting,
     ) {
-        self.camera.container_width = self.get_width(formatting) as f32;
-        self.camera.container_height = self.get_height(formatting) as f32;
-        let size = self.get_content_size(&formatting.font);
-        self.camera.content_height = size.1;
-        self.camera.content_width = size.0;
+        self.update_camera_bounds(formatting);
         self.camera.scroll(scroll, switch);
         // if switch {
         //     self.camera.0 = self.camera.0.add(scroll.1 * self.scroll_mul.1);
@@ -1530,6 +1537,15 @@ impl TextInput {
         self.text_height = height;
         self
     }
+    /// An inline setter for the scrollbars
+    #[must_use]
+    pub const fn with_scrollbars(
+        mut self,
+        scrollbars: crate::Scrollbars,
+    ) -> Self {
+        self.scrollbars = scrollbars;
+        self
+    }
 }
 
 impl TextInput {
@@ -1580,6 +1596,7 @@ impl TextInput {
             blacklist: Vec::new(),
             blacklist_is_whitelist: false,
             dragging: false,
+            scrollbars: crate::Scrollbars::new(),
         }
     }
 
@@ -1857,6 +1874,17 @@ impl TextInput {
         self.swap_caret_position(line1, line2);
     }
 
+    /// Add the current text to the undo history if it differs from the previous text
+    fn save_state_if_changed(&mut self, previous_state: &[String]) {
+        if self.text != previous_state {
+            if self.current_state < self.last_states.len().saturating_sub(1) {
+                self.last_states.truncate(self.current_state);
+            }
+            self.last_states.push((self.text.clone(), self.caret.clone()));
+            self.current_state = self.last_states.len() - 1;
+            //println!("Added {}", self.current_state);
+        }
+    }
     #[allow(clippy::too_many_lines)]
     fn handle_keybinds(
         &mut self,
@@ -2177,11 +2205,16 @@ impl DearMirlGuiModule for TextInput {
         let placeholder_color_change = -30.0;
         let line_number_padding_color_change = -30.0;
 
-        let highlight_color = rgb_to_u32(30, 20, 200);
+        let highlight_color = formatting.theme.selection_color;
 
         // Code
-        let mut buffer = Buffer::new_empty_with_color(
-            (self.width, self.get_height(formatting) as usize),
+        let size = (self.width, self.get_height(formatting) as usize);
+        let mut buffer =
+            Buffer::new_empty_with_color(size, formatting.background_color);
+        formatting.theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (size.0 as isize, size.1 as isize),
             mirl::graphics::adjust_brightness_hsl_of_rgb(
                 background_color,
                 background_color_change,
@@ -2388,7 +2421,7 @@ impl DearMirlGuiModule for TextInput {
             }
             if self.show_line_numbers {
                 // Line number background
-                render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                formatting.theme.draw_rectangle(
                     &mut buffer,
                     (0, 0),
                     (
@@ -2430,6 +2463,7 @@ impl DearMirlGuiModule for TextInput {
         //   self.height as f32 * text_size_mul,
         //   &formatting.font,
         // );
+        self.scrollbars.draw(&mut buffer, &self.camera, (0, 0), formatting);
 
         (buffer, InsertionMode::Simple)
     }
@@ -2473,7 +2507,21 @@ impl DearMirlGuiModule for TextInput {
         let mut took_functional_focus = false;
         //println!("{} {}", self.selected, info.container_id);
 
-        if let Some(mouse_position) = info.mouse_pos {
+        self.update_camera_bounds(formatting);
+        let scrollbars_before = self.scrollbars;
+        let scrollbar_output =
+            self.scrollbars.update(&mut self.camera, info.mouse_pos, info);
+        if scrollbars_before != self.scrollbars {
+            self.needs_redraw = true;
+        }
+        if scrollbar_output.focus_taken == FocusTaken::FunctionallyTaken {
+            // Typing continues while dragging a scrollbar
+            took_functional_focus = true;
+        }
+        let over_scrollbar = self.scrollbars.hovered.is_some();
+
+        if let Some(mouse_position) = info.mouse_pos.filter(|_| !over_scrollbar)
+        {
             let collides = collision.does_area_contain_point(mouse_position);
             //println!("\n\n\n\n{collision:?}\n{mouse_position:?}\n{collides}");
             if collides {
@@ -2549,6 +2597,9 @@ impl DearMirlGuiModule for TextInput {
             self.selected = 0;
             cursor_style = None;
         }
+        if over_scrollbar {
+            cursor_style = scrollbar_output.new_cursor_style;
+        }
 
         let mut request_clipboard_data = false;
         let mut new_clipboard_data = None;
@@ -2578,14 +2629,8 @@ impl DearMirlGuiModule for TextInput {
             request_clipboard_data = request_clipboard_data_local;
             new_clipboard_data = new_clipboard_data_local;
 
-            if !do_not_save_state && self.text != previous_state {
-                if self.current_state < self.last_states.len().saturating_sub(1)
-                {
-                    self.last_states.truncate(self.current_state);
-                }
-                self.last_states.push((self.text.clone(), self.caret.clone()));
-                self.current_state = self.last_states.len() - 1;
-                //println!("Added {}", self.current_state);
+            if !do_not_save_state {
+                self.save_state_if_changed(&previous_state);
             }
 
             self.last_keys_pressed.clone_from(info.pressed_keys);
@@ -2606,6 +2651,8 @@ impl DearMirlGuiModule for TextInput {
             text_input_selected: self.selected > 0,
             new_cursor_style: cursor_style,
             request_clipboard_data,
+            selected_menu_item: None,
+            window_events: Vec::new(),
         }
     }
     fn need_redraw(&mut self) -> bool {
@@ -2622,6 +2669,66 @@ impl DearMirlGuiModule for TextInput {
     fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
         self.needs_redraw = super::misc::determine_need_redraw(need_redraw);
     }
+    fn get_context_menu(&mut self) -> Option<Menu> {
+        let mut cut = MenuItem::new("cut", "Cut").with_shortcut("Ctrl+X");
+        let mut paste = MenuItem::new("paste", "Paste").with_shortcut("Ctrl+V");
+        if self.read_only {
+            cut = cut.disabled();
+            paste = paste.disabled();
+        }
+        Some(
+            Menu::new("")
+                .with_item(cut)
+                .with_item(
+                    MenuItem::new("copy", "Copy").with_shortcut("Ctrl+C"),
+                )
+                .with_item(paste)
+                .with_separator()
+                .with_item(
+                    MenuItem::new("select_all", "Select All")
+                        .with_shortcut("Ctrl+A"),
+                ),
+        )
+    }
+    fn context_menu_action(
+        &mut self,
+        id: &str,
+        inputs: &ModuleUpdateInfo,
+    ) -> crate::GuiOutput {
+        let action = match id {
+            "cut" => Actions::Cut,
+            "copy" => Actions::Copy,
+            "paste" => Actions::RequestPaste,
+            "select_all" => Actions::SelectAll,
+            _ => return crate::GuiOutput::empty(),
+        };
+        // The pasted text arrives in the next update, which only writes it when selected
+        if !self.read_only {
+            self.selected = inputs.container_id;
+        }
+        if self.caret.is_empty() {
+            self.caret = vec![Caret::new(0, 0)];
+        }
+        let previous_state = self.text.clone();
+        if self.last_states.is_empty() {
+            self.last_states.push((previous_state.clone(), self.caret.clone()));
+        }
+        let (do_not_save_state, request_clipboard_data, new_clipboard_data) =
+            self.handle_keybinds(&vec![KeyBind::new(
+                false,
+                false,
+                false,
+                Vec::new(),
+                action,
+            )]);
+        if !do_not_save_state {
+            self.save_state_if_changed(&previous_state);
+        }
+        self.needs_redraw = true;
+        crate::GuiOutput::default(FocusTaken::FunctionallyTaken)
+            .with_clipboard_data(new_clipboard_data)
+            .request_clipboard(request_clipboard_data)
+    }
 }
 
 /// Builder functions
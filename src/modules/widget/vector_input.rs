@@ -0,0 +1,587 @@
+use mirl::{
+    extensions::*,
+    math::ConstZero,
+    platform::{CursorStyle, keycodes::KeyCode},
+    prelude::Buffer,
+    render,
+};
+
+use crate::{
+    DearMirlGuiModule, FocusTaken, ModulePath,
+    module_manager::{InsertionMode, get_formatting},
+    modules::NumberInput,
+};
+
+/// Path inline support for [`VectorInput`]
+pub trait VectorInputPathSupport<T, const N: usize> {
+    /// Get the values of all components, returns None if the module could not be found
+    fn get_values(&self) -> Option<[T; N]>;
+    /// Get the value of a single component
+    fn get_component(&self, idx: usize) -> Option<T>;
+    /// Set the values of all components
+    fn set_values(&self, values: [T; N]);
+    /// Set the value of a single component
+    fn set_component(&self, idx: usize, value: T);
+}
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + Clone,
+    const N: usize,
+> VectorInputPathSupport<T, N> for ModulePath<VectorInput<T, N>>
+{
+    fn get_values(&self) -> Option<[T; N]> {
+        crate::module_manager::get_module_as::<_, _>(self, |vector| {
+            vector.get_values()
+        })
+        .ok()
+    }
+    fn get_component(&self, idx: usize) -> Option<T> {
+        crate::module_manager::get_module_as::<_, _>(self, |vector| {
+            vector.components.get(idx).map(|x| x.number.clone())
+        })
+        .ok()
+        .flatten()
+    }
+    fn set_values(&self, values: [T; N]) {
+        let _ =
+            crate::module_manager::get_module_as_mut::<_, _>(self, |vector| {
+                vector.set_values(values);
+            });
+    }
+    fn set_component(&self, idx: usize, value: T) {
+        let _ =
+            crate::module_manager::get_module_as_mut::<_, _>(self, |vector| {
+                if let Some(component) = vector.components.get_mut(idx) {
+                    component.set_number(value);
+                }
+            });
+    }
+}
+
+/// Path inline support for [`RgbaInput`]
+pub trait RgbaInputPathSupport {
+    /// Get the current color as a u32, returns 0 if the module could not be found
+    fn get_color(&self) -> u32;
+    /// Set the current color from a u32
+    fn set_color(&self, color: u32);
+}
+impl RgbaInputPathSupport for ModulePath<RgbaInput> {
+    fn get_color(&self) -> u32 {
+        crate::module_manager::get_module_as::<_, _>(self, |vector| {
+            vector.get_color()
+        })
+        .unwrap_or_default()
+    }
+    fn set_color(&self, color: u32) {
+        let _ =
+            crate::module_manager::get_module_as_mut::<_, _>(self, |vector| {
+                vector.set_color(color);
+            });
+    }
+}
+
+/// A two component input
+pub type Vec2Input<T> = VectorInput<T, 2>;
+/// A three component input
+pub type Vec3Input<T> = VectorInput<T, 3>;
+/// A four component input
+pub type Vec4Input<T> = VectorInput<T, 4>;
+/// A four component color input, use [`VectorInput::from_color`] to create it
+pub type RgbaInput = VectorInput<u8, 4>;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+/// A row of labelled [`NumberInput`] fields editing a single vector/tuple
+///
+/// - Click a field to edit it, Tab/Shift + Tab moves to the next/previous field
+/// - Drag a label horizontally to scrub its value
+pub struct VectorInput<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + Clone,
+    const N: usize,
+> {
+    /// The individual input fields
+    pub components: [NumberInput<T>; N],
+    /// The label drawn in front of each field
+    pub labels: [String; N],
+    /// The color each label is drawn in
+    pub label_colors: [u32; N],
+    /// How much a component changes per pixel dragged across its label
+    pub drag_speed: f64,
+    /// What values scrubbing is limited to
+    pub drag_range: Option<(f64, f64)>,
+    /// When scrubbing a label, should all components be changed at once
+    pub linked: bool,
+    /// Draw a preview of the color the components make up (Only makes sense for [`RgbaInput`])
+    pub show_color_preview: bool,
+    /// The container id that is currently scrubbing a label
+    pub scrubbing: usize,
+    /// What component is being scrubbed
+    pub scrubbed_component: usize,
+    /// The values of the components when scrubbing started
+    pub scrub_origin: Vec<f64>,
+    /// The horizontal distance the mouse has moved since scrubbing started
+    pub scrub_distance: f32,
+    /// Used for detecting new key strokes
+    pub last_keys_pressed: Vec<KeyCode>,
+    /// If the module needs to be redrawn
+    pub needs_redraw: bool,
+}
+
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + ConstZero
+        + PartialEq
+        + Clone,
+    const N: usize,
+> VectorInput<T, N>
+{
+    /// The default labels, components past the 4th are labelled by their index
+    pub const DEFAULT_LABELS: [&str; 4] = ["X", "Y", "Z", "W"];
+    /// How much a component changes per pixel by default
+    pub const DEFAULT_DRAG_SPEED: f64 = 0.1;
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn new(values: [T; N]) -> Self {
+        let formatting = get_formatting();
+        Self {
+            components: values.map(|value| {
+                NumberInput::new(value).with_width(formatting.height * 3)
+            }),
+            labels: core::array::from_fn(|idx| {
+                Self::DEFAULT_LABELS
+                    .get(idx)
+                    .map_or_else(|| idx.to_string(), |x| (*x).to_string())
+            }),
+            label_colors: [formatting.text_color; N],
+            drag_speed: Self::DEFAULT_DRAG_SPEED,
+            drag_range: None,
+            linked: false,
+            show_color_preview: false,
+            scrubbing: 0,
+            scrubbed_component: 0,
+            scrub_origin: Vec::new(),
+            scrub_distance: 0.0,
+            last_keys_pressed: Vec::new(),
+            needs_redraw: true,
+        }
+    }
+}
+impl RgbaInput {
+    /// Create a color input from a u32 color
+    #[must_use]
+    pub fn from_color(color: u32) -> Self {
+        Self::new(mirl::graphics::u32_to_rgba_u8(color).into())
+            .with_labels(["R", "G", "B", "A"])
+            .with_label_colors([
+                mirl::graphics::colors::RED,
+                mirl::graphics::colors::GREEN,
+                mirl::graphics::colors::BLUE,
+                get_formatting().text_color,
+            ])
+            .with_drag_range(0.0, 255.0)
+            .with_drag_speed(1.0)
+            .with_color_preview(true)
+    }
+    /// Get the current color as a u32
+    #[must_use]
+    pub fn get_color(&self) -> u32 {
+        let [r, g, b, a] = self.get_values();
+        mirl::graphics::rgba_to_u32(
+            u32::from(r),
+            u32::from(g),
+            u32::from(b),
+            u32::from(a),
+        )
+    }
+    /// Set the current color from a u32
+    pub fn set_color(&mut self, color: u32) {
+        self.set_values(mirl::graphics::u32_to_rgba_u8(color).into());
+    }
+}
+
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + Clone,
+    const N: usize,
+> VectorInput<T, N>
+{
+    /// Get the values of all components
+    #[must_use]
+    pub fn get_values(&self) -> [T; N] {
+        core::array::from_fn(|idx| self.components[idx].number.clone())
+    }
+    /// Set the values of all components
+    pub fn set_values(&mut self, values: [T; N]) {
+        for (component, value) in self.components.iter_mut().zip(values) {
+            component.set_number(value);
+        }
+        self.needs_redraw = true;
+    }
+    /// Get the index of the component that is selected in the given container
+    #[must_use]
+    pub fn get_selected_component(&self, container_id: usize) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|component| component.selected == container_id)
+    }
+    #[must_use]
+    /// Set the labels drawn in front of each component
+    pub fn with_labels(mut self, labels: [&str; N]) -> Self {
+        self.labels = labels.map(ToString::to_string);
+        self
+    }
+    #[must_use]
+    /// Set the colors the labels are drawn in
+    pub const fn with_label_colors(mut self, colors: [u32; N]) -> Self {
+        self.label_colors = colors;
+        self
+    }
+    #[must_use]
+    /// Set how much a component changes per pixel dragged across its label
+    pub const fn with_drag_speed(mut self, drag_speed: f64) -> Self {
+        self.drag_speed = drag_speed;
+        self
+    }
+    #[must_use]
+    /// Limit the values scrubbing can produce
+    pub const fn with_drag_range(mut self, min: f64, max: f64) -> Self {
+        self.drag_range = Some((min, max));
+        self
+    }
+    #[must_use]
+    /// When scrubbing a label, should all components be changed at once
+    pub const fn linked(mut self, linked: bool) -> Self {
+        self.linked = linked;
+        self
+    }
+    #[must_use]
+    /// Draw a preview of the color the components make up
+    pub const fn with_color_preview(mut self, show_color_preview: bool) -> Self {
+        self.show_color_preview = show_color_preview;
+        self
+    }
+    #[must_use]
+    /// Set the width of every single input field
+    pub fn with_component_width(mut self, width: usize) -> Self {
+        for component in &mut self.components {
+            component.width = width;
+        }
+        self
+    }
+}
+
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::extensions::TryFromPatch<f64>
+        + ConstZero
+        + PartialEq
+        + Clone,
+    const N: usize,
+> VectorInput<T, N>
+where
+    f64: mirl::extensions::TryFromPatch<T>,
+{
+    fn get_label_width(&self, idx: usize, formatting: &crate::Formatting) -> f32 {
+        render::get_text_width(
+            &self.labels[idx],
+            formatting.height as f32,
+            &formatting.font,
+        )
+    }
+    /// Get the horizontal start of each label and input field
+    fn get_layout(&mut self, formatting: &crate::Formatting) -> [(f32, f32); N] {
+        let mut x = 0.0;
+        core::array::from_fn(|idx| {
+            let label_x = x;
+            x += self.get_label_width(idx, formatting)
+                + formatting.horizontal_margin as f32;
+            let input_x = x;
+            x += (formatting.horizontal_margin as f32).mul_add(
+                2.0,
+                self.components[idx].get_width(formatting) as f32,
+            );
+            (label_x, input_x)
+        })
+    }
+    fn get_preview_size(&mut self, formatting: &crate::Formatting) -> f32 {
+        if self.show_color_preview {
+            self.get_height(formatting) as f32
+        } else {
+            0.0
+        }
+    }
+    /// Move the selection from one component to another
+    fn move_selection(&mut self, from: usize, to: usize, container_id: usize) {
+        self.components[from].selected = 0;
+        self.components[from].caret.reset_highlighted();
+        let target = &mut self.components[to];
+        target.selected = container_id;
+        target.last_keys_pressed.clone_from(&self.last_keys_pressed);
+        target.select_all();
+        target.needs_redraw = true;
+        self.needs_redraw = true;
+    }
+    /// Apply the current scrub distance to the scrubbed component(s)
+    fn apply_scrub(&mut self) {
+        let change = f64::from(self.scrub_distance) * self.drag_speed;
+        for (idx, component) in self.components.iter_mut().enumerate() {
+            if !self.linked && idx != self.scrubbed_component {
+                continue;
+            }
+            let Some(origin) = self.scrub_origin.get(idx) else {
+                continue;
+            };
+            let mut value = origin + change;
+            if let Some((min, max)) = self.drag_range {
+                value = value.clamp(min, max);
+            }
+            if let Some(new) = T::try_from_value(value)
+                && new != component.number
+            {
+                component.set_number(new);
+                self.needs_redraw = true;
+            }
+        }
+    }
+}
+
+impl<
+    T: core::fmt::Display
+        + std::marker::Send
+        + std::marker::Sync
+        + core::fmt::Debug
+        + 'static
+        + mirl::extensions::TryFromPatch<String>
+        + mirl::extensions::TryFromPatch<f64>
+        + ConstZero
+        + PartialEq
+        + Clone,
+    const N: usize,
+> DearMirlGuiModule for VectorInput<T, N>
+where
+    f64: mirl::extensions::TryFromPatch<T>,
+{
+    fn draw(
+        &mut self,
+        formatting: &crate::Formatting,
+        info: &crate::ModuleDrawInfo,
+    ) -> (Buffer, InsertionMode) {
+        let height = self.get_height(formatting);
+        let mut buffer = Buffer::new_empty((
+            self.get_width(formatting) as usize,
+            height as usize,
+        ));
+        let text_height = formatting.height as f32;
+        let text_y = (height as f32 - text_height) / 2.0;
+
+        for (idx, (label_x, input_x)) in
+            self.get_layout(formatting).into_iter().enumerate()
+        {
+            let color = if self.scrubbing == info.container_id
+                && self.scrubbed_component == idx
+            {
+                mirl::graphics::adjust_brightness_hsl_of_rgb(
+                    self.label_colors[idx],
+                    -20.0,
+                )
+            } else {
+                self.label_colors[idx]
+            };
+            render::draw_text_antialiased_isize::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                &self.labels[idx],
+                (label_x as isize, text_y as isize),
+                color,
+                text_height,
+                &formatting.font,
+            );
+            let img = self.components[idx].draw(formatting, info).0;
+            render::draw_buffer_on_buffer::<true, false, false, false>(
+                &mut buffer,
+                &img,
+                (input_x as isize, 0),
+            );
+        }
+        if self.show_color_preview {
+            let size = self.get_preview_size(formatting) as isize;
+            let values = self.get_values();
+            let channel = |idx: usize| {
+                values
+                    .get(idx)
+                    .and_then(|x| f64::try_from_value(x.clone()))
+                    .unwrap_or(255.0)
+                    .clamp(0.0, 255.0) as u32
+            };
+            let x = buffer.width as isize - size;
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                &mut buffer,
+                (x, 0),
+                (size, size),
+                mirl::graphics::rgba_to_u32(
+                    channel(0),
+                    channel(1),
+                    channel(2),
+                    channel(3),
+                ),
+            );
+        }
+
+        (buffer, InsertionMode::ReplaceAll)
+    }
+    fn get_height(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        self.components
+            .iter_mut()
+            .map(|component| component.get_height(formatting))
+            .fold(formatting.height as crate::DearMirlGuiCoordinateType, |a, b| {
+                if a > b { a } else { b }
+            })
+    }
+    fn get_width(
+        &mut self,
+        formatting: &crate::Formatting,
+    ) -> crate::DearMirlGuiCoordinateType {
+        let mut width = 0.0;
+        for idx in 0..N {
+            width += (formatting.horizontal_margin as f32).mul_add(
+                3.0,
+                self.get_label_width(idx, formatting)
+                    + self.components[idx].get_width(formatting) as f32,
+            );
+        }
+        (width + self.get_preview_size(formatting))
+            as crate::DearMirlGuiCoordinateType
+    }
+    #[allow(clippy::too_many_lines)]
+    fn update(&mut self, info: &crate::ModuleUpdateInfo) -> crate::GuiOutput {
+        let formatting = &get_formatting();
+        let layout = self.get_layout(formatting);
+        let height = self.get_height(formatting) as f32;
+        let mut output = crate::GuiOutput::empty();
+
+        // Tab/Shift + Tab between the components
+        let tab_pressed = self
+            .last_keys_pressed
+            .get_old_items(info.pressed_keys)
+            .contains(&&KeyCode::Tab);
+        self.last_keys_pressed.clone_from(info.pressed_keys);
+        if N > 1
+            && tab_pressed
+            && let Some(current) = self.get_selected_component(info.container_id)
+        {
+            let shift_down = info.pressed_keys.contains(&KeyCode::LeftShift)
+                || info.pressed_keys.contains(&KeyCode::RightShift);
+            let next = if shift_down {
+                (current + N - 1) % N
+            } else {
+                (current + 1) % N
+            };
+            self.move_selection(current, next, info.container_id);
+            output.focus_taken = FocusTaken::FunctionallyTaken;
+        }
+
+        // Scrubbing by dragging a label
+        if self.scrubbing == info.container_id {
+            if info.mouse_info.left.down {
+                self.scrub_distance += info.mouse_pos_delta.0;
+                self.apply_scrub();
+                output.focus_taken = FocusTaken::FunctionallyTaken;
+                output.new_cursor_style = Some(CursorStyle::ResizeHorizontally);
+            } else {
+                self.scrubbing = 0;
+                self.needs_redraw = true;
+            }
+        } else if let Some(mouse_pos) = info.mouse_pos
+            && !info.focus_taken.is_focus_taken()
+            && mouse_pos.1 >= 0.0
+            && mouse_pos.1 < height
+        {
+            for (idx, (label_x, input_x)) in layout.iter().enumerate() {
+                if mouse_pos.0 < *label_x || mouse_pos.0 >= *input_x {
+                    continue;
+                }
+                output.new_cursor_style = Some(CursorStyle::ResizeHorizontally);
+                if info.mouse_info.left.clicked && self.scrubbing == 0 {
+                    self.scrubbing = info.container_id;
+                    self.scrubbed_component = idx;
+                    self.scrub_distance = 0.0;
+                    self.scrub_origin = self
+                        .components
+                        .iter()
+                        .map(|component| {
+                            f64::try_from_value(component.number.clone())
+                                .unwrap_or_default()
+                        })
+                        .collect();
+                    for component in &mut self.components {
+                        if component.selected == info.container_id {
+                            component.selected = 0;
+                            component.needs_redraw = true;
+                        }
+                    }
+                    output.focus_taken = FocusTaken::FunctionallyTaken;
+                    self.needs_redraw = true;
+                }
+                break;
+            }
+        }
+
+        // Let each component handle itself with a localized mouse position
+        // The focus taken above is not passed on as it would deselect the component Tab just selected
+        let mut component_info = *info;
+        for (idx, (_, input_x)) in layout.into_iter().enumerate() {
+            component_info.mouse_pos =
+                info.mouse_pos.map(|(x, y)| (x - input_x, y));
+            let component_output = self.components[idx].update(&component_info);
+            component_info.focus_taken |= component_output.focus_taken;
+            output |= component_output;
+        }
+        output
+    }
+    fn need_redraw(&mut self) -> bool {
+        let mut needs_redraw = self.needs_redraw;
+        for component in &mut self.components {
+            // Every component has to be asked so none keep a stale flag
+            needs_redraw |= component.need_redraw();
+        }
+        self.needs_redraw = false;
+        needs_redraw
+    }
+    fn set_need_redraw(&mut self, need_redraw: Vec<(usize, bool)>) {
+        self.needs_redraw = super::misc::determine_need_redraw(need_redraw);
+    }
+    fn apply_new_formatting(&mut self, formatting: &crate::Formatting) {
+        for component in &mut self.components {
+            component.apply_new_formatting(formatting);
+        }
+        self.needs_redraw = true;
+    }
+}
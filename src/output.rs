@@ -20,6 +20,10 @@ pub struct GuiOutput {
     pub text_input_selected: bool,
     /// If a module would like to access what is currently stored in the clipboard
     pub request_clipboard_data: bool,
+    /// The id of the menu item the user clicked, see [`MenuBar`](crate::modules::MenuBar)
+    pub selected_menu_item: Option<String>,
+    /// What happened to the windows this update, see [`WindowEvent`](crate::WindowEvent)
+    pub window_events: Vec<crate::WindowEvent>,
 }
 impl GuiOutput {
     /// Compare two outputs and supplement missing values
@@ -40,6 +44,12 @@ impl GuiOutput {
                 || rhs.text_input_selected,
             request_clipboard_data: self.request_clipboard_data
                 || rhs.request_clipboard_data,
+            selected_menu_item: self
+                .selected_menu_item
+                .clone()
+                .or(rhs.selected_menu_item),
+            window_events: [self.window_events.clone(), rhs.window_events]
+                .concat(),
         }
     }
 }
@@ -71,6 +81,8 @@ impl GuiOutput {
             new_clipboard_data: None,
             text_input_selected: false,
             request_clipboard_data: false,
+            selected_menu_item: None,
+            window_events: Vec::new(),
         }
     }
     #[must_use]
@@ -150,6 +162,20 @@ impl GuiOutput {
         self.request_clipboard_data = request;
         self
     }
+
+    #[must_use]
+    /// Set the id of the menu item the user clicked
+    pub fn with_selected_menu_item(mut self, id: Option<String>) -> Self {
+        self.selected_menu_item = id;
+        self
+    }
+
+    #[must_use]
+    /// Add an event that happened to a window
+    pub fn with_window_event(mut self, event: crate::WindowEvent) -> Self {
+        self.window_events.push(event);
+        self
+    }
 }
 impl std::error::Error for GuiReturnModuleError {}
 
@@ -1,16 +1,19 @@
 pub use crate::{
     AnyCasting, DearMirlGuiModule, FocusTaken, WhatAmI,
     formatting::Formatting,
-    gui::DearMirlGui,
+    gui::{DearMirlGui, DearMirlGuiBuilder, WindowStacking},
     module_manager::{
         get_formatting, get_module_as, get_module_as_mut, register_module,
-        set_formatting,
+        set_context_menu, set_formatting,
     },
     module_path::ModulePath,
     modules,
     modules::{
-        button::ButtonModulePathSupport, check_box::CheckBoxPathSupport,
+        button::ButtonModulePathSupport,
+        check_box::CheckBoxPathSupport,
+        vector_input::{RgbaInputPathSupport, VectorInputPathSupport},
     },
     output::GuiOutput,
+    theme::Theme,
     window_manager::DearMirlGuiManager,
 };
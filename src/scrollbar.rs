@@ -0,0 +1,317 @@
+use mirl::{
+    misc::ScrollableCamera,
+    platform::CursorStyle,
+    render::{self, Buffer},
+};
+
+use crate::{FocusTaken, GuiOutput, ModuleUpdateInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+/// The direction a scrollbar scrolls in
+pub enum ScrollbarAxis {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Scrollbars along the right and bottom edge of a [`ScrollableCamera`]s container
+///
+/// The thumbs can be dragged, clicking the track besides a thumb scrolls by a whole page
+pub struct Scrollbars {
+    /// If the vertical scrollbar is shown
+    pub vertical: bool,
+    /// If the horizontal scrollbar is shown
+    pub horizontal: bool,
+    /// Hide the scrollbars while the content fits into the container
+    pub auto_hide: bool,
+    /// The width of the vertical and height of the horizontal scrollbar
+    pub thickness: usize,
+    /// The thumb doesn't get smaller than this, even for very long content
+    pub min_thumb_length: usize,
+    /// The scrollbar being dragged and where on the thumb it has been grabbed
+    pub dragging: Option<(ScrollbarAxis, f32)>,
+    /// The scrollbar the mouse is above
+    pub hovered: Option<ScrollbarAxis>,
+}
+impl Default for Scrollbars {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Scrollbars {
+    /// The default width/height of the scrollbars
+    pub const DEFAULT_THICKNESS: usize = 8;
+    /// The default smallest length of the thumb
+    pub const DEFAULT_MIN_THUMB_LENGTH: usize = 16;
+    /// Both scrollbars, hidden while the content fits
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            vertical: true,
+            horizontal: true,
+            auto_hide: true,
+            thickness: Self::DEFAULT_THICKNESS,
+            min_thumb_length: Self::DEFAULT_MIN_THUMB_LENGTH,
+            dragging: None,
+            hovered: None,
+        }
+    }
+    /// No scrollbars at all
+    #[must_use]
+    pub const fn none() -> Self {
+        let mut scrollbars = Self::new();
+        scrollbars.vertical = false;
+        scrollbars.horizontal = false;
+        scrollbars
+    }
+    /// Quick creation setter for which scrollbars are shown
+    #[must_use]
+    pub const fn with_axes(mut self, vertical: bool, horizontal: bool) -> Self {
+        self.vertical = vertical;
+        self.horizontal = horizontal;
+        self
+    }
+    /// Quick creation setter for always showing the enabled scrollbars
+    #[must_use]
+    pub const fn always_shown(mut self) -> Self {
+        self.auto_hide = false;
+        self
+    }
+    /// Quick creation setter for the width/height of the scrollbars
+    #[must_use]
+    pub const fn with_thickness(mut self, thickness: usize) -> Self {
+        self.thickness = thickness;
+        self
+    }
+    /// Check if the scrollbar is currently shown
+    #[must_use]
+    pub fn is_shown(
+        &self,
+        camera: &ScrollableCamera,
+        axis: ScrollbarAxis,
+    ) -> bool {
+        let (enabled, content, container) = match axis {
+            ScrollbarAxis::Vertical => {
+                (self.vertical, camera.content_height, camera.container_height)
+            }
+            ScrollbarAxis::Horizontal => {
+                (self.horizontal, camera.content_width, camera.container_width)
+            }
+        };
+        enabled && (!self.auto_hide || content > container)
+    }
+    /// The position and size of the track inside the container
+    fn get_track(
+        &self,
+        camera: &ScrollableCamera,
+        axis: ScrollbarAxis,
+    ) -> ((f32, f32), (f32, f32)) {
+        let thickness = self.thickness as f32;
+        // The corner is left free when both are shown
+        let other_axis = match axis {
+            ScrollbarAxis::Vertical => ScrollbarAxis::Horizontal,
+            ScrollbarAxis::Horizontal => ScrollbarAxis::Vertical,
+        };
+        let corner =
+            if self.is_shown(camera, other_axis) { thickness } else { 0.0 };
+        match axis {
+            ScrollbarAxis::Vertical => (
+                (camera.container_width - thickness, 0.0),
+                (thickness, (camera.container_height - corner).max(0.0)),
+            ),
+            ScrollbarAxis::Horizontal => (
+                (0.0, camera.container_height - thickness),
+                ((camera.container_width - corner).max(0.0), thickness),
+            ),
+        }
+    }
+    /// How far the content can be scrolled, the container size and the current offset along the axis
+    const fn get_scroll_metrics(
+        camera: &ScrollableCamera,
+        axis: ScrollbarAxis,
+    ) -> (f32, f32, f32) {
+        match axis {
+            ScrollbarAxis::Vertical => (
+                camera.content_height - camera.container_height,
+                camera.container_height,
+                camera.offset_y,
+            ),
+            ScrollbarAxis::Horizontal => (
+                camera.content_width - camera.container_width,
+                camera.container_width,
+                camera.offset_x,
+            ),
+        }
+    }
+    /// The start and length of the thumb along the track
+    fn get_thumb(
+        &self,
+        camera: &ScrollableCamera,
+        axis: ScrollbarAxis,
+        track_length: f32,
+    ) -> (f32, f32) {
+        let (scrollable, container, offset) =
+            Self::get_scroll_metrics(camera, axis);
+        if scrollable <= 0.0 {
+            return (0.0, track_length);
+        }
+        let length = (track_length * container / (scrollable + container))
+            .max(self.min_thumb_length as f32)
+            .min(track_length);
+        // The offset is negative when scrolled
+        let progress = (-offset / scrollable).clamp(0.0, 1.0);
+        (progress * (track_length - length), length)
+    }
+    fn set_offset(
+        camera: &mut ScrollableCamera,
+        axis: ScrollbarAxis,
+        offset: f32,
+    ) {
+        match axis {
+            ScrollbarAxis::Vertical => camera.offset_y = offset,
+            ScrollbarAxis::Horizontal => camera.offset_x = offset,
+        }
+        camera.clamp_to_bounds();
+    }
+    /// Handle dragging the thumbs and clicking the tracks, the mouse position has to be relative to the container
+    ///
+    /// The container and content sizes of the camera have to be up to date
+    pub fn update(
+        &mut self,
+        camera: &mut ScrollableCamera,
+        mouse_pos: Option<(f32, f32)>,
+        info: &ModuleUpdateInfo,
+    ) -> GuiOutput {
+        self.hovered = None;
+        let Some(mouse_pos) = mouse_pos else {
+            self.dragging = None;
+            return GuiOutput::empty();
+        };
+        let along = |axis: ScrollbarAxis, pos: (f32, f32)| match axis {
+            ScrollbarAxis::Vertical => pos.1,
+            ScrollbarAxis::Horizontal => pos.0,
+        };
+        if let Some((axis, grabbed_at)) = self.dragging {
+            if !info.mouse_info.left.down {
+                self.dragging = None;
+                return GuiOutput::empty();
+            }
+            let (track_pos, track_size) = self.get_track(camera, axis);
+            let track_length = along(axis, track_size);
+            let (_, thumb_length) = self.get_thumb(camera, axis, track_length);
+            let (scrollable, _, _) = Self::get_scroll_metrics(camera, axis);
+            let free_length = track_length - thumb_length;
+            if free_length > 0.0 {
+                let thumb_start = along(axis, mouse_pos)
+                    - along(axis, track_pos)
+                    - grabbed_at;
+                let progress = (thumb_start / free_length).clamp(0.0, 1.0);
+                Self::set_offset(camera, axis, -progress * scrollable);
+            }
+            self.hovered = Some(axis);
+            return GuiOutput::default(FocusTaken::FunctionallyTaken)
+                .with_cursor(CursorStyle::Default);
+        }
+        if info.focus_taken.is_focus_taken() {
+            return GuiOutput::empty();
+        }
+        for axis in [ScrollbarAxis::Vertical, ScrollbarAxis::Horizontal] {
+            if !self.is_shown(camera, axis) {
+                continue;
+            }
+            let (track_pos, track_size) = self.get_track(camera, axis);
+            let local = (mouse_pos.0 - track_pos.0, mouse_pos.1 - track_pos.1);
+            if local.0 < 0.0
+                || local.1 < 0.0
+                || local.0 >= track_size.0
+                || local.1 >= track_size.1
+            {
+                continue;
+            }
+            self.hovered = Some(axis);
+            if !info.mouse_info.left.clicked {
+                return GuiOutput::default(FocusTaken::VisuallyTaken)
+                    .with_cursor(CursorStyle::Default);
+            }
+            let (thumb_start, thumb_length) =
+                self.get_thumb(camera, axis, along(axis, track_size));
+            let clicked_at = along(axis, local);
+            if clicked_at < thumb_start {
+                // Page towards the click
+                let (_, container, offset) =
+                    Self::get_scroll_metrics(camera, axis);
+                Self::set_offset(camera, axis, offset + container);
+            } else if clicked_at >= thumb_start + thumb_length {
+                let (_, container, offset) =
+                    Self::get_scroll_metrics(camera, axis);
+                Self::set_offset(camera, axis, offset - container);
+            } else {
+                self.dragging = Some((axis, clicked_at - thumb_start));
+            }
+            return GuiOutput::default(FocusTaken::FunctionallyTaken)
+                .with_cursor(CursorStyle::Default);
+        }
+        GuiOutput::empty()
+    }
+    /// Draw the shown scrollbars, `position` being the top left corner of the container on the buffer
+    pub fn draw(
+        &self,
+        buffer: &mut Buffer,
+        camera: &ScrollableCamera,
+        position: (isize, isize),
+        formatting: &crate::Formatting,
+    ) {
+        for axis in [ScrollbarAxis::Vertical, ScrollbarAxis::Horizontal] {
+            if !self.is_shown(camera, axis) {
+                continue;
+            }
+            let (track_pos, track_size) = self.get_track(camera, axis);
+            let track_pos = (
+                position.0 + track_pos.0 as isize,
+                position.1 + track_pos.1 as isize,
+            );
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                track_pos,
+                (track_size.0 as isize, track_size.1 as isize),
+                mirl::graphics::adjust_brightness_hsl_of_rgb(
+                    formatting.background_color,
+                    -10.0,
+                ),
+            );
+            let thumb_color =
+                if self.dragging.is_some_and(|(dragged, _)| dragged == axis) {
+                    formatting.theme.active_color
+                } else if self.hovered == Some(axis) {
+                    formatting.theme.hover_color
+                } else {
+                    formatting.foreground_color
+                };
+            let (pos, size) = match axis {
+                ScrollbarAxis::Vertical => {
+                    let (start, length) =
+                        self.get_thumb(camera, axis, track_size.1);
+                    (
+                        (track_pos.0, track_pos.1 + start as isize),
+                        (track_size.0 as isize, length as isize),
+                    )
+                }
+                ScrollbarAxis::Horizontal => {
+                    let (start, length) =
+                        self.get_thumb(camera, axis, track_size.0);
+                    (
+                        (track_pos.0 + start as isize, track_pos.1),
+                        (length as isize, track_size.1 as isize),
+                    )
+                }
+            };
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                pos,
+                size,
+                thumb_color,
+            );
+        }
+    }
+}
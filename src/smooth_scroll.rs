@@ -0,0 +1,342 @@
+use mirl::{misc::ScrollableCamera, platform::CursorStyle};
+
+use crate::{FocusTaken, GuiOutput, ModuleUpdateInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Animated scrolling for a [`ScrollableCamera`]
+///
+/// Eases towards the scrolled to offset instead of jumping, keeps moving after dragging the content with the middle mouse button and bounces back when scrolled past the content
+///
+/// Driven by the delta time given to the update functions, a delta time of 0 jumps directly to the target
+pub struct SmoothScroll {
+    /// If scrolling is animated, the camera is moved directly when disabled
+    pub enabled: bool,
+    /// How fast the offset approaches the target, higher is faster
+    ///
+    /// Each second the remaining distance shrinks to `e^-speed` of itself
+    pub speed: f32,
+    /// The offset the camera is currently moving towards
+    pub target: Option<(f32, f32)>,
+    /// If the content can be dragged with the middle mouse button and keeps moving after being let go
+    pub kinetic: bool,
+    /// The current speed of the content in pixels per second
+    pub velocity: (f32, f32),
+    /// How fast the velocity decays, each second the velocity shrinks to `e^-friction` of itself
+    pub friction: f32,
+    /// If scrolling past the content is allowed briefly before bouncing back
+    pub overscroll: bool,
+    /// How far the content can be scrolled past its bounds in pixels
+    pub max_overscroll: f32,
+    /// If the content is currently being dragged
+    pub dragging: bool,
+}
+impl Default for SmoothScroll {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl SmoothScroll {
+    /// The default of [`Self::speed`]
+    pub const DEFAULT_SPEED: f32 = 15.0;
+    /// The default of [`Self::friction`]
+    pub const DEFAULT_FRICTION: f32 = 4.0;
+    /// The default of [`Self::max_overscroll`]
+    pub const DEFAULT_MAX_OVERSCROLL: f32 = 40.0;
+    /// Below this distance in pixels the camera snaps to the target
+    pub const SNAP_DISTANCE: f32 = 0.5;
+    /// Below this velocity in pixels per second kinetic scrolling stops
+    pub const MIN_VELOCITY: f32 = 5.0;
+    /// Animated, kinetic scrolling with overscroll
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: true,
+            speed: Self::DEFAULT_SPEED,
+            target: None,
+            kinetic: true,
+            velocity: (0.0, 0.0),
+            friction: Self::DEFAULT_FRICTION,
+            overscroll: true,
+            max_overscroll: Self::DEFAULT_MAX_OVERSCROLL,
+            dragging: false,
+        }
+    }
+    /// No animations, scrolling moves the camera directly
+    #[must_use]
+    pub const fn disabled() -> Self {
+        let mut smooth_scroll = Self::new();
+        smooth_scroll.enabled = false;
+        smooth_scroll
+    }
+    /// Quick creation setter for how fast the camera approaches the target
+    #[must_use]
+    pub const fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    /// Quick creation setter for kinetic dragging and how fast the velocity decays
+    #[must_use]
+    pub const fn with_kinetic(mut self, kinetic: bool, friction: f32) -> Self {
+        self.kinetic = kinetic;
+        self.friction = friction;
+        self
+    }
+    /// Quick creation setter for scrolling past the content and how far
+    #[must_use]
+    pub const fn with_overscroll(
+        mut self,
+        overscroll: bool,
+        max_overscroll: f32,
+    ) -> Self {
+        self.overscroll = overscroll;
+        self.max_overscroll = max_overscroll;
+        self
+    }
+    /// Check if the camera is still moving on its own
+    #[must_use]
+    pub const fn is_animating(&self) -> bool {
+        self.target.is_some() || self.velocity != (0.0, 0.0)
+    }
+    /// Stop all movement, the camera stays where it is
+    pub const fn stop(&mut self) {
+        self.target = None;
+        self.velocity = (0.0, 0.0);
+    }
+    /// The smallest and biggest allowed offset on each axis: `((min_x, max_x), (min_y, max_y))`
+    #[must_use]
+    pub fn get_bounds(camera: &ScrollableCamera) -> ((f32, f32), (f32, f32)) {
+        let mut min = *camera;
+        min.offset_x = f32::MIN;
+        min.offset_y = f32::MIN;
+        min.clamp_to_bounds();
+        let mut max = *camera;
+        max.offset_x = f32::MAX;
+        max.offset_y = f32::MAX;
+        max.clamp_to_bounds();
+        ((min.offset_x, max.offset_x), (min.offset_y, max.offset_y))
+    }
+    /// Clamp the offset to the bounds of the camera, extended on each side by `extra`
+    fn clamp_offset(
+        camera: &ScrollableCamera,
+        offset: (f32, f32),
+        extra: f32,
+    ) -> (f32, f32) {
+        let ((min_x, max_x), (min_y, max_y)) = Self::get_bounds(camera);
+        (
+            offset.0.clamp(min_x - extra, max_x + extra),
+            offset.1.clamp(min_y - extra, max_y + extra),
+        )
+    }
+    const fn get_overscroll_extra(&self) -> f32 {
+        if self.overscroll { self.max_overscroll } else { 0.0 }
+    }
+    /// By how much [`ScrollableCamera::scroll`] would move the camera, ignoring the bounds
+    #[must_use]
+    pub const fn get_scroll_delta(
+        camera: &ScrollableCamera,
+        by: (f32, f32),
+        vertical: bool,
+    ) -> (f32, f32) {
+        if vertical {
+            (
+                by.0 * camera.scroll_multiplier_x,
+                by.1 * camera.scroll_multiplier_y,
+            )
+        } else if !camera.horizontal_context_switch_multipliers {
+            (
+                by.1 * camera.scroll_multiplier_y,
+                by.0 * camera.scroll_multiplier_x,
+            )
+        } else {
+            (
+                by.1 * camera.scroll_multiplier_x,
+                by.0 * camera.scroll_multiplier_y,
+            )
+        }
+    }
+    /// Scroll the camera like [`ScrollableCamera::scroll`], animated when enabled
+    ///
+    /// Consecutive scrolls add up, scrolling past the content bounces back when overscroll is enabled
+    pub fn scroll(
+        &mut self,
+        camera: &mut ScrollableCamera,
+        by: (f32, f32),
+        vertical: bool,
+    ) {
+        if !self.enabled {
+            camera.scroll(by, vertical);
+            return;
+        }
+        let delta = Self::get_scroll_delta(camera, by, vertical);
+        let from = self.target.unwrap_or((camera.offset_x, camera.offset_y));
+        self.velocity = (0.0, 0.0);
+        self.target = Some(Self::clamp_offset(
+            camera,
+            (from.0 + delta.0, from.1 + delta.1),
+            self.get_overscroll_extra(),
+        ));
+    }
+    /// Move the camera to the given offset, animated when enabled
+    pub fn scroll_to(
+        &mut self,
+        camera: &mut ScrollableCamera,
+        offset: (f32, f32),
+    ) {
+        self.velocity = (0.0, 0.0);
+        if self.enabled {
+            self.target = Some(Self::clamp_offset(camera, offset, 0.0));
+        } else {
+            self.target = None;
+            camera.offset_x = offset.0;
+            camera.offset_y = offset.1;
+            camera.clamp_to_bounds();
+        }
+    }
+    /// Handle dragging the content with the middle mouse button, the mouse position has to be relative to the container
+    ///
+    /// The container and content sizes of the camera have to be up to date
+    pub fn update_dragging(
+        &mut self,
+        camera: &mut ScrollableCamera,
+        mouse_pos: Option<(f32, f32)>,
+        info: &ModuleUpdateInfo,
+    ) -> GuiOutput {
+        if !(self.enabled && self.kinetic) {
+            self.dragging = false;
+            return GuiOutput::empty();
+        }
+        if !self.dragging {
+            let over_container = mouse_pos.is_some_and(|pos| {
+                pos.0 >= 0.0
+                    && pos.1 >= 0.0
+                    && pos.0 < camera.container_width
+                    && pos.1 < camera.container_height
+            });
+            if !over_container
+                || info.focus_taken.is_focus_taken()
+                || !info.mouse_info.middle.clicked
+            {
+                return GuiOutput::empty();
+            }
+            self.dragging = true;
+            self.stop();
+        }
+        if !info.mouse_info.middle.down || mouse_pos.is_none() {
+            // Let go, the velocity of the last frames carries on
+            self.dragging = false;
+            return GuiOutput::empty();
+        }
+        let delta = info.mouse_pos_delta;
+        let ((min_x, max_x), (min_y, max_y)) = Self::get_bounds(camera);
+        // Dragging past the content only moves it half as far
+        let resistance = |offset: f32, min: f32, max: f32, delta: f32| {
+            if (offset <= min && delta < 0.0) || (offset >= max && delta > 0.0)
+            {
+                delta * 0.5
+            } else {
+                delta
+            }
+        };
+        let delta = (
+            resistance(camera.offset_x, min_x, max_x, delta.0),
+            resistance(camera.offset_y, min_y, max_y, delta.1),
+        );
+        let extra = self.get_overscroll_extra();
+        (camera.offset_x, camera.offset_y) = Self::clamp_offset(
+            camera,
+            (camera.offset_x + delta.0, camera.offset_y + delta.1),
+            extra,
+        );
+        if info.delta_time > 0.0 {
+            let delta_time = info.delta_time as f32;
+            // Smoothed so a single jittery frame doesn't decide the flick
+            self.velocity = (
+                self.velocity.0.mul_add(0.5, delta.0 / delta_time * 0.5),
+                self.velocity.1.mul_add(0.5, delta.1 / delta_time * 0.5),
+            );
+        }
+        GuiOutput::default(FocusTaken::FunctionallyTaken)
+            .with_cursor(CursorStyle::AllScroll)
+    }
+    /// Advance the animation, returns if the camera moved
+    ///
+    /// The container and content sizes of the camera have to be up to date
+    #[allow(clippy::float_cmp)] // Clamped values are compared, not calculated ones
+    pub fn update(
+        &mut self,
+        camera: &mut ScrollableCamera,
+        delta_time: f32,
+    ) -> bool {
+        if !self.enabled {
+            self.stop();
+            return false;
+        }
+        if self.dragging {
+            return false;
+        }
+        let before = (camera.offset_x, camera.offset_y);
+        let extra = self.get_overscroll_extra();
+        if self.velocity != (0.0, 0.0) {
+            if delta_time <= 0.0 {
+                self.velocity = (0.0, 0.0);
+            } else {
+                let moved = Self::clamp_offset(
+                    camera,
+                    (
+                        self.velocity.0.mul_add(delta_time, camera.offset_x),
+                        self.velocity.1.mul_add(delta_time, camera.offset_y),
+                    ),
+                    extra,
+                );
+                let bounded = Self::clamp_offset(camera, moved, 0.0);
+                // Past the content the velocity is eaten up a lot faster
+                let friction_x = if moved.0 == bounded.0 { 1.0 } else { 4.0 };
+                let friction_y = if moved.1 == bounded.1 { 1.0 } else { 4.0 };
+                self.velocity.0 *=
+                    (-self.friction * friction_x * delta_time).exp();
+                self.velocity.1 *=
+                    (-self.friction * friction_y * delta_time).exp();
+                if moved.0 == camera.offset_x {
+                    self.velocity.0 = 0.0;
+                }
+                if moved.1 == camera.offset_y {
+                    self.velocity.1 = 0.0;
+                }
+                (camera.offset_x, camera.offset_y) = moved;
+                if self.velocity.0.hypot(self.velocity.1) < Self::MIN_VELOCITY {
+                    self.velocity = (0.0, 0.0);
+                }
+            }
+        }
+        if self.velocity == (0.0, 0.0) && self.target.is_none() {
+            // Bounce back once the content came to a rest outside of its bounds
+            let bounded = Self::clamp_offset(
+                camera,
+                (camera.offset_x, camera.offset_y),
+                0.0,
+            );
+            if bounded != (camera.offset_x, camera.offset_y) {
+                self.target = Some(bounded);
+            }
+        }
+        if let Some(target) = self.target {
+            // The bounds might have changed since the target was set
+            let target = Self::clamp_offset(camera, target, extra);
+            let factor = if delta_time <= 0.0 {
+                1.0
+            } else {
+                1.0 - (-self.speed * delta_time).exp()
+            };
+            camera.offset_x += (target.0 - camera.offset_x) * factor;
+            camera.offset_y += (target.1 - camera.offset_y) * factor;
+            if (target.0 - camera.offset_x).hypot(target.1 - camera.offset_y)
+                < Self::SNAP_DISTANCE
+            {
+                (camera.offset_x, camera.offset_y) = target;
+                let bounded = Self::clamp_offset(camera, target, 0.0);
+                self.target = (bounded != target).then_some(bounded);
+            }
+        }
+        before != (camera.offset_x, camera.offset_y)
+    }
+}
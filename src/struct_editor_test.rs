@@ -14,7 +14,7 @@ pub struct TestConfig {
 
 #[derive(Debug, Clone, Inspectable, Default, PartialEq)]
 pub struct NestedSettings {
-    //pub timeout_ms: u64,
+    pub timeout_ms: u64,
     pub retry_count: u8,
     pub priority: f32,
 }
@@ -57,7 +57,7 @@ pub fn create_test_struct() -> TestConfig {
         count: 42,
         ratio: std::f64::consts::PI,
         nested: NestedSettings {
-            //timeout_ms: 5000,
+            timeout_ms: 5000,
             retry_count: 3,
             priority: 0.75,
         },
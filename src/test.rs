@@ -45,7 +45,16 @@ fn main_loop<
     #[cfg(feature = "experimental")]
     let struct_editor = register_module(
         // "struct_editor",
-        modules::StructEditor::new(&editable_struct).unwrap(),
+        modules::StructEditor::new(&editable_struct)
+            .unwrap()
+            .with_filter_box()
+            .with_max_height(300)
+            .with_field_callback("nested", |change| {
+                println!(
+                    "{} changed: {:?} -> {:?}",
+                    change.path, change.old, change.new
+                );
+            }),
     )
     .with_name("Struct Editor".to_string());
 
@@ -65,6 +74,32 @@ fn main_loop<
         modules::Slider::<f64, f64>::new(None, true, None).unwrap(),
     )
     .with_name("Slider");
+    let gain_slider = register_module(
+        modules::Slider::<f32, f32>::new(None, false, Some(0.01..10.0))
+            .unwrap()
+            .with_width(200)
+            .with_mapping(modules::SliderMapping::Logarithmic)
+            .with_ticks(4, true)
+            .with_value_label(true),
+    );
+    let vertical_slider = register_module(
+        modules::Slider::<f32, i32>::new(None, false, Some(0..10))
+            .unwrap()
+            .with_width(120)
+            .with_step(1)
+            .with_ticks(11, true)
+            .with_vertical(true),
+    );
+    let range_slider = register_module(
+        modules::RangeSlider::from_slider(
+            modules::Slider::<f32, f32>::new(Some(0.25), false, Some(0.0..100.0))
+                .unwrap()
+                .with_width(200)
+                .with_step(5.0)
+                .with_ticks(5, true),
+            0.75,
+        ),
+    );
     let progress_bar_up = register_module(
         // "progress_bar_up",
         modules::ProgressBar::new(None, false),
@@ -87,6 +122,17 @@ fn main_loop<
         ),
     )
     .with_name("Button");
+    set_context_menu(
+        &button,
+        Some(
+            modules::Menu::new("")
+                .with_item(modules::MenuItem::new("button_rename", "Rename"))
+                .with_item(
+                    modules::MenuItem::new("button_log", "Log presses")
+                        .checkable(true),
+                ),
+        ),
+    );
     let button2 = register_module(
         //"button2",
         modules::Button::new(
@@ -187,10 +233,52 @@ fn main_loop<
         //"lever3",
         modules::NumberInput::new(10).with_width(100),
     );
+    let vector_input = register_module(modules::Vec3Input::new([1.0, 2.0, 3.0]));
+    let color_input = register_module(modules::RgbaInput::from_color(
+        mirl::graphics::rgba_to_u32(255, 100, 0, 255),
+    ));
 
     // let display =
     //     register_module("display", modules::NumberDisplay::new(0, 3, 20.0));
 
+    let toolbar_button = register_module(
+        modules::Button::new("Toolbar".into()).with_interaction_function(
+            |state| {
+                if state.clicked {
+                    println!("Toolbar button pressed");
+                }
+            },
+        ),
+    )
+    .with_name("Toolbar button");
+    let menu_bar = register_module(modules::MenuBar::new(Vec::from([
+        modules::Menu::new("File")
+            .with_item(
+                modules::MenuItem::new("new", "New").with_shortcut("Ctrl+N"),
+            )
+            .with_item(
+                modules::MenuItem::new("save", "Save").with_shortcut("Ctrl+S"),
+            )
+            .with_separator()
+            .with_submenu(
+                modules::Menu::new("Recent")
+                    .with_item(modules::MenuItem::new("recent_1", "scene.ron"))
+                    .with_item(
+                        modules::MenuItem::new("recent_2", "old.ron")
+                            .disabled(),
+                    ),
+            )
+            .with_separator()
+            .with_item(modules::MenuItem::new("quit", "Quit")),
+        modules::Menu::new("View")
+            .with_item(
+                modules::MenuItem::new("grid", "Show grid").checkable(true),
+            )
+            .with_item(
+                modules::MenuItem::new("fps", "Show fps").checkable(false),
+            ),
+    ])))
+    .with_name("Menu bar");
     let crank_info = register_module(
         //"crank_info",
         modules::TextDisplay::new("0"),
@@ -245,6 +333,15 @@ fn main_loop<
                             (0.into_value(), 0.into_value()),
                             &Vec::from([text_input.id(), m1.id()]),
                         )
+                        .with_formatting(
+                            Formatting::default(&font, 16)
+                                .background_color(mirl::graphics::rgba_to_u32(
+                                    32, 40, 48, 255,
+                                ))
+                                .foreground_color(mirl::graphics::rgba_to_u32(
+                                    64, 80, 96, 255,
+                                )),
+                        )
                         .collapsed(),
                     )
                     .id(),
@@ -253,15 +350,19 @@ fn main_loop<
                     crank_info.id(),
                     crank.id(),
                     number_input.id(),
+                    vector_input.id(),
+                    color_input.id(),
                     //text_input.id(),
                     #[cfg(feature = "experimental")]
                     struct_editor.id(),
                 ],
-            ),
-            DearMirlGui::new_simple(
-                "Another Window",
-                (550.into_value(), 280.into_value()),
-                &[
+            )
+            .with_toolbar_modules(&[menu_bar.id()])
+            .with_size_to_see_all(),
+            crate::DearMirlGuiBuilder::new("Another Window")
+                .with_position((550.into_value(), 280.into_value()))
+                .with_formatting(Formatting::light(&font, 20))
+                .with_modules(&[
                     m1.id(),
                     checkbox1.id(),
                     selection.id(),
@@ -277,9 +378,33 @@ fn main_loop<
                     lever2.id(),
                     same_line.id(),
                     lever3.id(),
-                ],
-            ),
-        ]));
+                    gain_slider.id(),
+                    range_slider.id(),
+                    vertical_slider.id(),
+                ])
+                .with_toolbar_modules(&[toolbar_button.id(), checkbox2.id()])
+                .with_context_menu(
+                    modules::Menu::new("").with_item(modules::MenuItem::new(
+                        "window_info",
+                        "Info",
+                    )),
+                )
+                .with_max_size((600, 500))
+                .with_auto_size(false, true)
+                .with_close_button(true)
+                .with_pin_button(true)
+                .with_title_bar_button(crate::TitleBarButton::new(
+                    "help",
+                    crate::TitleBarIcon::Text("?".to_string()),
+                ))
+                .build()
+                .unwrap(),
+        ]))
+        .with_layout_file("test_layout.ini")
+        .with_bounds(crate::DirtyRect::new(
+            (0, 0),
+            (buffer.width, buffer.height),
+        ));
 
     let cursor_style_manager = window
         .load_custom_cursors(
@@ -293,6 +418,7 @@ fn main_loop<
 
     let mut slider_animation: f64 = 0.0;
     let mut request_clipboard_data = false;
+    let mut quit_confirmation: Option<crate::ModalHandle> = None;
 
     while window.is_open() {
         ticker.tick();
@@ -400,6 +526,39 @@ fn main_loop<
         if !gui_output.focus_taken {
             // Do stuff that uses mouse/keyboard
         }
+        if let Some(id) = &gui_output.selected_menu_item {
+            println!("Menu item selected: {id}");
+            if id == "quit" {
+                quit_confirmation = Some(
+                    window_manager
+                        .confirm("Quit", "Do you really want to quit?"),
+                );
+            }
+        }
+        for event in &gui_output.window_events {
+            match event {
+                crate::WindowEvent::TitleBarButtonClicked {
+                    button, ..
+                } if button == "help" => {
+                    window_manager.message_box(
+                        "Help",
+                        "Drag the title bar to move the window",
+                    );
+                }
+                crate::WindowEvent::Closed(id) => {
+                    println!("Window closed: {id}");
+                }
+                _ => {}
+            }
+        }
+        if let Some(result) =
+            quit_confirmation.as_ref().and_then(crate::ModalHandle::poll)
+        {
+            quit_confirmation = None;
+            if result == crate::ModalResult::Ok {
+                break;
+            }
+        }
         if let Some(data) = &gui_output.new_clipboard_data {
             println!(
                 "New clipboard data has been provided: {}",
@@ -0,0 +1,231 @@
+use mirl::{graphics::rgba_to_u32, prelude::Buffer, render};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The colors of the states and roles of widgets and how their shapes are drawn, part of the [`Formatting`](crate::Formatting)
+///
+/// The background, foreground and text colors stay in the [`Formatting`](crate::Formatting), see [`Formatting::dark`](crate::Formatting::dark), [`Formatting::light`](crate::Formatting::light) and [`Formatting::high_contrast`](crate::Formatting::high_contrast) for complete presets
+pub struct Theme {
+    /// Elements the mouse is above
+    pub hover_color: u32,
+    /// Elements that are being pressed or dragged
+    pub active_color: u32,
+    /// Elements that can't be interacted with
+    pub disabled_color: u32,
+    /// The outline of elements
+    pub border_color: u32,
+    /// Filled parts like progress, checked boxes and selected options
+    pub accent_color: u32,
+    /// Selected text
+    pub selection_color: u32,
+    /// Invalid input and failures
+    pub error_color: u32,
+    /// How thick the outline of elements is in pixels, 0 = no outline
+    pub border_thickness: usize,
+    /// The radius of the corners of elements in pixels, 0 = square corners
+    pub corner_rounding: usize,
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+impl Theme {
+    /// Fits the default colors of the [`Formatting`](crate::Formatting)
+    #[must_use]
+    pub const fn dark() -> Self {
+        Self {
+            hover_color: rgba_to_u32(48, 36, 120, 255),
+            active_color: rgba_to_u32(60, 45, 150, 255),
+            disabled_color: rgba_to_u32(70, 70, 80, 255),
+            border_color: rgba_to_u32(80, 70, 160, 255),
+            accent_color: rgba_to_u32(100, 140, 255, 255),
+            selection_color: rgba_to_u32(30, 20, 200, 255),
+            error_color: rgba_to_u32(220, 50, 50, 255),
+            border_thickness: 0,
+            corner_rounding: 0,
+        }
+    }
+    /// Dark elements on a bright background
+    #[must_use]
+    pub const fn light() -> Self {
+        Self {
+            hover_color: rgba_to_u32(185, 190, 210, 255),
+            active_color: rgba_to_u32(165, 172, 200, 255),
+            disabled_color: rgba_to_u32(150, 150, 155, 255),
+            border_color: rgba_to_u32(140, 145, 165, 255),
+            accent_color: rgba_to_u32(40, 100, 220, 255),
+            selection_color: rgba_to_u32(150, 180, 255, 255),
+            error_color: rgba_to_u32(200, 30, 30, 255),
+            border_thickness: 1,
+            corner_rounding: 3,
+        }
+    }
+    /// Thick white outlines and saturated colors on black
+    #[must_use]
+    pub const fn high_contrast() -> Self {
+        Self {
+            hover_color: rgba_to_u32(40, 40, 40, 255),
+            active_color: rgba_to_u32(80, 80, 0, 255),
+            disabled_color: rgba_to_u32(128, 128, 128, 255),
+            border_color: mirl::graphics::colors::WHITE,
+            accent_color: rgba_to_u32(255, 255, 0, 255),
+            selection_color: rgba_to_u32(0, 120, 215, 255),
+            error_color: rgba_to_u32(255, 80, 80, 255),
+            border_thickness: 2,
+            corner_rounding: 0,
+        }
+    }
+    /// Quick creation setter for the outline thickness
+    #[must_use]
+    pub const fn with_border_thickness(mut self, thickness: usize) -> Self {
+        self.border_thickness = thickness;
+        self
+    }
+    /// Quick creation setter for the corner radius
+    #[must_use]
+    pub const fn with_corner_rounding(mut self, rounding: usize) -> Self {
+        self.corner_rounding = rounding;
+        self
+    }
+    /// Draw a filled rectangle with the outline and corner rounding of the theme
+    pub fn draw_rectangle(
+        &self,
+        buffer: &mut Buffer,
+        pos: (isize, isize),
+        size: (isize, isize),
+        color: u32,
+    ) {
+        let thickness =
+            (self.border_thickness as isize).min(size.0 / 2).min(size.1 / 2);
+        let radius = self.corner_rounding as isize;
+        if thickness <= 0 {
+            draw_rounded_rectangle(buffer, pos, size, radius, color);
+            return;
+        }
+        // The fill is drawn over the outline, leaving only its edge visible
+        draw_rounded_rectangle(buffer, pos, size, radius, self.border_color);
+        let inner_size = (size.0 - thickness * 2, size.1 - thickness * 2);
+        if inner_size.0 <= 0 || inner_size.1 <= 0 {
+            // Too small for anything but the outline
+            return;
+        }
+        draw_rounded_rectangle(
+            buffer,
+            (pos.0 + thickness, pos.1 + thickness),
+            inner_size,
+            (radius - thickness).max(0),
+            color,
+        );
+    }
+    /// Draw only the outline of a rectangle, for elements that draw their own content
+    ///
+    /// Corners are left square as the content below them isn't known
+    pub fn draw_border(
+        &self,
+        buffer: &mut Buffer,
+        pos: (isize, isize),
+        size: (isize, isize),
+    ) {
+        let thickness =
+            (self.border_thickness as isize).min(size.0 / 2).min(size.1 / 2);
+        if thickness <= 0 {
+            return;
+        }
+        for (edge_pos, edge_size) in [
+            (pos, (size.0, thickness)),
+            ((pos.0, pos.1 + size.1 - thickness), (size.0, thickness)),
+            (pos, (thickness, size.1)),
+            ((pos.0 + size.0 - thickness, pos.1), (thickness, size.1)),
+        ] {
+            render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                buffer,
+                edge_pos,
+                edge_size,
+                self.border_color,
+            );
+        }
+    }
+}
+
+fn draw_rounded_rectangle(
+    buffer: &mut Buffer,
+    pos: (isize, isize),
+    size: (isize, isize),
+    radius: isize,
+    color: u32,
+) {
+    let radius = radius.min(size.0 / 2).min(size.1 / 2);
+    if radius <= 0 {
+        render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+            buffer, pos, size, color,
+        );
+        return;
+    }
+    // A cross covering all but the corners, which are filled with circles
+    render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+        buffer,
+        (pos.0 + radius, pos.1),
+        (size.0 - radius * 2, size.1),
+        color,
+    );
+    render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+        buffer,
+        (pos.0, pos.1 + radius),
+        (size.0, size.1 - radius * 2),
+        color,
+    );
+    let left = pos.0 + radius;
+    let right = pos.0 + size.0 - 1 - radius;
+    let top = pos.1 + radius;
+    let bottom = pos.1 + size.1 - 1 - radius;
+    for center in [(left, top), (right, top), (left, bottom), (right, bottom)] {
+        render::draw_circle::<{ crate::DRAW_SAFE }, false>(
+            buffer, center, radius, color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILL: u32 = 1;
+
+    fn draw(theme: Theme, size: (usize, usize)) -> Buffer {
+        let mut buffer = Buffer::new_empty(size);
+        theme.draw_rectangle(
+            &mut buffer,
+            (0, 0),
+            (size.0 as isize, size.1 as isize),
+            FILL,
+        );
+        buffer
+    }
+
+    #[test]
+    fn thick_borders_leave_no_fill_in_small_rectangles() {
+        let theme = Theme::high_contrast().with_border_thickness(5);
+        for size in [(4, 4), (9, 4), (2, 2)] {
+            let buffer = draw(theme, size);
+            assert!(
+                buffer.data.iter().all(|pixel| *pixel == theme.border_color),
+                "{size:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn fill_is_inset_by_the_border() {
+        let theme = Theme::high_contrast().with_border_thickness(2);
+        let buffer = draw(theme, (6, 5));
+        let filled: Vec<usize> = buffer
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, pixel)| **pixel == FILL)
+            .map(|(idx, _)| idx)
+            .collect();
+        // Only the pixels (2..4, 2..3) are left for the fill
+        assert_eq!(filled, [2 * 6 + 2, 2 * 6 + 3]);
+    }
+}
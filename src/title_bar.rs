@@ -0,0 +1,48 @@
+use mirl::render::Buffer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// What is drawn on a [`TitleBarButton`]
+pub enum TitleBarIcon {
+    /// A short text like `?` drawn in the text color
+    Text(String),
+    /// An image drawn centered on the button
+    Image(std::sync::Arc<Buffer>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(unpredictable_function_pointer_comparisons)]
+/// A user defined button on the title bar of a window, placed left of the close and pin buttons
+///
+/// Clicking it calls the callback with the id of the window and adds a [`WindowEvent::TitleBarButtonClicked`](crate::WindowEvent::TitleBarButtonClicked) to the output
+pub struct TitleBarButton {
+    /// Identifies the button in the [`WindowEvent`](crate::WindowEvent)
+    pub id: String,
+    #[allow(missing_docs)]
+    pub icon: TitleBarIcon,
+    /// Called with the id of the window when the button has been clicked
+    pub callback: Option<fn(usize)>,
+}
+impl TitleBarButton {
+    #[allow(missing_docs)]
+    #[must_use]
+    pub fn new(id: &str, icon: TitleBarIcon) -> Self {
+        Self { id: id.to_string(), icon, callback: None }
+    }
+    /// Quick creation setter for the function called when the button has been clicked
+    #[must_use]
+    pub const fn with_callback(mut self, callback: fn(usize)) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A button on the title bar of a window, except for the collapse button
+pub enum TitleBarButtonKind {
+    /// Closes the window, see [`DearMirlGui::closable`](crate::DearMirlGui::closable)
+    Close,
+    /// Stops the window from being moved or resized, see [`DearMirlGui::pinnable`](crate::DearMirlGui::pinnable)
+    Pin,
+    /// The index of a [`TitleBarButton`] in [`DearMirlGui::title_bar_buttons`](crate::DearMirlGui::title_bar_buttons)
+    Custom(usize),
+}
@@ -0,0 +1,48 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Something that happened to a window, collected in [`GuiOutput::window_events`](crate::GuiOutput::window_events)
+///
+/// Every event holds the id of the window it happened to
+pub enum WindowEvent {
+    /// The window has been opened, see [`DearMirlGui::set_open`](crate::DearMirlGui::set_open)
+    Opened(usize),
+    /// The window has been closed using the close button or [`DearMirlGui::set_open`](crate::DearMirlGui::set_open)
+    Closed(usize),
+    /// The window has been moved while being updated, for example by dragging its title bar
+    Moved(usize),
+    /// The window has been resized while being updated, for example by dragging its edges
+    Resized(usize),
+    #[allow(missing_docs)]
+    Collapsed(usize),
+    /// The window is no longer collapsed
+    Expanded(usize),
+    /// The window has been pinned or unpinned using the pin button
+    Pinned {
+        /// The id of the window
+        window: usize,
+        /// If the window is pinned now
+        pinned: bool,
+    },
+    /// A custom [`TitleBarButton`](crate::TitleBarButton) has been clicked
+    TitleBarButtonClicked {
+        /// The id of the window
+        window: usize,
+        /// The id of the button
+        button: String,
+    },
+}
+impl WindowEvent {
+    /// The id of the window the event happened to
+    #[must_use]
+    pub const fn window(&self) -> usize {
+        match self {
+            Self::Opened(window)
+            | Self::Closed(window)
+            | Self::Moved(window)
+            | Self::Resized(window)
+            | Self::Collapsed(window)
+            | Self::Expanded(window)
+            | Self::Pinned { window, .. }
+            | Self::TitleBarButtonClicked { window, .. } => *window,
+        }
+    }
+}
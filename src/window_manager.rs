@@ -2,17 +2,22 @@ use mirl::{
     extensions::*,
     math::ConstZero,
     platform::{keycodes::KeyCode, mouse::MouseSnapShot},
-    render::Buffer,
+    render::{self, Buffer},
 };
 
 use crate::{
-    DearMirlGui, DearMirlGuiModule, FocusTaken, GuiOutput, ModuleUpdateInfo,
+    DearMirlGui, DearMirlGuiModule, DirtyRect, DockPosition, DockSpace,
+    FocusTaken, GuiOutput, LayoutSettings, Modal, ModalHandle,
+    ModuleUpdateInfo, WindowLayout, blend_buffer_area_on_buffer,
+    draw_buffer_area_on_buffer, get_uncovered_areas, merge_dirty_rects,
 };
 
 #[derive(Debug, Clone)]
 /// Handling a single window is easy but dealing with even just two can get annoying. Let this fella help ya
 pub struct DearMirlGuiManager<const FAST: bool, const USE_CACHE: bool> {
-    /// The locally stored windows sorted by z layering
+    /// The locally stored windows sorted by z layering, the first one is in front
+    ///
+    /// Windows are kept sorted by their [`WindowStacking`](crate::WindowStacking), the order inside a layer is preserved
     pub windows: Vec<DearMirlGui<FAST, USE_CACHE>>,
     /// If the left mouse button was pressed last frame
     pub last_left_mouse_down: bool,
@@ -24,10 +29,42 @@ pub struct DearMirlGuiManager<const FAST: bool, const USE_CACHE: bool> {
     pub last_mouse_pos: (f32, f32),
     /// If any window needs to be redrawn
     pub needs_redraw: bool,
+    /// Opened modals, the last one is on top and receives all input
+    pub modals: Vec<Modal<FAST, USE_CACHE>>,
+    /// The color drawn over everything below a modal
+    pub modal_dim_color: u32,
+    /// Windows docked to the edges of the buffer or each other
+    pub dock_space: DockSpace,
+    /// Where the shown windows have been drawn last, front to back
+    pub drawn_windows: Vec<(usize, DirtyRect)>,
+    /// Where the overlays have been drawn last
+    pub drawn_overlays: Vec<DirtyRect>,
+    /// The size of the buffer drawn on last
+    pub drawn_buffer_size: (usize, usize),
+    /// If the next [`draw_changed_on_buffer`](Self::draw_changed_on_buffer) has to redraw everything
+    pub full_redraw: bool,
+    /// The file the window layout is saved to whenever it changes, see [`with_layout_file`](Self::with_layout_file)
+    pub layout_file: Option<std::path::PathBuf>,
+    /// The layout as it has last been saved or loaded
+    pub saved_layout: Option<LayoutSettings>,
+    /// A changed layout that hasn't been saved yet and since when it is unchanged
+    pub pending_layout: Option<(LayoutSettings, std::time::Instant)>,
+    /// How long a changed layout has to stay unchanged before it is saved to the [`layout_file`](Self::layout_file), so scrolling and dragging don't write the file every update
+    pub layout_save_delay: std::time::Duration,
+    /// Why loading or automatically saving the layout failed last
+    pub layout_error: Option<String>,
+    /// The area windows are kept inside of while dragging and resizing, usually the size of the buffer
+    pub bounds: Option<DirtyRect>,
 }
 impl<const FAST: bool, const USE_CACHE: bool>
     DearMirlGuiManager<FAST, USE_CACHE>
 {
+    /// Half transparent black
+    pub const DEFAULT_MODAL_DIM_COLOR: u32 =
+        mirl::graphics::rgba_to_u32(0, 0, 0, 128);
+    /// One second
+    pub const DEFAULT_LAYOUT_SAVE_DELAY: std::time::Duration =
+        std::time::Duration::from_secs(1);
     /// Create a new window manager
     #[must_use]
     pub const fn new(windows: Vec<DearMirlGui<FAST, USE_CACHE>>) -> Self {
@@ -38,8 +75,346 @@ impl<const FAST: bool, const USE_CACHE: bool>
             last_right_mouse_down: false,
             last_mouse_pos: (0.0, 0.0),
             needs_redraw: true,
+            modals: Vec::new(),
+            modal_dim_color: Self::DEFAULT_MODAL_DIM_COLOR,
+            dock_space: DockSpace::new(),
+            drawn_windows: Vec::new(),
+            drawn_overlays: Vec::new(),
+            drawn_buffer_size: (0, 0),
+            full_redraw: true,
+            layout_file: None,
+            saved_layout: None,
+            pending_layout: None,
+            layout_save_delay: Self::DEFAULT_LAYOUT_SAVE_DELAY,
+            layout_error: None,
+            bounds: None,
         }
     }
+    /// Open a modal above all windows, it captures all input until it is closed
+    pub fn open_modal(&mut self, modal: Modal<FAST, USE_CACHE>) -> ModalHandle {
+        let handle = modal.handle.clone();
+        self.modals.push(modal);
+        self.needs_redraw = true;
+        self.full_redraw = true;
+        handle
+    }
+    /// Show some text with an OK button, see [`Modal::message_box`]
+    pub fn message_box(&mut self, title: &str, text: &str) -> ModalHandle {
+        self.open_modal(Modal::message_box(title, text))
+    }
+    /// Ask the user to confirm something, see [`Modal::confirm`]
+    pub fn confirm(&mut self, title: &str, text: &str) -> ModalHandle {
+        self.open_modal(Modal::confirm(title, text))
+    }
+    /// Load the window layout from the file if it exists and save it there whenever it changes
+    #[must_use]
+    pub fn with_layout_file(
+        mut self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> Self {
+        let path = path.into();
+        match self.load_layout(&path) {
+            Ok(()) => {}
+            // Nothing has been saved yet
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => self.layout_error = Some(error.to_string()),
+        }
+        self.layout_file = Some(path);
+        self
+    }
+    /// Quick creation setter for how long a changed layout has to stay unchanged before it is saved
+    #[must_use]
+    pub const fn with_layout_save_delay(
+        mut self,
+        delay: std::time::Duration,
+    ) -> Self {
+        self.layout_save_delay = delay;
+        self
+    }
+    /// Quick creation setter for the area windows are kept inside of
+    #[must_use]
+    pub fn with_bounds(mut self, bounds: DirtyRect) -> Self {
+        self.set_bounds(Some(bounds));
+        self
+    }
+    /// Set the area windows are kept inside of, windows that aren't docked are moved back inside of it
+    pub fn set_bounds(&mut self, bounds: Option<DirtyRect>) {
+        self.bounds = bounds;
+        for i in &mut self.windows {
+            if i.bounds == bounds {
+                continue;
+            }
+            i.bounds = bounds;
+            if !self.dock_space.is_docked(i.id) {
+                i.apply_size_constraints();
+                i.needs_redraw = true;
+                self.needs_redraw = true;
+            }
+        }
+    }
+    /// Get the state of all windows
+    #[must_use]
+    pub fn get_layout_settings(&self) -> LayoutSettings {
+        LayoutSettings {
+            windows: LayoutSettings::get_keys(&self.windows)
+                .into_iter()
+                .zip(&self.windows)
+                .enumerate()
+                .map(|(z, (key, i))| (key, WindowLayout::of_window(i, z)))
+                .collect(),
+        }
+    }
+    /// Restore the state of all windows with a saved layout, windows without one are put in front
+    ///
+    /// Docked windows keep the position and size of their dock node
+    pub fn apply_layout_settings(&mut self, settings: &LayoutSettings) {
+        let keys = LayoutSettings::get_keys(&self.windows);
+        let mut z_order = std::collections::HashMap::new();
+        for (key, i) in keys.iter().zip(&mut self.windows) {
+            let layout = settings.get(key);
+            z_order.insert(i.id, layout.map(|layout| layout.z));
+            if let Some(layout) = layout
+                && !self.dock_space.is_docked(i.id)
+            {
+                layout.apply(i);
+            }
+        }
+        self.windows.sort_by_key(|i| z_order.get(&i.id).copied().flatten());
+        self.sort_by_stacking();
+        self.needs_redraw = true;
+        self.full_redraw = true;
+    }
+    /// Save the state of all windows to a file
+    ///
+    /// # Errors
+    /// When the file cannot be written
+    pub fn save_layout(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<()> {
+        let settings = self.get_layout_settings();
+        std::fs::write(path, settings.to_ini_string())?;
+        self.saved_layout = Some(settings);
+        Ok(())
+    }
+    /// Restore the state of all windows from a file
+    ///
+    /// # Errors
+    /// When the file cannot be read
+    pub fn load_layout(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<()> {
+        let settings =
+            LayoutSettings::from_ini_str(&std::fs::read_to_string(path)?);
+        self.apply_layout_settings(&settings);
+        self.saved_layout = Some(self.get_layout_settings());
+        Ok(())
+    }
+    /// Save the layout to the [`layout_file`](Self::layout_file) once it changed and then stayed the same for the [`layout_save_delay`](Self::layout_save_delay) while no window is being moved or resized
+    fn auto_save_layout(&mut self) {
+        let Some(path) = self.layout_file.clone() else {
+            return;
+        };
+        if self.windows.iter().any(|i| i.dragging || i.resizing) {
+            return;
+        }
+        let layout = self.get_layout_settings();
+        if self.saved_layout.as_ref() == Some(&layout) {
+            self.pending_layout = None;
+            return;
+        }
+        let unchanged_since = match &self.pending_layout {
+            Some((pending, since)) if *pending == layout => *since,
+            _ => {
+                self.pending_layout = Some((layout, std::time::Instant::now()));
+                return;
+            }
+        };
+        // Still scrolling, auto sizing... wait until it settles
+        if unchanged_since.elapsed() < self.layout_save_delay {
+            return;
+        }
+        self.pending_layout = None;
+        if let Err(error) = self.save_layout(path) {
+            self.layout_error = Some(error.to_string());
+            // Don't retry every frame
+            self.saved_layout = Some(self.get_layout_settings());
+        }
+    }
+    /// Get the window with the given id
+    #[must_use]
+    pub fn get_window(
+        &self,
+        id: usize,
+    ) -> Option<&DearMirlGui<FAST, USE_CACHE>> {
+        self.windows.iter().find(|i| i.id == id)
+    }
+    /// Get the window with the given id
+    #[must_use]
+    pub fn get_window_mut(
+        &mut self,
+        id: usize,
+    ) -> Option<&mut DearMirlGui<FAST, USE_CACHE>> {
+        self.windows.iter_mut().find(|i| i.id == id)
+    }
+    /// Get the frontmost window with the given title
+    #[must_use]
+    pub fn get_window_by_title(
+        &self,
+        title: &str,
+    ) -> Option<&DearMirlGui<FAST, USE_CACHE>> {
+        self.windows.iter().find(|i| i.title == title)
+    }
+    /// Get the frontmost window with the given title
+    #[must_use]
+    pub fn get_window_by_title_mut(
+        &mut self,
+        title: &str,
+    ) -> Option<&mut DearMirlGui<FAST, USE_CACHE>> {
+        self.windows.iter_mut().find(|i| i.title == title)
+    }
+    /// Move the window with the given id in front of all other windows of its layer
+    ///
+    /// Returns false if there is no window with that id
+    pub fn bring_to_front(&mut self, id: usize) -> bool {
+        let Some(idx) = self.windows.iter().position(|i| i.id == id) else {
+            return false;
+        };
+        let window = self.windows.remove(idx);
+        self.windows.insert(0, window);
+        self.sort_by_stacking();
+        true
+    }
+    /// Move the window with the given id behind all other windows of its layer
+    ///
+    /// Returns false if there is no window with that id
+    pub fn send_to_back(&mut self, id: usize) -> bool {
+        let Some(idx) = self.windows.iter().position(|i| i.id == id) else {
+            return false;
+        };
+        let window = self.windows.remove(idx);
+        self.windows.push(window);
+        self.sort_by_stacking();
+        true
+    }
+    /// Sort the windows by their [`WindowStacking`](crate::WindowStacking) while keeping the order inside each layer
+    pub fn sort_by_stacking(&mut self) {
+        if !self.windows.is_sorted_by_key(|i| i.stacking) {
+            self.windows.sort_by_key(|i| i.stacking);
+            self.needs_redraw = true;
+        }
+    }
+    /// Dock the window with the given id next to the target window (None = the edges of the buffer), a floating target window is docked together with it
+    ///
+    /// Returns false if there is no window with one of the ids
+    pub fn dock(
+        &mut self,
+        window: usize,
+        target: Option<usize>,
+        position: DockPosition,
+    ) -> bool {
+        if !self.dock_space.dock_by_id(
+            &mut self.windows,
+            window,
+            target,
+            position,
+        ) {
+            return false;
+        }
+        self.dock_space.apply_layout(&mut self.windows);
+        self.needs_redraw = true;
+        self.full_redraw = true;
+        true
+    }
+    /// Undock the window with the given id, returns false if there is no window with that id
+    pub fn undock(&mut self, window: usize) -> bool {
+        let Some(window) = self.windows.iter_mut().find(|i| i.id == window)
+        else {
+            return false;
+        };
+        self.dock_space.undock(window);
+        self.dock_space.apply_layout(&mut self.windows);
+        self.needs_redraw = true;
+        self.full_redraw = true;
+        true
+    }
+    /// Open the window with the given id and move it to the front
+    ///
+    /// Returns false if there is no window with that id
+    pub fn open_window(&mut self, id: usize) -> bool {
+        let Some(window) = self.get_window_mut(id) else {
+            return false;
+        };
+        window.set_open(true);
+        self.bring_to_front(id);
+        self.needs_redraw = true;
+        true
+    }
+    /// Close the window with the given id, it is undocked on the next update
+    ///
+    /// Returns false if there is no window with that id
+    pub fn close_window(&mut self, id: usize) -> bool {
+        let Some(window) = self.get_window_mut(id) else {
+            return false;
+        };
+        window.set_open(false);
+        self.needs_redraw = true;
+        true
+    }
+    /// Closed windows don't keep their place in the dock space
+    fn undock_closed_windows(&mut self, output: &GuiOutput) {
+        for event in &output.window_events {
+            if let crate::WindowEvent::Closed(id) = event
+                && self.dock_space.is_docked(*id)
+            {
+                self.undock(*id);
+            }
+        }
+    }
+    /// Update the modal on top, returns None when no modal is open
+    fn update_modal(
+        &mut self,
+        inputs: &crate::ModuleUpdateInfo,
+    ) -> Option<GuiOutput> {
+        let modal = self.modals.last_mut()?;
+        let mut input = *inputs;
+        input.container_id = modal.window.id;
+        let mut output = modal.window.update_overlays(input);
+        input.focus_taken |= output.focus_taken;
+        output |= modal.window.update_using_module_data(input, &output);
+        self.needs_redraw |= modal.window.need_redraw();
+
+        let closed = modal.update_result(inputs.pressed_keys);
+        if closed {
+            modal.remove_owned_modules();
+            self.modals.pop();
+            self.needs_redraw = true;
+            self.full_redraw = true;
+        }
+        // Nothing below a modal may react to the input
+        let no_keys = Vec::new();
+        let mut input = *inputs;
+        input.focus_taken = FocusTaken::FunctionallyTaken;
+        input.mouse_pos = None;
+        input.real_mouse_pos = None;
+        input.pressed_keys = &no_keys;
+        for modal in self.modals.iter_mut().rev().skip(usize::from(!closed)) {
+            input.container_id = modal.window.id;
+            let _ = modal.window.update_using_module_data(input, &output);
+        }
+        let mut window_events = Vec::new();
+        for window in &mut self.windows {
+            input.container_id = window.id;
+            // The windows below can't be interacted with but may still be closed or moved from code
+            window_events.extend(
+                window.update_using_module_data(input, &output).window_events,
+            );
+        }
+        output.window_events.extend(window_events);
+        self.undock_closed_windows(&output);
+        Some(output.set_focus_to(FocusTaken::FunctionallyTaken))
+    }
     /// Update all windows inside
     #[must_use]
     #[allow(clippy::too_many_arguments, clippy::too_many_lines)] // Well, clippy... it's just big. :(
@@ -98,9 +473,46 @@ impl<const FAST: bool, const USE_CACHE: bool>
             self.last_right_mouse_down = mouse_data.right.down;
             self.last_middle_mouse_down = mouse_data.middle.down;
 
+            if let Some(output) = self.update_modal(inputs) {
+                return output;
+            }
+            // The stacking of a window may have been changed directly
+            self.sort_by_stacking();
+            // Windows may have been added since the bounds were set
+            if self.bounds.is_some() {
+                self.set_bounds(self.bounds);
+            }
+
             let mut input = *inputs;
+            // Overlays are drawn above every window so they get the input first
+            for i in &mut self.windows {
+                if !i.open || self.dock_space.is_hidden(i.id) {
+                    continue;
+                }
+                input.container_id = i.id;
+                let o = i.update_overlays(input);
+                input.focus_taken |= o.focus_taken;
+                output |= o;
+            }
+            if input.focus_taken.is_focus_taken() {
+                self.needs_redraw = true;
+            }
+            let root = self.dock_space.root.clone();
+            let drop_target = self.dock_space.drop_target;
+            // Tab bars and splitters aren't covered by the docked windows
+            let o = self.dock_space.update(&input, &self.windows);
+            if o.focus_taken == FocusTaken::FunctionallyTaken {
+                self.needs_redraw = true;
+            }
+            input.focus_taken |= o.focus_taken;
+            output |= o;
             let mut to_switch = None;
             for (idx, i) in self.windows.iter_mut().enumerate() {
+                if !i.open || self.dock_space.is_hidden(i.id) {
+                    // Closed windows still report being closed
+                    output.window_events.append(&mut i.events);
+                    continue;
+                }
                 input.container_id = i.id;
                 let o = i.update_using_module_data(input, &output);
                 self.needs_redraw |= i.need_redraw();
@@ -121,8 +533,23 @@ impl<const FAST: bool, const USE_CACHE: bool>
                 output |= o;
             }
             if let Some(idx) = to_switch {
-                self.windows.swap(0, idx);
+                if idx != 0 {
+                    self.needs_redraw = true;
+                }
+                let id = self.windows[idx].id;
+                self.bring_to_front(id);
             }
+            self.undock_closed_windows(&output);
+            self.dock_space.update_dragging(&input, &mut self.windows);
+            self.dock_space.apply_layout(&mut self.windows);
+            // Tab bars, splitters and the drop preview lie outside of the windows
+            if (&root, drop_target)
+                != (&self.dock_space.root, self.dock_space.drop_target)
+            {
+                self.needs_redraw = true;
+                self.full_redraw = true;
+            }
+            self.auto_save_layout();
             output
         }
     }
@@ -133,13 +560,266 @@ impl<const FAST: bool, const USE_CACHE: bool>
         }
     }
     /// Draw all windows on the buffer
+    ///
+    /// Only the visible parts of windows are drawn, windows covered by opaque ones aren't rendered at all
     pub fn draw_on_buffer(&mut self, buffer: &mut Buffer) {
-        for i in self.windows.iter_mut().rev() {
-            // TODO: Is there any way of skipping overdraw on overlapping windows?
-            i.draw_on_buffer(buffer);
+        self.set_dock_space_size(buffer);
+        let overlays = self.get_overlays();
+        self.draw_everything(buffer, &overlays);
+    }
+    fn draw_everything(
+        &mut self,
+        buffer: &mut Buffer,
+        overlays: &[(Buffer, (isize, isize))],
+    ) {
+        self.dock_space.draw(buffer, &self.windows);
+        self.draw_windows(
+            buffer,
+            &[DirtyRect::new((0, 0), (buffer.width, buffer.height))],
+        );
+        self.dock_space.draw_drop_preview(buffer);
+        for (overlay, position) in overlays {
+            render::draw_buffer_on_buffer::<true, true, true, false>(
+                buffer, overlay, *position,
+            );
         }
+        self.draw_modals(buffer);
+        self.remember_drawn(buffer, overlays);
+    }
+    /// Only redraw what changed since the last draw, returns the changed areas so only those have to be presented
+    ///
+    /// The changed areas are cleared with the background color first, the rest of the buffer has to be kept as it was drawn last
+    pub fn draw_changed_on_buffer(
+        &mut self,
+        buffer: &mut Buffer,
+        background_color: u32,
+    ) -> Vec<DirtyRect> {
+        self.set_dock_space_size(buffer);
+        let screen = DirtyRect::new((0, 0), (buffer.width, buffer.height));
+        let overlays = self.get_overlays();
+        let dirty = self.get_dirty_rects(screen, &overlays);
+        if dirty.contains(&screen) {
+            buffer.clear_buffer_with_color(background_color);
+            self.draw_everything(buffer, &overlays);
+            return dirty;
+        }
+        for area in &dirty {
+            if self.dock_space.root.is_some() {
+                // Drawn onto a layer to keep the tab bars inside the area
+                let mut layer = Buffer::new_empty_with_color(
+                    (area.width, area.height),
+                    background_color,
+                );
+                let mut dock_space = self.dock_space.clone();
+                dock_space.position =
+                    dock_space.position.sub((area.x as f32, area.y as f32));
+                dock_space.draw(&mut layer, &self.windows);
+                draw_buffer_area_on_buffer(
+                    buffer,
+                    &layer,
+                    (area.x, area.y),
+                    *area,
+                );
+            } else {
+                render::draw_rectangle::<{ crate::DRAW_SAFE }>(
+                    buffer,
+                    (area.x, area.y),
+                    (area.width as isize, area.height as isize),
+                    background_color,
+                );
+            }
+        }
+        self.draw_windows(buffer, &dirty);
+        // Every overlay lies completely within the changed areas
+        for (overlay, position) in &overlays {
+            render::draw_buffer_on_buffer::<true, true, true, false>(
+                buffer, overlay, *position,
+            );
+        }
+        self.remember_drawn(buffer, &overlays);
+        dirty
+    }
+    fn set_dock_space_size(&mut self, buffer: &Buffer) {
+        let size = (buffer.width as f32, buffer.height as f32);
+        if self.dock_space.size != size {
+            self.dock_space.size = size;
+            self.dock_space.apply_layout(&mut self.windows);
+        }
+    }
+    fn get_overlays(&self) -> Vec<(Buffer, (isize, isize))> {
+        self.windows
+            .iter()
+            .rev()
+            .filter(|i| i.open && !self.dock_space.is_hidden(i.id))
+            .flat_map(DearMirlGui::<FAST, USE_CACHE>::draw_overlays)
+            .collect()
+    }
+    /// The shown windows and where they are on screen, front to back
+    fn get_window_areas(&self) -> Vec<(usize, DirtyRect)> {
+        self.windows
+            .iter()
+            .filter(|i| i.open && !self.dock_space.is_hidden(i.id))
+            .map(|i| (i.id, DirtyRect::of_window(i)))
+            .collect()
+    }
+    /// Draw the parts of the windows inside the areas that aren't covered by other windows, every window is rendered at most once
+    fn draw_windows(&mut self, buffer: &mut Buffer, areas: &[DirtyRect]) {
+        let mut covering = Vec::new();
+        let mut to_draw = Vec::new();
+        // Nothing behind the uncovered parts of opaque windows has to be drawn
+        for (idx, i) in self.windows.iter().enumerate() {
+            if !i.open || self.dock_space.is_hidden(i.id) {
+                continue;
+            }
+            let window_area = DirtyRect::of_window(i);
+            let visible: Vec<DirtyRect> = areas
+                .iter()
+                .filter_map(|area| window_area.intersection(area))
+                .flat_map(|visible| get_uncovered_areas(visible, &covering))
+                .collect();
+            let opaque = i.is_opaque();
+            if !visible.is_empty() {
+                to_draw.push((idx, visible, opaque));
+            }
+            if opaque {
+                covering.push(window_area);
+            }
+        }
+        // Back to front so translucent windows are blended onto the ones behind them
+        for (idx, visible, opaque) in to_draw.into_iter().rev() {
+            let window = &mut self.windows[idx];
+            let rendered = window.render();
+            let position = (window.x as isize, window.y as isize);
+            for part in visible {
+                if opaque {
+                    draw_buffer_area_on_buffer(
+                        buffer, &rendered, position, part,
+                    );
+                } else {
+                    blend_buffer_area_on_buffer(
+                        buffer, &rendered, position, part,
+                    );
+                }
+            }
+        }
+    }
+    /// Get the areas that changed since the last draw, merged and inside the screen
+    fn get_dirty_rects(
+        &self,
+        screen: DirtyRect,
+        overlays: &[(Buffer, (isize, isize))],
+    ) -> Vec<DirtyRect> {
+        // Modals dim everything and the drop preview follows the mouse
+        if self.full_redraw
+            || !self.modals.is_empty()
+            || self.dock_space.drop_target.is_some()
+            || self.drawn_buffer_size != (screen.width, screen.height)
+        {
+            return Vec::from([screen]);
+        }
+        let mut dirty = Vec::new();
+        let mut covering: Vec<(usize, DirtyRect)> = Vec::new();
+        for (id, area) in self.get_window_areas() {
+            let previous = self.drawn_windows.iter().position(|x| x.0 == id);
+            match previous {
+                Some(idx) if self.drawn_windows[idx].1 == area => {
+                    let mut in_front: Vec<usize> =
+                        covering.iter().map(|x| x.0).collect();
+                    let mut was_in_front: Vec<usize> =
+                        self.drawn_windows[..idx].iter().map(|x| x.0).collect();
+                    in_front.sort_unstable();
+                    was_in_front.sort_unstable();
+                    if in_front != was_in_front {
+                        dirty.push(area);
+                    } else if self
+                        .windows
+                        .iter()
+                        .any(|i| i.id == id && i.needs_redraw)
+                    {
+                        // Translucent windows show the changes behind them
+                        let covering: Vec<DirtyRect> = covering
+                            .iter()
+                            .filter(|x| {
+                                self.windows
+                                    .iter()
+                                    .any(|i| i.id == x.0 && i.is_opaque())
+                            })
+                            .map(|x| x.1)
+                            .collect();
+                        dirty.extend(get_uncovered_areas(area, &covering));
+                    }
+                }
+                Some(idx) => {
+                    dirty.push(self.drawn_windows[idx].1);
+                    dirty.push(area);
+                }
+                None => dirty.push(area),
+            }
+            covering.push((id, area));
+        }
+        // Closed or hidden windows
+        for (id, area) in &self.drawn_windows {
+            if !covering.iter().any(|x| x.0 == *id) {
+                dirty.push(*area);
+            }
+        }
+        dirty.extend(self.drawn_overlays.iter().copied());
+        dirty.extend(overlays.iter().map(|(overlay, position)| {
+            DirtyRect::new(*position, (overlay.width, overlay.height))
+        }));
+        merge_dirty_rects(
+            dirty
+                .iter()
+                .filter_map(|area| area.intersection(&screen))
+                .collect(),
+        )
+    }
+    fn remember_drawn(
+        &mut self,
+        buffer: &Buffer,
+        overlays: &[(Buffer, (isize, isize))],
+    ) {
+        self.drawn_windows = self.get_window_areas();
+        self.drawn_overlays = overlays
+            .iter()
+            .map(|(overlay, position)| {
+                DirtyRect::new(*position, (overlay.width, overlay.height))
+            })
+            .collect();
+        self.drawn_buffer_size = (buffer.width, buffer.height);
+        self.full_redraw = false;
         self.needs_redraw = false;
     }
+    /// Draw the modals above everything, each dimming what is below it
+    fn draw_modals(&mut self, buffer: &mut Buffer) {
+        if self.modals.is_empty() {
+            return;
+        }
+        let dim = Buffer::new_empty_with_color(
+            (buffer.width, buffer.height),
+            self.modal_dim_color,
+        );
+        for modal in &mut self.modals {
+            render::draw_buffer_on_buffer::<true, true, true, false>(
+                buffer,
+                &dim,
+                (0, 0),
+            );
+            // Modals stay in the center, even when the buffer is resized
+            modal.window.x =
+                (buffer.width.saturating_sub(modal.window.get_width()) / 2)
+                    as crate::DearMirlGuiCoordinateType;
+            modal.window.y =
+                (buffer.height.saturating_sub(modal.window.get_height()) / 2)
+                    as crate::DearMirlGuiCoordinateType;
+            modal.window.draw_on_buffer(buffer);
+            for (overlay, position) in modal.window.draw_overlays() {
+                render::draw_buffer_on_buffer::<true, true, true, false>(
+                    buffer, &overlay, position,
+                );
+            }
+        }
+    }
     fn get_bounds(
         &self,
     ) -> (